@@ -1,21 +1,27 @@
 //! Partial implementation of the `Accounts` namespace.
 
 use crate::api::{Namespace, Web3};
+use crate::confirm::{send_raw_transaction_with_confirmation, SendTransactionWithConfirmation};
 use crate::error::Error;
 use crate::helpers::CallFuture;
+use crate::signing::{self, keccak256, public_key_address};
 use crate::types::{
-    Address, Bytes, Recovery, RecoveryMessage, SignedData, SignedTransaction, TransactionParameters, H256, U256,
+    AccessList, AccessListItem, Address, Block, BlockId, BlockNumber, Bytes, DecodedTransaction, Recovery,
+    RecoveryMessage, SignedData, SignedTransaction, TransactionParameters, TransactionReceipt, ValidationError,
+    ACCESS_LIST_ADDRESS_COST, ACCESS_LIST_STORAGE_KEY_COST, H256, U256,
 };
 use crate::Transport;
-use futures::future::{self, Either, FutureResult, Join3};
+use futures::future::{self, Either, FutureResult, Join4};
 use futures::{Async, Future, Poll};
-use rlp::RlpStream;
+use rlp::{DecoderError, Rlp, RlpStream};
+use rustc_hex::FromHex;
 use secp256k1::key::ONE_KEY;
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
 use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
 use std::convert::TryInto;
 use std::mem;
 use std::ops::Deref;
-use tiny_keccak::{Hasher, Keccak};
+use std::time::Duration;
 use zeroize::{DefaultIsZeroes, Zeroize};
 
 /// `Accounts` namespace
@@ -48,6 +54,37 @@ impl<T: Transport> Accounts<T> {
         SignTransactionFuture::new(self, tx, key)
     }
 
+    /// Signs an Ethereum transaction with a given private key, submits it with
+    /// `eth_sendRawTransaction` and waits for `confirmations`.
+    ///
+    /// Missing `nonce`, `gas_price` and `chain_id` are filled in from the node
+    /// before signing. The nonce is taken from the pending state, so transactions sent
+    /// back to back get consecutive nonces. The node is polled for new blocks every `poll_interval`.
+    ///
+    /// Transferring value to the zero address fails with `Error::ZeroAddressRecipient`
+    /// unless `allow_zero_recipient` is set on the transaction.
+    pub fn send(
+        &self,
+        tx: TransactionParameters,
+        key: &SecretKey,
+        poll_interval: Duration,
+        confirmations: usize,
+    ) -> SendTransactionFuture<T> {
+        let burns_value = tx.to == Some(Address::zero()) && !tx.value.is_zero();
+        let state = if burns_value && !tx.allow_zero_recipient {
+            SendTransactionState::Rejected(Some(Error::ZeroAddressRecipient))
+        } else {
+            SendTransactionState::Signing(Box::new(self.sign_transaction(tx, key)))
+        };
+
+        SendTransactionFuture {
+            transport: self.transport.clone(),
+            poll_interval,
+            confirmations,
+            state,
+        }
+    }
+
     /// Hash a message according to EIP-191.
     ///
     /// The data is a UTF-8 encoded string and will enveloped as follows:
@@ -57,12 +94,7 @@ impl<T: Transport> Accounts<T> {
     where
         S: AsRef<[u8]>,
     {
-        let message = message.as_ref();
-
-        let mut eth_message = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
-        eth_message.extend_from_slice(message);
-
-        keccak256(&eth_message).into()
+        signing::hash_message(message.as_ref())
     }
 
     /// Sign arbitrary string data.
@@ -127,15 +159,228 @@ impl<T: Transport> Accounts<T> {
 
         Ok(public_key_address(&public_key))
     }
+
+    /// Recovers the sender of a raw signed transaction.
+    ///
+    /// Legacy transactions (`v` is `27` or `28`) are signed over the 6-element
+    /// RLP encoding, while EIP-155 transactions are signed over the 9-element
+    /// encoding including the chain ID derived from `v`.
+    pub fn recover_sender<B>(&self, raw_transaction: B) -> Result<Address, Error>
+    where
+        B: AsRef<[u8]>,
+    {
+        decode_raw_transaction(raw_transaction.as_ref()).map(|tx| tx.from)
+    }
 }
 
-/// Compute the Keccak-256 hash of input bytes.
-pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
-    let mut output = [0u8; 32];
-    let mut hasher = Keccak::v256();
-    hasher.update(bytes);
-    hasher.finalize(&mut output);
-    output
+/// Decodes a hex encoded raw signed transaction and recovers its sender.
+///
+/// Legacy (with or without EIP-155 replay protection), EIP-2930 (`0x01`) and
+/// EIP-1559 (`0x02`) transactions are supported.
+pub fn decode_transaction(hex: &str) -> Result<DecodedTransaction, Error> {
+    let raw: Vec<u8> = hex
+        .trim_start_matches("0x")
+        .from_hex()
+        .map_err(|err| Error::Decoder(format!("{:?}", err)))?;
+    decode_raw_transaction(&raw)
+}
+
+/// Decodes a raw signed transaction and recovers its sender.
+///
+/// Input starting with a byte in `0x00..=0x7f` is an EIP-2718 typed transaction
+/// envelope, anything else is decoded as a legacy RLP list. The `hash` of the
+/// decoded transaction is computed from the exact input bytes.
+pub fn decode_raw_transaction(raw: &[u8]) -> Result<DecodedTransaction, Error> {
+    match raw.first() {
+        None => Err(Error::Decoder("Empty transaction".into())),
+        // typed transaction envelopes start with a byte in `0x00..=0x7f` (EIP-2718)
+        Some(&transaction_type) if transaction_type <= 0x7f => decode_typed_transaction(transaction_type, raw),
+        Some(_) => {
+            let (tx, signature) = Transaction::decode_signed(raw)?;
+            let chain_id = Recovery::new(H256::zero(), signature.v, signature.r, signature.s).chain_id();
+            let recovery_id = Recovery::new(H256::zero(), signature.v, signature.r, signature.s).recovery_id()?;
+            let from = recover_hash(tx.signing_hash(chain_id), recovery_id, signature.r, signature.s)?;
+
+            Ok(DecodedTransaction {
+                transaction_type: None,
+                hash: keccak256(raw).into(),
+                from,
+                to: tx.to,
+                nonce: tx.nonce,
+                gas: tx.gas,
+                gas_price: Some(tx.gas_price),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                value: tx.value,
+                data: tx.data.into(),
+                access_list: None,
+                chain_id,
+                v: signature.v,
+                r: signature.r,
+                s: signature.s,
+            })
+        }
+    }
+}
+
+/// Validates a raw signed transaction before broadcasting it to the network with `chain_id`.
+///
+/// Besides decoding the transaction and recovering its sender, requires EIP-155 replay
+/// protection for `chain_id` and the supplied gas to cover the intrinsic gas. All problems
+/// found are returned.
+pub fn validate_raw_transaction(raw: &[u8], chain_id: u64) -> Result<DecodedTransaction, Vec<ValidationError>> {
+    let tx = decode_raw_transaction(raw).map_err(|err| match err {
+        Error::Signing(err) => vec![ValidationError::InvalidSignature(err.to_string())],
+        err => vec![ValidationError::InvalidEncoding(err.to_string())],
+    })?;
+
+    let mut errors = vec![];
+    match tx.chain_id {
+        None => errors.push(ValidationError::MissingChainId),
+        Some(actual) if actual != chain_id => errors.push(ValidationError::ChainIdMismatch {
+            expected: chain_id,
+            actual,
+        }),
+        Some(_) => {}
+    }
+    let required = intrinsic_gas(&tx);
+    if tx.gas < required {
+        errors.push(ValidationError::InsufficientGas {
+            required,
+            provided: tx.gas,
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(tx)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Gas charged for a transaction before any code is executed.
+///
+/// Includes the access list costs (EIP-2930) and, for contract creations, the
+/// initcode word cost (EIP-3860).
+fn intrinsic_gas(tx: &DecodedTransaction) -> U256 {
+    let data: u64 = tx.data.0.iter().map(|&byte| if byte == 0 { 4 } else { 16 }).sum();
+    let base = if tx.to.is_none() {
+        let initcode_words = (tx.data.0.len() as u64).div_ceil(32);
+        53_000 + 2 * initcode_words
+    } else {
+        21_000
+    };
+    let access_list: u64 = tx
+        .access_list
+        .iter()
+        .flatten()
+        .map(|item| ACCESS_LIST_ADDRESS_COST + ACCESS_LIST_STORAGE_KEY_COST * item.storage_keys.len() as u64)
+        .sum();
+    U256::from(base + data + access_list)
+}
+
+/// Decodes an EIP-2718 typed transaction envelope.
+///
+/// The payload is `[chainId, nonce, gasPrice, gas, to, value, data, accessList, yParity, r, s]`
+/// for EIP-2930 transactions, EIP-1559 transactions replace `gasPrice` with
+/// `maxPriorityFeePerGas` and `maxFeePerGas`.
+fn decode_typed_transaction(transaction_type: u8, raw: &[u8]) -> Result<DecodedTransaction, Error> {
+    let fee_fields = match transaction_type {
+        1 => 1,
+        2 => 2,
+        other => return Err(Error::Decoder(format!("Unsupported transaction type: {}", other))),
+    };
+
+    let rlp = Rlp::new(&raw[1..]);
+    let item_count = rlp.item_count()?;
+    if item_count != 10 + fee_fields {
+        return Err(DecoderError::RlpIncorrectListLen.into());
+    }
+
+    // the signature covers the transaction type and all fields but the signature itself
+    let mut unsigned = RlpStream::new_list(item_count - 3);
+    for i in 0..item_count - 3 {
+        unsigned.append_raw(rlp.at(i)?.as_raw(), 1);
+    }
+    let mut payload = vec![transaction_type];
+    payload.extend_from_slice(&unsigned.out());
+
+    let (gas_price, max_priority_fee_per_gas, max_fee_per_gas) = if fee_fields == 1 {
+        (Some(rlp.val_at(2)?), None, None)
+    } else {
+        (None, Some(rlp.val_at(2)?), Some(rlp.val_at(3)?))
+    };
+    let offset = 2 + fee_fields;
+
+    let y_parity: u64 = rlp.val_at(item_count - 3)?;
+    if y_parity > 1 {
+        return Err(Error::Decoder(format!("Invalid yParity: {}", y_parity)));
+    }
+    let r = decode_h256(&rlp.at(item_count - 2)?)?;
+    let s = decode_h256(&rlp.at(item_count - 1)?)?;
+    let from = recover_hash(keccak256(&payload).into(), RecoveryId::from_i32(y_parity as i32)?, r, s)?;
+
+    Ok(DecodedTransaction {
+        transaction_type: Some(transaction_type),
+        hash: keccak256(raw).into(),
+        from,
+        to: decode_to(&rlp.at(offset + 1)?)?,
+        nonce: rlp.val_at(1)?,
+        gas: rlp.val_at(offset)?,
+        gas_price,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        value: rlp.val_at(offset + 2)?,
+        data: rlp.val_at::<Vec<u8>>(offset + 3)?.into(),
+        access_list: Some(decode_access_list(&rlp.at(offset + 4)?)?),
+        chain_id: Some(rlp.val_at(0)?),
+        v: y_parity,
+        r,
+        s,
+    })
+}
+
+/// Decodes an access list, a list of `[address, [storageKey, ...]]` items.
+fn decode_access_list(rlp: &Rlp) -> Result<AccessList, DecoderError> {
+    rlp.iter()
+        .map(|item| {
+            if item.item_count()? != 2 {
+                return Err(DecoderError::RlpIncorrectListLen);
+            }
+            Ok(AccessListItem {
+                address: item.val_at(0)?,
+                storage_keys: item.list_at(1)?,
+            })
+        })
+        .collect()
+}
+
+/// Decodes a transaction recipient, an empty value means contract creation.
+fn decode_to(rlp: &Rlp) -> Result<Option<Address>, DecoderError> {
+    if rlp.is_empty() {
+        Ok(None)
+    } else {
+        rlp.as_val().map(Some)
+    }
+}
+
+/// Decodes a signature value, which is RLP encoded as an integer without leading zeros.
+fn decode_h256(rlp: &Rlp) -> Result<H256, DecoderError> {
+    let mut hash = H256::zero();
+    rlp.as_val::<U256>()?.to_big_endian(hash.as_bytes_mut());
+    Ok(hash)
+}
+
+/// Recovers the address which signed the given message hash.
+fn recover_hash(message_hash: H256, recovery_id: RecoveryId, r: H256, s: H256) -> Result<Address, Error> {
+    let mut compact = [0u8; 64];
+    compact[..32].copy_from_slice(r.as_bytes());
+    compact[32..].copy_from_slice(s.as_bytes());
+    let signature = RecoverableSignature::from_compact(&compact, recovery_id)?;
+    let message = Message::from_slice(message_hash.as_bytes())?;
+    let public_key = Secp256k1::verification_only().recover(&message, &signature)?;
+
+    Ok(public_key_address(&public_key))
 }
 
 /// Gets the public address of a private key.
@@ -145,30 +390,15 @@ fn secret_key_address(key: &SecretKey) -> Address {
     public_key_address(&public_key)
 }
 
-/// Gets the address of a public key.
-///
-/// The public address is defined as the low 20 bytes of the keccak hash of
-/// the public key. Note that the public key returned from the `secp256k1`
-/// crate is 65 bytes long, that is because it is prefixed by `0x04` to
-/// indicate an uncompressed public key; this first byte is ignored when
-/// computing the hash.
-fn public_key_address(public_key: &PublicKey) -> Address {
-    let public_key = public_key.serialize_uncompressed();
-
-    debug_assert_eq!(public_key[0], 0x04);
-    let hash = keccak256(&public_key[1..]);
-
-    Address::from_slice(&hash[12..])
-}
-
 type MaybeReady<T, R> = Either<FutureResult<R, Error>, CallFuture<R, <T as Transport>::Out>>;
 
-type TxParams<T> = Join3<MaybeReady<T, U256>, MaybeReady<T, U256>, MaybeReady<T, U256>>;
+type TxParams<T> =
+    Join4<MaybeReady<T, U256>, MaybeReady<T, U256>, MaybeReady<T, U256>, MaybeReady<T, Option<Block<H256>>>>;
 
 /// Future resolving when transaction signing is complete.
 ///
 /// Transaction signing can perform RPC requests in order to fill missing
-/// parameters required for signing `nonce`, `gas_price` and `chain_id`. Note
+/// parameters required for signing `nonce`, the fees and `chain_id`. Note
 /// that if all transaction parameters were provided, this future will resolve
 /// immediately.
 pub struct SignTransactionFuture<T: Transport> {
@@ -190,10 +420,30 @@ impl<T: Transport> SignTransactionFuture<T> {
         }
 
         let from = secret_key_address(key);
-        let inner = Future::join3(
-            maybe!(tx.nonce, accounts.web3().eth().transaction_count(from, None)),
-            maybe!(tx.gas_price, accounts.web3().eth().gas_price()),
-            maybe!(tx.chain_id.map(U256::from), accounts.web3().eth().chain_id()),
+        // legacy transactions don't include the chain ID, so there is no need to fetch it
+        let chain_id = if tx.force_legacy { Some(0) } else { tx.chain_id };
+        let eth = accounts.web3().eth();
+        let eip1559 = transaction_type(&tx) == Some(EIP1559_TX_TYPE);
+        // EIP-1559 transactions pay a priority fee on top of the base fee instead of a gas price
+        let fee = if !eip1559 {
+            maybe!(tx.gas_price.or(tx.max_fee_per_gas), eth.gas_price())
+        } else if tx.gas_price.is_some() {
+            Either::A(future::err(Error::ConflictingFees))
+        } else {
+            maybe!(tx.max_priority_fee_per_gas, eth.max_priority_fee_per_gas())
+        };
+        // a missing max fee is derived from the base fee of the latest block
+        let latest_block = if eip1559 && tx.max_fee_per_gas.is_none() {
+            Either::B(eth.block(BlockId::Number(BlockNumber::Latest)))
+        } else {
+            Either::A(future::ok(None))
+        };
+        // the pending nonce accounts for transactions of `from` which are not mined yet
+        let inner = Future::join4(
+            maybe!(tx.nonce, eth.transaction_count(from, Some(BlockNumber::Pending))),
+            fee,
+            maybe!(chain_id.map(U256::from), eth.chain_id()),
+            latest_block,
         );
 
         SignTransactionFuture {
@@ -209,17 +459,44 @@ impl<T: Transport> Future for SignTransactionFuture<T> {
     type Error = Error;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
-        let (nonce, gas_price, chain_id) = try_ready!(self.inner.poll());
-        let chain_id = chain_id.as_u64();
+        let (nonce, fee, chain_id, latest_block) = try_ready!(self.inner.poll());
+        let chain_id = if self.tx.force_legacy {
+            None
+        } else {
+            Some(chain_id.as_u64())
+        };
 
         let data = mem::replace(&mut self.tx.data, Bytes::default());
+        let transaction_type = transaction_type(&self.tx);
+        let (gas_price, max_priority_fee_per_gas) = if transaction_type == Some(EIP1559_TX_TYPE) {
+            let max_fee_per_gas = match self.tx.max_fee_per_gas {
+                Some(max_fee_per_gas) => max_fee_per_gas,
+                None => {
+                    let base_fee = latest_block.and_then(|block| block.base_fee_per_gas).ok_or_else(|| {
+                        Error::InvalidResponse("Latest block has no base fee to derive the max fee from".into())
+                    })?;
+                    // leaves room for the base fee to double, like `Eth::fill_transaction_fees`
+                    base_fee.saturating_mul(2.into()).saturating_add(fee)
+                }
+            };
+            (max_fee_per_gas, fee)
+        } else {
+            (fee, U256::zero())
+        };
+        let access_list = match transaction_type {
+            Some(_) => self.tx.access_list.take().unwrap_or_default(),
+            None => Vec::new(),
+        };
         let tx = Transaction {
+            transaction_type,
             to: self.tx.to,
             nonce,
             gas: self.tx.gas,
             gas_price,
+            max_priority_fee_per_gas,
             value: self.tx.value,
             data: data.0,
+            access_list,
         };
         let signed = tx.sign(&self.key, chain_id);
 
@@ -233,6 +510,66 @@ impl<T: Transport> Drop for SignTransactionFuture<T> {
     }
 }
 
+enum SendTransactionState<T: Transport> {
+    Rejected(Option<Error>),
+    Signing(Box<SignTransactionFuture<T>>),
+    Sending(Box<SendTransactionWithConfirmation<T>>),
+}
+
+/// Future resolving to the receipt of a locally signed transaction once it
+/// has been submitted and confirmed.
+pub struct SendTransactionFuture<T: Transport> {
+    transport: T,
+    poll_interval: Duration,
+    confirmations: usize,
+    state: SendTransactionState<T>,
+}
+
+impl<T: Transport> Future for SendTransactionFuture<T> {
+    type Item = TransactionReceipt;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let next_state = match self.state {
+                SendTransactionState::Rejected(ref mut error) => {
+                    return Err(error.take().expect("SendTransactionFuture polled after completion"))
+                }
+                SendTransactionState::Signing(ref mut future) => {
+                    let signed = try_ready!(future.poll());
+                    SendTransactionState::Sending(Box::new(send_raw_transaction_with_confirmation(
+                        self.transport.clone(),
+                        signed.raw_transaction,
+                        self.poll_interval,
+                        self.confirmations,
+                    )))
+                }
+                SendTransactionState::Sending(ref mut future) => return future.poll(),
+            };
+            self.state = next_state;
+        }
+    }
+}
+
+/// EIP-2718 type of EIP-1559 dynamic-fee transactions.
+const EIP1559_TX_TYPE: u8 = 2;
+
+/// EIP-2718 type of EIP-2930 access list transactions.
+const EIP2930_TX_TYPE: u8 = 1;
+
+/// Returns the EIP-2718 type of the transaction described by the parameters, `None` for legacy.
+fn transaction_type(tx: &TransactionParameters) -> Option<u8> {
+    if tx.force_legacy {
+        None
+    } else if tx.max_fee_per_gas.is_some() || tx.max_priority_fee_per_gas.is_some() {
+        Some(EIP1559_TX_TYPE)
+    } else if tx.access_list.is_some() {
+        Some(EIP2930_TX_TYPE)
+    } else {
+        None
+    }
+}
+
 /// A struct that represents a the components of a secp256k1 signature.
 struct Signature {
     v: u64,
@@ -266,31 +603,126 @@ fn sign(message: &Message, key: &SecretKey, chain_id: Option<u64>) -> Signature
 
 /// A transaction used for RLP encoding, hashing and signing.
 struct Transaction {
+    /// EIP-2718 transaction type, `None` for legacy transactions.
+    transaction_type: Option<u8>,
     to: Option<Address>,
     nonce: U256,
     gas: U256,
+    /// Gas price, or the max fee per gas of EIP-1559 transactions.
     gas_price: U256,
+    /// Max priority fee per gas, only encoded in EIP-1559 transactions.
+    max_priority_fee_per_gas: U256,
     value: U256,
     data: Vec<u8>,
+    /// Access list, only encoded in typed transactions.
+    access_list: Vec<AccessListItem>,
 }
 
 impl Transaction {
+    /// Decodes a raw signed transaction into the transaction and its signature.
+    fn decode_signed(raw: &[u8]) -> Result<(Self, Signature), DecoderError> {
+        let rlp = Rlp::new(raw);
+        if rlp.item_count()? != 9 {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        let tx = Transaction {
+            transaction_type: None,
+            nonce: rlp.val_at(0)?,
+            gas_price: rlp.val_at(1)?,
+            max_priority_fee_per_gas: U256::zero(),
+            gas: rlp.val_at(2)?,
+            to: decode_to(&rlp.at(3)?)?,
+            value: rlp.val_at(4)?,
+            data: rlp.val_at(5)?,
+            access_list: Vec::new(),
+        };
+        let signature = Signature {
+            v: rlp.val_at(6)?,
+            r: decode_h256(&rlp.at(7)?)?,
+            s: decode_h256(&rlp.at(8)?)?,
+        };
+
+        Ok((tx, signature))
+    }
+
+    /// Hash of the unsigned transaction, which is the message being signed.
+    ///
+    /// For typed transactions the preimage is the type byte followed by the RLP payload.
+    fn signing_hash(&self, chain_id: Option<u64>) -> H256 {
+        match self.transaction_type {
+            Some(transaction_type) => {
+                let mut rlp = RlpStream::new();
+                self.rlp_append_typed(&mut rlp, chain_id.unwrap_or_default(), None);
+                keccak256(&typed_envelope(transaction_type, rlp.as_raw())).into()
+            }
+            None => {
+                let mut rlp = RlpStream::new();
+                self.rlp_append_unsigned(&mut rlp, chain_id);
+                keccak256(rlp.as_raw()).into()
+            }
+        }
+    }
+
+    /// RLP encode the payload of a typed transaction, including the signature if given.
+    ///
+    /// The payload is `[chainId, nonce, gasPrice, gas, to, value, data, accessList]` for EIP-2930
+    /// transactions, EIP-1559 transactions replace `gasPrice` with `maxPriorityFeePerGas` and
+    /// `maxFeePerGas`. It is followed by `[yParity, r, s]` when signed.
+    fn rlp_append_typed(&self, rlp: &mut RlpStream, chain_id: u64, signature: Option<&Signature>) {
+        let dynamic_fee = self.transaction_type == Some(EIP1559_TX_TYPE);
+        let fee_fields = if dynamic_fee { 2 } else { 1 };
+        rlp.begin_list(7 + fee_fields + if signature.is_some() { 3 } else { 0 });
+        rlp.append(&chain_id);
+        rlp.append(&self.nonce);
+        if dynamic_fee {
+            rlp.append(&self.max_priority_fee_per_gas);
+        }
+        rlp.append(&self.gas_price);
+        rlp.append(&self.gas);
+        if let Some(to) = self.to {
+            rlp.append(&to);
+        } else {
+            // contract creation, recipient is encoded as an empty string (`0x80`)
+            rlp.append_empty_data();
+        }
+        rlp.append(&self.value);
+        rlp.append(&self.data);
+        rlp.begin_list(self.access_list.len());
+        for item in &self.access_list {
+            rlp.begin_list(2);
+            rlp.append(&item.address);
+            rlp.append_list::<H256, H256>(&item.storage_keys);
+        }
+        if let Some(signature) = signature {
+            rlp.append(&signature.v);
+            rlp.append(&U256::from_big_endian(signature.r.as_bytes()));
+            rlp.append(&U256::from_big_endian(signature.s.as_bytes()));
+        }
+    }
+
     /// RLP encode an unsigned transaction for the specified chain ID.
-    fn rlp_append_unsigned(&self, rlp: &mut RlpStream, chain_id: u64) {
-        rlp.begin_list(9);
+    ///
+    /// Without a chain ID the legacy 6-element encoding is used, otherwise the
+    /// chain ID and two empty values are appended as per EIP-155.
+    fn rlp_append_unsigned(&self, rlp: &mut RlpStream, chain_id: Option<u64>) {
+        rlp.begin_list(if chain_id.is_some() { 9 } else { 6 });
         rlp.append(&self.nonce);
         rlp.append(&self.gas_price);
         rlp.append(&self.gas);
         if let Some(to) = self.to {
             rlp.append(&to);
         } else {
-            rlp.append(&"");
+            // contract creation, recipient is encoded as an empty string (`0x80`)
+            rlp.append_empty_data();
         }
         rlp.append(&self.value);
         rlp.append(&self.data);
-        rlp.append(&chain_id);
-        rlp.append(&0u8);
-        rlp.append(&0u8);
+        if let Some(chain_id) = chain_id {
+            rlp.append(&chain_id);
+            rlp.append(&0u8);
+            rlp.append(&0u8);
+        }
     }
 
     /// RLP encode a signed transaction with the specified signature.
@@ -302,7 +734,8 @@ impl Transaction {
         if let Some(to) = self.to {
             rlp.append(&to);
         } else {
-            rlp.append(&"");
+            // contract creation, recipient is encoded as an empty string (`0x80`)
+            rlp.append_empty_data();
         }
         rlp.append(&self.value);
         rlp.append(&self.data);
@@ -312,22 +745,34 @@ impl Transaction {
     }
 
     /// Sign and return a raw signed transaction.
-    fn sign(self, key: &SecretKey, chain_id: u64) -> SignedTransaction {
-        let mut rlp = RlpStream::new();
-        self.rlp_append_unsigned(&mut rlp, chain_id);
-
-        let hash = keccak256(rlp.as_raw());
-        let message = Message::from_slice(&hash).expect("hash is non-zero 32-bytes; qed");
-        let signature = sign(&message, key, Some(chain_id));
-
-        rlp.clear();
-        self.rlp_append_signed(&mut rlp, &signature);
+    ///
+    /// Without a chain ID a legacy (pre EIP-155) transaction is produced.
+    /// Typed transactions are signed with the recovery parity as the V-value.
+    fn sign(self, key: &SecretKey, chain_id: Option<u64>) -> SignedTransaction {
+        let hash = self.signing_hash(chain_id);
+        let message = Message::from_slice(hash.as_bytes()).expect("hash is non-zero 32-bytes; qed");
+
+        let (signature, raw) = match self.transaction_type {
+            Some(transaction_type) => {
+                let mut signature = sign(&message, key, None);
+                signature.v -= 27;
+                let mut rlp = RlpStream::new();
+                self.rlp_append_typed(&mut rlp, chain_id.unwrap_or_default(), Some(&signature));
+                (signature, typed_envelope(transaction_type, rlp.as_raw()))
+            }
+            None => {
+                let signature = sign(&message, key, chain_id);
+                let mut rlp = RlpStream::new();
+                self.rlp_append_signed(&mut rlp, &signature);
+                (signature, rlp.out())
+            }
+        };
 
-        let transaction_hash = keccak256(rlp.as_raw()).into();
-        let raw_transaction = rlp.out().into();
+        let transaction_hash = keccak256(&raw).into();
+        let raw_transaction = raw.into();
 
         SignedTransaction {
-            message_hash: hash.into(),
+            message_hash: hash,
             v: signature.v,
             r: signature.r,
             s: signature.s,
@@ -337,6 +782,14 @@ impl Transaction {
     }
 }
 
+/// Prefixes an RLP payload with the EIP-2718 transaction type.
+fn typed_envelope(transaction_type: u8, payload: &[u8]) -> Vec<u8> {
+    let mut envelope = Vec::with_capacity(payload.len() + 1);
+    envelope.push(transaction_type);
+    envelope.extend_from_slice(payload);
+    envelope
+}
+
 /// A wrapper type around `SecretKey` to prevent leaking secret key data. This
 /// type will properly zeroize the secret key to `ONE_KEY` in a way that will
 /// not get optimized away by the compiler nor be prone to leaks that take
@@ -402,7 +855,7 @@ mod tests {
 
         transport.assert_request(
             "eth_getTransactionCount",
-            &[json!(from).to_string(), json!("latest").to_string()],
+            &[json!(from).to_string(), json!("pending").to_string()],
         );
         transport.assert_request("eth_gasPrice", &[]);
         transport.assert_request("eth_chainId", &[]);
@@ -432,6 +885,109 @@ mod tests {
         assert_eq!(signed, Ok(expected));
     }
 
+    #[test]
+    fn accounts_send_transaction() {
+        let tx = TransactionParameters {
+            to: Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()),
+            value: 1_000_000_000.into(),
+            gas: 2_000_000.into(),
+            ..Default::default()
+        };
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let from: Address = secret_key_address(&key);
+        let transaction_hash: H256 = "de8db924885b0803d2edc335f745b2b8750c8848744905684c20b987443a9593"
+            .parse()
+            .unwrap();
+        let receipt = TransactionReceipt {
+            transaction_hash,
+            transaction_index: 0.into(),
+            block_hash: Some(H256::zero()),
+            block_number: Some(2.into()),
+            cumulative_gas_used: 21_000.into(),
+            gas_used: Some(21_000.into()),
+            contract_address: None,
+            logs: vec![],
+            status: Some(1.into()),
+            logs_bloom: Default::default(),
+        };
+
+        let mut transport = TestTransport::default();
+        transport.add_response(json!(U256::zero()));
+        transport.add_response(json!(U256::from(21_000_000_000u128)));
+        transport.add_response(json!("0x1"));
+        transport.add_response(json!(transaction_hash));
+        transport.add_response(json!(receipt));
+
+        let result = {
+            let accounts = Accounts::new(&transport);
+            accounts.send(tx, &key, Duration::from_secs(0), 0).wait()
+        };
+
+        transport.assert_request(
+            "eth_getTransactionCount",
+            &[json!(from).to_string(), json!("pending").to_string()],
+        );
+        transport.assert_request("eth_gasPrice", &[]);
+        transport.assert_request("eth_chainId", &[]);
+        transport.assert_request(
+            "eth_sendRawTransaction",
+            &[r#""0xf869808504e3b29200831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca008025a0c9cf86333bcb065d140032ecaab5d9281bde80f21b9687b3e94161de42d51895a0727a108a0b8d101465414033c3f705a9c7b826e596766046ee1183dbc8aeaa68""#.into()],
+        );
+        transport.assert_request("eth_getTransactionReceipt", &[json!(transaction_hash).to_string()]);
+        transport.assert_no_more_requests();
+        assert_eq!(result, Ok(receipt));
+    }
+
+    #[test]
+    fn accounts_send_rejects_value_transfer_to_zero_address() {
+        let tx = TransactionParameters {
+            to: Some(Address::zero()),
+            value: 1.into(),
+            ..Default::default()
+        };
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+
+        let transport = TestTransport::default();
+        let result = {
+            let accounts = Accounts::new(&transport);
+            accounts.send(tx, &key, Duration::from_secs(0), 0).wait()
+        };
+
+        transport.assert_no_more_requests();
+        assert_eq!(result, Err(Error::ZeroAddressRecipient));
+    }
+
+    #[test]
+    fn accounts_send_allows_value_transfer_to_zero_address_when_opted_in() {
+        let tx = TransactionParameters {
+            nonce: Some(0.into()),
+            gas_price: Some(1.into()),
+            chain_id: Some(1),
+            to: Some(Address::zero()),
+            value: 1.into(),
+            allow_zero_recipient: true,
+            ..Default::default()
+        };
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+
+        let mut transport = TestTransport::default();
+        let (signed, result) = {
+            let accounts = Accounts::new(&transport);
+            let signed = accounts.sign_transaction(tx.clone(), &key).wait().unwrap();
+            (signed, accounts.send(tx, &key, Duration::from_secs(0), 0).wait())
+        };
+
+        transport.assert_request("eth_sendRawTransaction", &[json!(signed.raw_transaction).to_string()]);
+        transport.assert_no_more_requests();
+        assert_eq!(result, Err(Error::Unreachable));
+    }
+
     #[test]
     fn accounts_sign_transaction_with_all_parameters() {
         let key: SecretKey = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
@@ -456,6 +1012,326 @@ mod tests {
         accounts.transport().assert_no_more_requests();
     }
 
+    #[test]
+    fn accounts_sign_fully_specified_transaction_deterministically() {
+        // same test vector as `sign_transaction_data`, but going through `sign_transaction`
+        // with every parameter specified so that no node is needed
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let tx = TransactionParameters {
+            nonce: Some(0.into()),
+            to: Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()),
+            gas: 2_000_000.into(),
+            gas_price: Some(234_567_897_654_321u64.into()),
+            value: 1_000_000_000.into(),
+            chain_id: Some(1),
+            ..Default::default()
+        };
+
+        let accounts = Accounts::new(TestTransport::default());
+        let first = accounts.sign_transaction(tx.clone(), &key).wait().unwrap();
+        let second = accounts.sign_transaction(tx, &key).wait().unwrap();
+
+        accounts.transport().assert_no_more_requests();
+        assert_eq!(first, second);
+        assert_eq!(
+            first.raw_transaction,
+            Bytes(
+                "f86a8086d55698372431831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca008025a009ebb6ca057a0535d6186462bc0b465b561c94a295bdb0621fc19208ab149a9ca0440ffd775ce91a833ab410777204d5341a6f9fa91216a6f3ee2c051fea6a0428"
+                    .from_hex()
+                    .unwrap(),
+            )
+        );
+    }
+
+    #[test]
+    fn accounts_sign_legacy_transaction_with_chain_id() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+
+        let accounts = Accounts::new(TestTransport::default());
+        let signed = accounts
+            .sign_transaction(
+                TransactionParameters {
+                    nonce: Some(0.into()),
+                    gas_price: Some(1.into()),
+                    chain_id: Some(1),
+                    force_legacy: true,
+                    ..Default::default()
+                },
+                &key,
+            )
+            .wait()
+            .unwrap();
+
+        let raw = Rlp::new(&signed.raw_transaction.0);
+        let mut unsigned = RlpStream::new_list(6);
+        for i in 0..6 {
+            unsigned.append_raw(raw.at(i).unwrap().as_raw(), 1);
+        }
+
+        assert!(signed.v == 27 || signed.v == 28);
+        assert_eq!(signed.message_hash, H256(keccak256(&unsigned.out())));
+        assert_eq!(
+            accounts.recover_sender(&signed.raw_transaction.0).unwrap(),
+            secret_key_address(&key)
+        );
+        accounts.transport().assert_no_more_requests();
+    }
+
+    #[test]
+    fn accounts_sign_same_transaction_before_and_after_eip155() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let tx = TransactionParameters {
+            nonce: Some(0.into()),
+            to: Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()),
+            gas: 2_000_000.into(),
+            gas_price: Some(21_000_000_000u64.into()),
+            value: 1_000_000_000.into(),
+            chain_id: Some(1),
+            ..Default::default()
+        };
+
+        let accounts = Accounts::new(TestTransport::default());
+        let eip155 = accounts.sign_transaction(tx.clone(), &key).wait().unwrap();
+        let legacy = accounts
+            .sign_transaction(
+                TransactionParameters {
+                    force_legacy: true,
+                    ..tx
+                },
+                &key,
+            )
+            .wait()
+            .unwrap();
+
+        // v = recovery_id + 35 + chain_id * 2 with replay protection, recovery_id + 27 without
+        assert!(eip155.v == 37 || eip155.v == 38);
+        assert!(legacy.v == 27 || legacy.v == 28);
+        assert_ne!(eip155.message_hash, legacy.message_hash);
+        assert_ne!(eip155.raw_transaction, legacy.raw_transaction);
+        for signed in &[eip155, legacy] {
+            assert_eq!(
+                accounts.recover_sender(&signed.raw_transaction.0).unwrap(),
+                secret_key_address(&key)
+            );
+        }
+        accounts.transport().assert_no_more_requests();
+    }
+
+    #[test]
+    fn accounts_sign_eip1559_transaction() {
+        // signed transaction from `decode_eip1559_transaction` test vector
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+
+        let accounts = Accounts::new(TestTransport::default());
+        let signed = accounts
+            .sign_transaction(
+                TransactionParameters {
+                    nonce: Some(7.into()),
+                    to: Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()),
+                    gas: 21_000.into(),
+                    max_fee_per_gas: Some(100_000_000_000u64.into()),
+                    max_priority_fee_per_gas: Some(2_000_000_000u64.into()),
+                    value: 1_000_000_000.into(),
+                    data: Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+                    chain_id: Some(1),
+                    ..Default::default()
+                },
+                &key,
+            )
+            .wait()
+            .unwrap();
+
+        let raw = &signed.raw_transaction.0;
+        let payload = Rlp::new(&raw[1..]);
+        let mut unsigned = RlpStream::new_list(9);
+        for i in 0..9 {
+            unsigned.append_raw(payload.at(i).unwrap().as_raw(), 1);
+        }
+        let mut preimage = vec![2];
+        preimage.extend_from_slice(&unsigned.out());
+
+        assert_eq!(
+            signed.raw_transaction,
+            Bytes(
+                "02f8730107847735940085174876e80082520894f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca0084deadbeefc080a0215fac4c38e39c5d067907b750c0236fe9cde4eca255dbcd64ee65813e1f82b7a01aa9598f7a56e70391f4fce500ab373fbc5a6dd4b1d20ba19ca790e37889996e"
+                    .from_hex()
+                    .unwrap(),
+            )
+        );
+        assert_eq!(
+            signed.transaction_hash,
+            "3e5dc2337cdfd8216f10a5dd10eb1165e8437dba6f2e41a6db60108f227b5985"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(signed.message_hash, H256(keccak256(&preimage)));
+        assert_eq!(signed.v, 0);
+        accounts.transport().assert_no_more_requests();
+    }
+
+    #[test]
+    fn accounts_sign_eip1559_transaction_with_access_list() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let access_list = vec![AccessListItem {
+            address: Address::from_low_u64_be(0x123),
+            storage_keys: vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)],
+        }];
+
+        let mut transport = TestTransport::default();
+        transport.add_response(json!("0x2"));
+
+        let signed = {
+            let accounts = Accounts::new(&transport);
+            accounts
+                .sign_transaction(
+                    TransactionParameters {
+                        nonce: Some(0.into()),
+                        max_fee_per_gas: Some(10.into()),
+                        access_list: Some(access_list),
+                        chain_id: Some(5),
+                        ..Default::default()
+                    },
+                    &key,
+                )
+                .wait()
+                .unwrap()
+        };
+
+        // the missing priority fee is suggested by the node, not copied from the max fee
+        transport.assert_request("eth_maxPriorityFeePerGas", &[]);
+        transport.assert_no_more_requests();
+        let decoded = decode_raw_transaction(&signed.raw_transaction.0).unwrap();
+        assert_eq!(decoded.transaction_type, Some(2));
+        assert_eq!(decoded.from, secret_key_address(&key));
+        assert_eq!(decoded.max_fee_per_gas, Some(10.into()));
+        assert_eq!(decoded.max_priority_fee_per_gas, Some(2.into()));
+        assert_eq!(decoded.chain_id, Some(5));
+        let payload = Rlp::new(&signed.raw_transaction.0[1..]);
+        assert_eq!(payload.at(8).unwrap().item_count(), Ok(1));
+    }
+
+    #[test]
+    fn accounts_sign_eip1559_transaction_with_max_fee_from_base_fee() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let block = Block::<H256> {
+            base_fee_per_gas: Some(0x50.into()),
+            ..Default::default()
+        };
+
+        let mut transport = TestTransport::default();
+        transport.add_response(json!(block));
+
+        let signed = {
+            let accounts = Accounts::new(&transport);
+            accounts
+                .sign_transaction(
+                    TransactionParameters {
+                        nonce: Some(0.into()),
+                        max_priority_fee_per_gas: Some(0x14.into()),
+                        chain_id: Some(5),
+                        ..Default::default()
+                    },
+                    &key,
+                )
+                .wait()
+                .unwrap()
+        };
+
+        transport.assert_request("eth_getBlockByNumber", &[r#""latest""#.into(), "false".into()]);
+        transport.assert_no_more_requests();
+        let decoded = decode_raw_transaction(&signed.raw_transaction.0).unwrap();
+        assert_eq!(decoded.transaction_type, Some(2));
+        assert_eq!(decoded.max_fee_per_gas, Some(0xb4.into()));
+        assert_eq!(decoded.max_priority_fee_per_gas, Some(0x14.into()));
+    }
+
+    #[test]
+    fn accounts_sign_rejects_gas_price_with_eip1559_fees() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+
+        let accounts = Accounts::new(TestTransport::default());
+        let result = accounts
+            .sign_transaction(
+                TransactionParameters {
+                    nonce: Some(0.into()),
+                    gas_price: Some(1.into()),
+                    max_fee_per_gas: Some(2.into()),
+                    chain_id: Some(1),
+                    ..Default::default()
+                },
+                &key,
+            )
+            .wait();
+
+        assert_eq!(result, Err(Error::ConflictingFees));
+        accounts.transport().assert_no_more_requests();
+    }
+
+    #[test]
+    fn accounts_sign_eip2930_transaction() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let access_list = vec![AccessListItem {
+            address: Address::from_low_u64_be(0x123),
+            storage_keys: vec![H256::from_low_u64_be(1)],
+        }];
+
+        let accounts = Accounts::new(TestTransport::default());
+        let signed = accounts
+            .sign_transaction(
+                TransactionParameters {
+                    nonce: Some(3.into()),
+                    to: Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()),
+                    gas_price: Some(20_000_000_000u64.into()),
+                    access_list: Some(access_list),
+                    chain_id: Some(1),
+                    ..Default::default()
+                },
+                &key,
+            )
+            .wait()
+            .unwrap();
+
+        let raw = &signed.raw_transaction.0;
+        let payload = Rlp::new(&raw[1..]);
+        let mut unsigned = RlpStream::new_list(8);
+        for i in 0..8 {
+            unsigned.append_raw(payload.at(i).unwrap().as_raw(), 1);
+        }
+        let mut preimage = vec![1];
+        preimage.extend_from_slice(&unsigned.out());
+
+        assert_eq!(raw[0], 1);
+        assert_eq!(payload.item_count(), Ok(11));
+        assert_eq!(signed.message_hash, H256(keccak256(&preimage)));
+        assert_eq!(signed.transaction_hash, H256(keccak256(raw)));
+        assert!(signed.v == 0 || signed.v == 1);
+
+        let decoded = decode_raw_transaction(raw).unwrap();
+        assert_eq!(decoded.transaction_type, Some(1));
+        assert_eq!(decoded.from, secret_key_address(&key));
+        assert_eq!(decoded.gas_price, Some(20_000_000_000u64.into()));
+        assert_eq!(decoded.max_fee_per_gas, None);
+        assert_eq!(decoded.nonce, 3.into());
+        assert_eq!(decoded.chain_id, Some(1));
+        accounts.transport().assert_no_more_requests();
+    }
+
     #[test]
     fn accounts_hash_message() {
         // test vector taken from:
@@ -573,12 +1449,15 @@ mod tests {
             to: Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()),
             value: 1_000_000_000.into(),
             data: Vec::new(),
+            transaction_type: None,
+            max_priority_fee_per_gas: 0.into(),
+            access_list: Vec::new(),
         };
         let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
             .parse()
             .unwrap();
 
-        let signed = tx.sign(&key, 1);
+        let signed = tx.sign(&key, Some(1));
 
         let expected = SignedTransaction {
             message_hash: "6893a6ee8df79b0f5d64a180cd1ef35d030f3e296a5361cf04d02ce720d32ec5"
@@ -603,4 +1482,328 @@ mod tests {
 
         assert_eq!(signed, expected);
     }
+
+    #[test]
+    fn sign_contract_creation_transaction() {
+        let tx = Transaction {
+            nonce: 0.into(),
+            gas: 100_000.into(),
+            gas_price: 20_000_000_000u64.into(),
+            to: None,
+            value: 0.into(),
+            data: vec![0x60, 0x80],
+            transaction_type: None,
+            max_priority_fee_per_gas: 0.into(),
+            access_list: Vec::new(),
+        };
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+
+        let signed = tx.sign(&key, Some(1));
+
+        // [nonce, gasPrice, gas, to = 0x80, value, data, chainId, 0, 0]
+        let unsigned: Vec<u8> = "d3808504a817c800830186a08080826080018080".from_hex().unwrap();
+        assert_eq!(signed.message_hash, H256(keccak256(&unsigned)));
+
+        let raw = rlp::Rlp::new(&signed.raw_transaction.0);
+        assert_eq!(raw.item_count().unwrap(), 9);
+        assert_eq!(raw.at(3).unwrap().as_raw(), &[0x80]);
+        assert_eq!(signed.transaction_hash, H256(keccak256(&signed.raw_transaction.0)));
+        assert!(signed.v == 0x25 || signed.v == 0x26);
+    }
+
+    #[test]
+    fn recover_sender_of_eip155_transaction() {
+        // signed transaction from `sign_transaction_data` test vector
+        let raw: Vec<u8> = "f86a8086d55698372431831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca008025a009ebb6ca057a0535d6186462bc0b465b561c94a295bdb0621fc19208ab149a9ca0440ffd775ce91a833ab410777204d5341a6f9fa91216a6f3ee2c051fea6a0428"
+            .from_hex()
+            .unwrap();
+        let accounts = Accounts::new(TestTransport::default());
+
+        let sender = accounts.recover_sender(&raw).unwrap();
+
+        assert_eq!(sender, "2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse().unwrap());
+        accounts.transport().assert_no_more_requests();
+    }
+
+    #[test]
+    fn recover_sender_of_legacy_transaction() {
+        let tx = Transaction {
+            nonce: 0.into(),
+            gas: 2_000_000.into(),
+            gas_price: 234_567_897_654_321u64.into(),
+            to: Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()),
+            value: 1_000_000_000.into(),
+            data: Vec::new(),
+            transaction_type: None,
+            max_priority_fee_per_gas: 0.into(),
+            access_list: Vec::new(),
+        };
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let accounts = Accounts::new(TestTransport::default());
+
+        let signed = tx.sign(&key, None);
+        let sender = accounts.recover_sender(&signed.raw_transaction.0).unwrap();
+
+        assert!(signed.v == 27 || signed.v == 28);
+        assert_eq!(sender, "2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse().unwrap());
+    }
+
+    #[test]
+    fn sign_transaction_with_chain_id_zero() {
+        let tx = Transaction {
+            nonce: 0.into(),
+            gas: 2_000_000.into(),
+            gas_price: 234_567_897_654_321u64.into(),
+            to: Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()),
+            value: 1_000_000_000.into(),
+            data: Vec::new(),
+            transaction_type: None,
+            max_priority_fee_per_gas: 0.into(),
+            access_list: Vec::new(),
+        };
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let accounts = Accounts::new(TestTransport::default());
+
+        let mut legacy = RlpStream::new();
+        tx.rlp_append_unsigned(&mut legacy, None);
+        let mut replay_protected = RlpStream::new();
+        tx.rlp_append_unsigned(&mut replay_protected, Some(0));
+
+        assert_eq!(Rlp::new(legacy.as_raw()).item_count(), Ok(6));
+        assert_eq!(Rlp::new(replay_protected.as_raw()).item_count(), Ok(9));
+        assert_ne!(tx.signing_hash(None), tx.signing_hash(Some(0)));
+
+        let signed = tx.sign(&key, Some(0));
+        let recovery = Recovery::from(&signed);
+
+        assert!(signed.v == 35 || signed.v == 36);
+        assert_eq!(recovery.chain_id(), Some(0));
+        assert_eq!(
+            accounts.recover_sender(&signed.raw_transaction.0).unwrap(),
+            "2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_legacy_transaction() {
+        // signed transaction from `sign_transaction_data` test vector
+        let tx = decode_transaction("0xf86a8086d55698372431831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca008025a009ebb6ca057a0535d6186462bc0b465b561c94a295bdb0621fc19208ab149a9ca0440ffd775ce91a833ab410777204d5341a6f9fa91216a6f3ee2c051fea6a0428").unwrap();
+
+        assert_eq!(tx.transaction_type, None);
+        assert_eq!(
+            tx.hash,
+            "d8f64a42b57be0d565f385378db2f6bf324ce14a594afc05de90436e9ce01f60"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(tx.from, "2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse().unwrap());
+        assert_eq!(tx.to, Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()));
+        assert_eq!(tx.nonce, 0.into());
+        assert_eq!(tx.gas, 2_000_000.into());
+        assert_eq!(tx.gas_price, Some(234_567_897_654_321u64.into()));
+        assert_eq!(tx.max_fee_per_gas, None);
+        assert_eq!(tx.value, 1_000_000_000.into());
+        assert_eq!(tx.data, Bytes::default());
+        assert_eq!(tx.access_list, None);
+        assert_eq!(tx.chain_id, Some(1));
+        assert_eq!(tx.v, 0x25);
+    }
+
+    #[test]
+    fn decode_eip1559_transaction() {
+        let tx = decode_transaction("0x02f8730107847735940085174876e80082520894f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca0084deadbeefc080a0215fac4c38e39c5d067907b750c0236fe9cde4eca255dbcd64ee65813e1f82b7a01aa9598f7a56e70391f4fce500ab373fbc5a6dd4b1d20ba19ca790e37889996e").unwrap();
+
+        assert_eq!(tx.transaction_type, Some(2));
+        assert_eq!(
+            tx.hash,
+            "3e5dc2337cdfd8216f10a5dd10eb1165e8437dba6f2e41a6db60108f227b5985"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(tx.from, "2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse().unwrap());
+        assert_eq!(tx.to, Some("F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse().unwrap()));
+        assert_eq!(tx.nonce, 7.into());
+        assert_eq!(tx.gas, 21_000.into());
+        assert_eq!(tx.gas_price, None);
+        assert_eq!(tx.max_priority_fee_per_gas, Some(2_000_000_000u64.into()));
+        assert_eq!(tx.max_fee_per_gas, Some(100_000_000_000u64.into()));
+        assert_eq!(tx.value, 1_000_000_000.into());
+        assert_eq!(tx.data, Bytes(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(tx.access_list, Some(vec![]));
+        assert_eq!(tx.chain_id, Some(1));
+        assert_eq!(tx.v, 0);
+    }
+
+    #[test]
+    fn decode_typed_transaction_rejects_invalid_y_parity() {
+        let raw = "02f8730107847735940085174876e80082520894f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca0084deadbeefc080a0215fac4c38e39c5d067907b750c0236fe9cde4eca255dbcd64ee65813e1f82b7a01aa9598f7a56e70391f4fce500ab373fbc5a6dd4b1d20ba19ca790e37889996e"
+            .from_hex::<Vec<u8>>()
+            .unwrap();
+        let with_y_parity = |y_parity: u64| {
+            let rlp = Rlp::new(&raw[1..]);
+            let mut stream = RlpStream::new_list(12);
+            for i in 0..12 {
+                if i == 9 {
+                    stream.append(&y_parity);
+                } else {
+                    stream.append_raw(rlp.at(i).unwrap().as_raw(), 1);
+                }
+            }
+            let mut raw = vec![2];
+            raw.extend_from_slice(&stream.out());
+            raw
+        };
+
+        assert!(decode_raw_transaction(&with_y_parity(0)).is_ok());
+        for &y_parity in &[2, u64::MAX] {
+            let raw = with_y_parity(y_parity);
+            assert_eq!(
+                decode_raw_transaction(&raw),
+                Err(Error::Decoder(format!("Invalid yParity: {}", y_parity)))
+            );
+            match validate_raw_transaction(&raw, 1).unwrap_err().as_slice() {
+                [ValidationError::InvalidEncoding(_)] => {}
+                other => panic!("Expected encoding error, got: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn decode_raw_transaction_hash_round_trips() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let accounts = Accounts::new(TestTransport::default());
+        let legacy = TransactionParameters {
+            nonce: Some(0.into()),
+            gas_price: Some(1.into()),
+            chain_id: Some(1),
+            ..Default::default()
+        };
+        let access_list = TransactionParameters {
+            access_list: Some(vec![]),
+            ..legacy.clone()
+        };
+        let eip1559 = TransactionParameters {
+            gas_price: None,
+            max_fee_per_gas: Some(2.into()),
+            max_priority_fee_per_gas: Some(1.into()),
+            ..legacy.clone()
+        };
+
+        for (tx, transaction_type) in [(legacy, None), (access_list, Some(1)), (eip1559, Some(2))] {
+            let signed = accounts.sign_transaction(tx, &key).wait().unwrap();
+            let decoded = decode_raw_transaction(&signed.raw_transaction.0).unwrap();
+
+            assert_eq!(decoded.transaction_type, transaction_type);
+            assert_eq!(decoded.hash, signed.transaction_hash);
+            assert_eq!(decoded.from, secret_key_address(&key));
+        }
+
+        assert!(decode_raw_transaction(&[]).is_err());
+        assert!(decode_raw_transaction(&[0x03, 0xc0]).is_err());
+    }
+
+    #[test]
+    fn should_validate_raw_transaction() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let tx = TransactionParameters {
+            nonce: Some(0.into()),
+            gas_price: Some(1.into()),
+            chain_id: Some(1),
+            to: Some(Address::from_low_u64_be(0x123)),
+            data: vec![0x00, 0x01].into(),
+            ..Default::default()
+        };
+        let accounts = Accounts::new(TestTransport::default());
+        let signed = accounts.sign_transaction(tx, &key).wait().unwrap();
+
+        let validated = validate_raw_transaction(&signed.raw_transaction.0, 1).unwrap();
+
+        assert_eq!(validated.hash, signed.transaction_hash);
+        assert_eq!(validated.from, secret_key_address(&key));
+    }
+
+    #[test]
+    fn should_collect_all_raw_transaction_validation_errors() {
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let tx = TransactionParameters {
+            nonce: Some(0.into()),
+            gas: 21_000.into(),
+            gas_price: Some(1.into()),
+            chain_id: Some(1),
+            to: Some(Address::from_low_u64_be(0x123)),
+            data: vec![0x00, 0x01].into(),
+            ..Default::default()
+        };
+        let accounts = Accounts::new(TestTransport::default());
+        let signed = accounts.sign_transaction(tx, &key).wait().unwrap();
+
+        let errors = validate_raw_transaction(&signed.raw_transaction.0, 5).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::ChainIdMismatch { expected: 5, actual: 1 },
+                ValidationError::InsufficientGas {
+                    required: 21_020.into(),
+                    provided: 21_000.into(),
+                },
+            ]
+        );
+        match validate_raw_transaction(&[0x03, 0xc0], 1).unwrap_err().as_slice() {
+            [ValidationError::InvalidEncoding(_)] => {}
+            other => panic!("Expected encoding error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_include_access_list_and_initcode_in_intrinsic_gas() {
+        // given
+        let key: SecretKey = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let access_list = vec![AccessListItem {
+            address: Address::from_low_u64_be(0x123),
+            storage_keys: vec![H256::zero(), H256::from_low_u64_be(1)],
+        }];
+        let tx = TransactionParameters {
+            nonce: Some(0.into()),
+            gas: 21_000.into(),
+            gas_price: Some(1.into()),
+            chain_id: Some(1),
+            to: None,
+            data: vec![0x01; 33].into(),
+            access_list: Some(access_list.clone()),
+            ..Default::default()
+        };
+        let accounts = Accounts::new(TestTransport::default());
+        let signed = accounts.sign_transaction(tx, &key).wait().unwrap();
+
+        // when
+        let decoded = decode_raw_transaction(&signed.raw_transaction.0).unwrap();
+        let errors = validate_raw_transaction(&signed.raw_transaction.0, 1).unwrap_err();
+
+        // then
+        assert_eq!(decoded.access_list, Some(access_list));
+        // creation + 33 non-zero bytes + 2 initcode words + 1 address + 2 storage keys
+        let required = 53_000 + 33 * 16 + 2 * 2 + 2_400 + 2 * 1_900;
+        assert_eq!(
+            errors,
+            vec![ValidationError::InsufficientGas {
+                required: required.into(),
+                provided: 21_000.into(),
+            }]
+        );
+    }
 }
@@ -0,0 +1,115 @@
+//! `Debug` namespace
+
+use crate::api::Namespace;
+use crate::helpers::{self, CallFuture};
+use crate::types::{Address, CallFrame, StorageRange, H256};
+use crate::Transport;
+use serde_json::json;
+
+/// `Debug` namespace
+#[derive(Debug, Clone)]
+pub struct Debug<T> {
+    transport: T,
+}
+
+impl<T: Transport> Namespace<T> for Debug<T> {
+    fn new(transport: T) -> Self
+    where
+        Self: Sized,
+    {
+        Debug { transport }
+    }
+
+    fn transport(&self) -> &T {
+        &self.transport
+    }
+}
+
+impl<T: Transport> Debug<T> {
+    /// Returns up to `max_result` storage slots of `address` starting at `start_key`,
+    /// as of after the transaction at `tx_index` in the given block.
+    ///
+    /// Larger ranges are fetched page by page, passing the returned `next_key`
+    /// as `start_key` of the next call until it is `None`.
+    pub fn storage_range_at(
+        &self,
+        block_hash: H256,
+        tx_index: usize,
+        address: Address,
+        start_key: H256,
+        max_result: usize,
+    ) -> CallFuture<StorageRange, T::Out> {
+        let block_hash = helpers::serialize(&block_hash);
+        let tx_index = helpers::serialize(&tx_index);
+        let address = helpers::serialize(&address);
+        let start_key = helpers::serialize(&start_key);
+        let max_result = helpers::serialize(&max_result);
+        CallFuture::new(self.transport.execute(
+            "debug_storageRangeAt",
+            vec![block_hash, tx_index, address, start_key, max_result],
+        ))
+    }
+
+    /// Replays the transaction with geth's `callTracer`, returning its call tree.
+    pub fn trace_transaction_calls(&self, hash: H256) -> CallFuture<CallFrame, T::Out> {
+        let hash = helpers::serialize(&hash);
+        let options = json!({ "tracer": "callTracer" });
+        CallFuture::new(self.transport.execute("debug_traceTransaction", vec![hash, options]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Future;
+
+    use crate::api::Namespace;
+    use crate::types::{Address, CallFrame, StorageRange, H256};
+
+    use super::Debug;
+
+    const EXAMPLE_STORAGE_RANGE: &str = r#"{
+        "storage": {
+            "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563": {
+                "key": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "value": "0x0000000000000000000000000000000000000000000000000000000000000001"
+            }
+        },
+        "nextKey": null
+    }"#;
+
+    const EXAMPLE_CALL_FRAME: &str = r#"{
+        "type": "CALL",
+        "from": "0x0000000000000000000000000000000000000001",
+        "to": "0x0000000000000000000000000000000000000002",
+        "value": "0x0",
+        "gas": "0x7a120",
+        "gasUsed": "0x5208",
+        "input": "0x",
+        "output": "0x"
+    }"#;
+
+    rpc_test!(
+    Debug:storage_range_at, H256::from_low_u64_be(0x123), 1usize, Address::from_low_u64_be(0x456), H256::zero(), 10usize
+    =>
+    "debug_storageRangeAt", vec![
+        r#""0x0000000000000000000000000000000000000000000000000000000000000123""#,
+        r#"1"#,
+        r#""0x0000000000000000000000000000000000000456""#,
+        r#""0x0000000000000000000000000000000000000000000000000000000000000000""#,
+        r#"10"#
+    ];
+    ::serde_json::from_str(EXAMPLE_STORAGE_RANGE).unwrap()
+    => ::serde_json::from_str::<StorageRange>(EXAMPLE_STORAGE_RANGE).unwrap()
+    );
+
+    rpc_test!(
+    Debug:trace_transaction_calls, H256::from_low_u64_be(0x123)
+    =>
+    "debug_traceTransaction", vec![
+        r#""0x0000000000000000000000000000000000000000000000000000000000000123""#,
+        r#"{"tracer":"callTracer"}"#
+    ];
+    ::serde_json::from_str(EXAMPLE_CALL_FRAME).unwrap()
+    => ::serde_json::from_str::<CallFrame>(EXAMPLE_CALL_FRAME).unwrap()
+    );
+}
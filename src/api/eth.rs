@@ -2,16 +2,22 @@
 
 use crate::api::Namespace;
 use crate::helpers::{self, CallFuture};
+use crate::signing::eip712::TypedData;
+use crate::signing::keccak256;
 use crate::types::{
-    Address, Block, BlockId, BlockNumber, Bytes, CallRequest, Filter, Index, Log, SyncState, Transaction,
+    AccessList, AccessListComparison, AccessListWithGasUsed, AccountState, Address, Block, BlockId, BlockNumber, Bytes,
+    CallRequest, FeeHistory, Filter, Index, Log, Proof, RawTransaction, SyncState, Transaction, TransactionCondition,
     TransactionId, TransactionReceipt, TransactionRequest, Work, H256, H520, H64, U256, U64,
 };
-use crate::Transport;
+use crate::{Error, Transport};
+use futures::future::{Join, Join3, JoinAll};
+use futures::{Async, Future, Poll};
 
 /// `Eth` namespace
 #[derive(Debug, Clone)]
 pub struct Eth<T> {
     transport: T,
+    default_from: Option<Address>,
 }
 
 impl<T: Transport> Namespace<T> for Eth<T> {
@@ -19,7 +25,10 @@ impl<T: Transport> Namespace<T> for Eth<T> {
     where
         Self: Sized,
     {
-        Eth { transport }
+        Eth {
+            transport,
+            default_from: None,
+        }
     }
 
     fn transport(&self) -> &T {
@@ -28,6 +37,20 @@ impl<T: Transport> Namespace<T> for Eth<T> {
 }
 
 impl<T: Transport> Eth<T> {
+    /// Use given address as a sender of `eth_call` and `eth_estimateGas` requests
+    /// which don't specify `from` explicitly.
+    pub fn with_default_from(mut self, from: Address) -> Self {
+        self.default_from = Some(from);
+        self
+    }
+
+    fn with_from(&self, mut req: CallRequest) -> CallRequest {
+        if req.from.is_none() {
+            req.from = self.default_from;
+        }
+        req
+    }
+
     /// Get list of available accounts.
     pub fn accounts(&self) -> CallFuture<Vec<Address>, T::Out> {
         CallFuture::new(self.transport.execute("eth_accounts", vec![]))
@@ -40,7 +63,7 @@ impl<T: Transport> Eth<T> {
 
     /// Call a constant method of contract without changing the state of the blockchain.
     pub fn call(&self, req: CallRequest, block: Option<BlockNumber>) -> CallFuture<Bytes, T::Out> {
-        let req = helpers::serialize(&req);
+        let req = helpers::serialize(&self.with_from(req));
         let block = helpers::serialize(&block.unwrap_or(BlockNumber::Latest));
 
         CallFuture::new(self.transport.execute("eth_call", vec![req, block]))
@@ -51,6 +74,38 @@ impl<T: Transport> Eth<T> {
         CallFuture::new(self.transport.execute("eth_coinbase", vec![]))
     }
 
+    /// Generate an access list (EIP-2930) of addresses and storage keys the call is going to access.
+    ///
+    /// Setting the list on a transaction making the same call reduces the gas it uses.
+    pub fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockNumber>,
+    ) -> CallFuture<AccessListWithGasUsed, T::Out> {
+        let req = helpers::serialize(&self.with_from(req));
+        let block = helpers::serialize(&block.unwrap_or(BlockNumber::Latest));
+
+        CallFuture::new(self.transport.execute("eth_createAccessList", vec![req, block]))
+    }
+
+    /// Compares the gas estimated for a call with the gas estimated for it with a generated access list.
+    ///
+    /// Useful to decide whether to send the transaction with an access list, which only pays off
+    /// when the gas saved on accessing its entries exceeds the intrinsic cost of the list.
+    /// Both values come from `eth_estimateGas`, as the `gasUsed` reported by `eth_createAccessList`
+    /// is the gas used after refunds rather than the gas limit the call needs.
+    pub fn compare_access_list(&self, req: CallRequest, block: Option<BlockNumber>) -> AccessListComparisonFuture<T> {
+        let inner = self
+            .estimate_gas(req.clone(), block)
+            .join(self.create_access_list(req.clone(), block));
+        AccessListComparisonFuture {
+            eth: self.clone(),
+            req,
+            block,
+            state: AccessListComparisonState::CreateAccessList(inner),
+        }
+    }
+
     /// Compile LLL
     pub fn compile_lll(&self, code: String) -> CallFuture<Bytes, T::Out> {
         let code = helpers::serialize(&code);
@@ -71,7 +126,7 @@ impl<T: Transport> Eth<T> {
 
     /// Call a contract without changing the state of the blockchain to estimate gas usage.
     pub fn estimate_gas(&self, req: CallRequest, block: Option<BlockNumber>) -> CallFuture<U256, T::Out> {
-        let req = helpers::serialize(&req);
+        let req = helpers::serialize(&self.with_from(req));
 
         let args = match block {
             Some(block) => vec![req, helpers::serialize(&block)],
@@ -86,6 +141,16 @@ impl<T: Transport> Eth<T> {
         CallFuture::new(self.transport.execute("eth_gasPrice", vec![]))
     }
 
+    /// Get current recommended priority fee per gas (EIP-1559 tip)
+    pub fn max_priority_fee_per_gas(&self) -> CallFuture<U256, T::Out> {
+        CallFuture::new(self.transport.execute("eth_maxPriorityFeePerGas", vec![]))
+    }
+
+    /// Get current base fee per unit of blob gas (EIP-4844)
+    pub fn blob_base_fee(&self) -> CallFuture<U256, T::Out> {
+        CallFuture::new(self.transport.execute("eth_blobBaseFee", vec![]))
+    }
+
     /// Get balance of given address
     pub fn balance(&self, address: Address, block: Option<BlockNumber>) -> CallFuture<U256, T::Out> {
         let address = helpers::serialize(&address);
@@ -94,12 +159,42 @@ impl<T: Transport> Eth<T> {
         CallFuture::new(self.transport.execute("eth_getBalance", vec![address, block]))
     }
 
+    /// Get balance, nonce and code hash of given account.
+    ///
+    /// The three underlying requests are issued at once, so they end up in a single
+    /// round trip when the transport batches them (see `transports::Batch`).
+    pub fn account_state(&self, address: Address, block: Option<BlockNumber>) -> AccountStateFuture<T> {
+        AccountStateFuture {
+            inner: self
+                .balance(address, block)
+                .join3(self.transaction_count(address, block), self.code(address, block)),
+        }
+    }
+
     /// Get all logs matching a given filter object
     pub fn logs(&self, filter: Filter) -> CallFuture<Vec<Log>, T::Out> {
         let filter = helpers::serialize(&filter);
         CallFuture::new(self.transport.execute("eth_getLogs", vec![filter]))
     }
 
+    /// Get all logs matching a given filter object, splitting large address sets
+    /// into multiple queries of at most `max_addresses` addresses each.
+    ///
+    /// Logs are returned in the order of the queries, so they are not sorted by block
+    /// when the address set has been split. The `limit` of the filter applies to each
+    /// query separately, a `max_addresses` of 0 disables splitting.
+    pub fn logs_chunked(&self, filter: Filter, max_addresses: usize) -> LogsChunked<T> {
+        let queries = filter
+            .split_addresses(max_addresses)
+            .into_iter()
+            .map(|filter| self.logs(filter))
+            .collect::<Vec<_>>();
+
+        LogsChunked {
+            inner: futures::future::join_all(queries),
+        }
+    }
+
     /// Get block details with transaction hashes.
     pub fn block(&self, block: BlockId) -> CallFuture<Option<Block<H256>>, T::Out> {
         let include_txs = helpers::serialize(&false);
@@ -166,11 +261,40 @@ impl<T: Transport> Eth<T> {
         CallFuture::new(self.transport.execute("eth_getCompilers", vec![]))
     }
 
+    /// Get base fees and priority fee percentiles of `block_count` blocks up to `newest_block`.
+    ///
+    /// Percentiles are given in ascending order from `0` to `100`, `reward` is only
+    /// returned when some are requested.
+    pub fn fee_history(
+        &self,
+        block_count: U256,
+        newest_block: BlockNumber,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> CallFuture<FeeHistory, T::Out> {
+        let block_count = helpers::serialize(&block_count);
+        let newest_block = helpers::serialize(&newest_block);
+        let reward_percentiles = helpers::serialize(&reward_percentiles.unwrap_or_default());
+
+        CallFuture::new(
+            self.transport
+                .execute("eth_feeHistory", vec![block_count, newest_block, reward_percentiles]),
+        )
+    }
+
     /// Get chain id
     pub fn chain_id(&self) -> CallFuture<U256, T::Out> {
         CallFuture::new(self.transport.execute("eth_chainId", vec![]))
     }
 
+    /// Get Merkle proof of an account and its storage entries under given keys (EIP-1186)
+    pub fn proof(&self, address: Address, keys: Vec<U256>, block: Option<BlockNumber>) -> CallFuture<Proof, T::Out> {
+        let address = helpers::serialize(&address);
+        let keys = helpers::serialize(&keys);
+        let block = helpers::serialize(&block.unwrap_or(BlockNumber::Latest));
+
+        CallFuture::new(self.transport.execute("eth_getProof", vec![address, keys, block]))
+    }
+
     /// Get storage entry
     pub fn storage(&self, address: Address, idx: U256, block: Option<BlockNumber>) -> CallFuture<H256, T::Out> {
         let address = helpers::serialize(&address);
@@ -219,6 +343,18 @@ impl<T: Transport> Eth<T> {
         CallFuture::new(self.transport.execute("eth_getTransactionReceipt", vec![hash]))
     }
 
+    /// Get receipts of multiple transactions.
+    ///
+    /// Receipts of transactions which are still pending (or unknown) resolve to `None`
+    /// without failing the others. The requests are issued at once, so they end up in a
+    /// single round trip when the transport batches them (see `transports::Batch`).
+    pub fn transaction_receipts(
+        &self,
+        hashes: Vec<H256>,
+    ) -> JoinAll<Vec<CallFuture<Option<TransactionReceipt>, T::Out>>> {
+        futures::future::join_all(hashes.into_iter().map(|hash| self.transaction_receipt(hash)).collect())
+    }
+
     /// Get uncle by block ID and uncle index -- transactions only has hashes.
     pub fn uncle(&self, block: BlockId, index: Index) -> CallFuture<Option<Block<H256>>, T::Out> {
         let index = helpers::serialize(&index);
@@ -280,7 +416,32 @@ impl<T: Transport> Eth<T> {
         CallFuture::new(self.transport.execute("eth_newPendingTransactionFilter", vec![]))
     }
 
-    /// Start new pending transaction filter
+    /// Fill in fees of a transaction based on the latest block.
+    ///
+    /// When the latest block reports `baseFeePerGas` (London and later) EIP-1559 fees are used:
+    /// priority fee is the difference between current gas price and the base fee, and max fee
+    /// leaves room for the base fee to double. Otherwise legacy `gasPrice` is filled in.
+    /// Transactions which already specify `gasPrice` or `maxFeePerGas` are left untouched.
+    pub fn fill_transaction_fees(&self, tx: TransactionRequest) -> FillTransactionFees<T> {
+        FillTransactionFees::new(self, tx)
+    }
+
+    /// Check whether a transaction condition still lies ahead of the current chain head.
+    ///
+    /// Resolves to `false` (and logs a warning) when the condition references a block that
+    /// has already been mined or a timestamp already passed, in which case the transaction
+    /// would be included immediately.
+    pub fn check_condition(&self, condition: TransactionCondition) -> CheckCondition<T> {
+        CheckCondition {
+            condition,
+            inner: self.block(BlockId::Number(BlockNumber::Latest)),
+        }
+    }
+
+    /// Get current ethereum protocol version.
+    ///
+    /// The version is returned as reported by the node, which is either a hex (`"0x41"`)
+    /// or a decimal (`"65"`) string depending on the client.
     pub fn protocol_version(&self) -> CallFuture<String, T::Out> {
         CallFuture::new(self.transport.execute("eth_protocolVersion", vec![]))
     }
@@ -297,13 +458,36 @@ impl<T: Transport> Eth<T> {
         CallFuture::new(self.transport.execute("eth_sendTransaction", vec![tx]))
     }
 
-    /// Signs a hash of given data
+    /// Signs data with an unlocked account of the node (`eth_sign`).
+    ///
+    /// The node prefixes the data with `"\x19Ethereum Signed Message:\n" + len(data)` before hashing,
+    /// so the signature can't be used to sign an arbitrary transaction. Note that the account has to be
+    /// unlocked on the node, which exposes it to anyone with access to the RPC interface; prefer signing
+    /// locally with `Accounts::sign` when the key is available.
     pub fn sign(&self, address: Address, data: Bytes) -> CallFuture<H520, T::Out> {
         let address = helpers::serialize(&address);
         let data = helpers::serialize(&data);
         CallFuture::new(self.transport.execute("eth_sign", vec![address, data]))
     }
 
+    /// Signs EIP-712 typed structured data with an unlocked account of the node (`eth_signTypedData_v4`).
+    ///
+    /// The signed digest is `typed_data.hash()`, which can be used to verify the signature locally.
+    pub fn sign_typed_data(&self, address: Address, typed_data: TypedData) -> CallFuture<H520, T::Out> {
+        let address = helpers::serialize(&address);
+        let typed_data = helpers::serialize(&typed_data);
+        let params = vec![address, typed_data];
+        CallFuture::new(self.transport.execute("eth_signTypedData_v4", params))
+    }
+
+    /// Signs a transaction with an unlocked account of the node without sending it (`eth_signTransaction`).
+    /// Returns the signed transaction in raw bytes along with it's details, so it can be
+    /// submitted later using `send_raw_transaction`.
+    pub fn sign_transaction(&self, tx: TransactionRequest) -> CallFuture<RawTransaction, T::Out> {
+        let tx = helpers::serialize(&tx);
+        CallFuture::new(self.transport.execute("eth_signTransaction", vec![tx]))
+    }
+
     /// Submit hashrate of external miner
     pub fn submit_hashrate(&self, rate: U256, id: H256) -> CallFuture<bool, T::Out> {
         let rate = helpers::serialize(&rate);
@@ -328,16 +512,182 @@ impl<T: Transport> Eth<T> {
     }
 }
 
+/// Future resolving to a transaction request with fees filled in.
+pub struct FillTransactionFees<T: Transport> {
+    tx: Option<TransactionRequest>,
+    inner: Option<Join<CallFuture<Option<Block<H256>>, T::Out>, CallFuture<U256, T::Out>>>,
+}
+
+impl<T: Transport> FillTransactionFees<T> {
+    fn new(eth: &Eth<T>, tx: TransactionRequest) -> Self {
+        let inner = if tx.gas_price.is_some() || tx.max_fee_per_gas.is_some() {
+            None
+        } else {
+            Some(eth.block(BlockId::Number(BlockNumber::Latest)).join(eth.gas_price()))
+        };
+
+        FillTransactionFees { tx: Some(tx), inner }
+    }
+}
+
+impl<T: Transport> Future for FillTransactionFees<T> {
+    type Item = TransactionRequest;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let fees = match self.inner {
+            Some(ref mut inner) => Some(try_ready!(inner.poll())),
+            None => None,
+        };
+        let mut tx = self.tx.take().expect("future polled after ready; qed");
+
+        if let Some((block, gas_price)) = fees {
+            match block.and_then(|block| block.base_fee_per_gas) {
+                Some(base_fee) => {
+                    let priority_fee = tx
+                        .max_priority_fee_per_gas
+                        .unwrap_or_else(|| gas_price.saturating_sub(base_fee));
+                    tx.max_priority_fee_per_gas = Some(priority_fee);
+                    tx.max_fee_per_gas = Some(base_fee.saturating_mul(2.into()).saturating_add(priority_fee));
+                }
+                None => tx.gas_price = Some(gas_price),
+            }
+        }
+
+        Ok(Async::Ready(tx))
+    }
+}
+
+/// Future resolving to logs of all queries of a split filter.
+pub struct LogsChunked<T: Transport> {
+    inner: JoinAll<Vec<CallFuture<Vec<Log>, T::Out>>>,
+}
+
+impl<T: Transport> Future for LogsChunked<T> {
+    type Item = Vec<Log>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let logs = try_ready!(self.inner.poll());
+        Ok(Async::Ready(logs.into_iter().flatten().collect()))
+    }
+}
+
+/// Future resolving to the state of an account, see `Eth::account_state`.
+pub struct AccountStateFuture<T: Transport> {
+    inner: Join3<CallFuture<U256, T::Out>, CallFuture<U256, T::Out>, CallFuture<Bytes, T::Out>>,
+}
+
+impl<T: Transport> Future for AccountStateFuture<T> {
+    type Item = AccountState;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let (balance, nonce, code) = try_ready!(self.inner.poll());
+        Ok(Async::Ready(AccountState {
+            balance,
+            nonce,
+            code_hash: keccak256(&code.0).into(),
+            has_code: !code.0.is_empty(),
+        }))
+    }
+}
+
+enum AccessListComparisonState<O> {
+    CreateAccessList(Join<CallFuture<U256, O>, CallFuture<AccessListWithGasUsed, O>>),
+    EstimateWithAccessList(U256, Option<AccessList>, CallFuture<U256, O>),
+}
+
+/// Future resolving to the gas of a call with and without an access list, see `Eth::compare_access_list`.
+pub struct AccessListComparisonFuture<T: Transport> {
+    eth: Eth<T>,
+    req: CallRequest,
+    block: Option<BlockNumber>,
+    state: AccessListComparisonState<T::Out>,
+}
+
+impl<T: Transport> Future for AccessListComparisonFuture<T> {
+    type Item = AccessListComparison;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let next_state = match self.state {
+                AccessListComparisonState::CreateAccessList(ref mut future) => {
+                    let (estimate, generated) = try_ready!(future.poll());
+                    let req = CallRequest {
+                        access_list: Some(generated.access_list.clone()),
+                        ..self.req.clone()
+                    };
+                    let future = self.eth.estimate_gas(req, self.block);
+                    AccessListComparisonState::EstimateWithAccessList(estimate, Some(generated.access_list), future)
+                }
+                AccessListComparisonState::EstimateWithAccessList(estimate, ref mut access_list, ref mut future) => {
+                    let with_access_list = try_ready!(future.poll());
+                    return Ok(Async::Ready(AccessListComparison {
+                        access_list: access_list
+                            .take()
+                            .expect("AccessListComparisonFuture polled after completion"),
+                        gas_without_access_list: estimate,
+                        gas_with_access_list: with_access_list,
+                    }));
+                }
+            };
+            self.state = next_state;
+        }
+    }
+}
+
+/// Future resolving to `true` if a transaction condition is not yet met by the chain head.
+pub struct CheckCondition<T: Transport> {
+    condition: TransactionCondition,
+    inner: CallFuture<Option<Block<H256>>, T::Out>,
+}
+
+impl<T: Transport> Future for CheckCondition<T> {
+    type Item = bool;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let head = match try_ready!(self.inner.poll()) {
+            Some(head) => head,
+            None => return Ok(Async::Ready(true)),
+        };
+
+        let pending = match self.condition {
+            TransactionCondition::Block(number) => match head.number {
+                Some(head) => number > head.as_u64(),
+                None => true,
+            },
+            TransactionCondition::Timestamp(timestamp) => U256::from(timestamp) > head.timestamp,
+        };
+        if !pending {
+            log::warn!(
+                "Transaction condition {:?} is already met by block {:?}",
+                self.condition,
+                head.number
+            );
+        }
+
+        Ok(Async::Ready(pending))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::Future;
     use serde_json::json;
 
     use crate::api::Namespace;
+    use crate::helpers::tests::TestTransport;
     use crate::rpc::Value;
+    use crate::signing::eip712::TypedData;
+    use crate::signing::keccak256;
+    use crate::transports::Batch;
     use crate::types::{
-        Address, Block, BlockId, BlockNumber, Bytes, CallRequest, FilterBuilder, Log, SyncInfo, SyncState, Transaction,
-        TransactionId, TransactionReceipt, TransactionRequest, Work, H256, H520, H64,
+        AccessListItem, AccessListWithGasUsed, AccountState, Address, Block, BlockId, BlockNumber, Bytes, CallRequest,
+        FeeHistory, FilterBuilder, Log, Proof, RawTransaction, SyncInfo, SyncState, Transaction, TransactionCondition,
+        TransactionId, TransactionReceipt, TransactionRequest, Work, H256, H520, H64, U256,
     };
 
     use super::Eth;
@@ -400,6 +750,26 @@ mod tests {
     "input": "0x603880600c6000396000f300603880600c6000396000f3603880600c6000396000f360"
   }"#;
 
+    const EXAMPLE_TYPED_DATA: &str = r#"{"domain":{"chainId":1},"message":{"contents":"Hello, Bob!"},"primaryType":"Mail","types":{"EIP712Domain":[{"name":"chainId","type":"uint256"}],"Mail":[{"name":"contents","type":"string"}]}}"#;
+
+    // taken from RPC docs.
+    const EXAMPLE_RAW_TX: &'static str = r#"{
+    "raw": "0xd46e8dd67c5d32be8d46e8dd67c5d32be8058bb8eb970870f072445675058bb8eb970870f072445675",
+    "tx": {
+      "hash": "0xc6ef2fc5426d6ad6fd9e2a26abeab0aa2411b7ab17f30a99d3cb96aed1d1055b",
+      "nonce": "0x0",
+      "blockHash": null,
+      "blockNumber": null,
+      "transactionIndex": null,
+      "from": "0x0000000000000000000000000000000000000123",
+      "to": "0x0000000000000000000000000000000000000123",
+      "value": "0x1",
+      "gas": "0x7f110",
+      "gasPrice": "0x1",
+      "input": "0x"
+    }
+  }"#;
+
     // taken from RPC docs.
     const EXAMPLE_RECEIPT: &'static str = r#"{
     "hash": "0xb903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238",
@@ -415,6 +785,16 @@ mod tests {
     "logs": []
   }"#;
 
+    const EXAMPLE_FEE_HISTORY: &str = r#"{
+    "oldestBlock": "0xfab8ac",
+    "baseFeePerGas": ["0x3da8e7618", "0x3e1ba3b1b", "0x3dbd3e7e4"],
+    "gasUsedRatio": [0.5290747666666666, 0.49240453333333334],
+    "reward": [["0x59682f00", "0x9502f900"], ["0x59682f00", "0x77359400"]]
+  }"#;
+
+    // shared with the `Proof` tests, which check that it is a consistent state trie proof
+    const EXAMPLE_PROOF: &str = include_str!("../types/res/proof.json");
+
     rpc_test! (
     Eth:accounts => "eth_accounts";
     Value::Array(vec![Value::String("0x0000000000000000000000000000000000000123".into())]) => vec![Address::from_low_u64_be(0x123)]
@@ -430,12 +810,38 @@ mod tests {
       from: None, to: Address::from_low_u64_be(0x123),
       gas: None, gas_price: None,
       value: Some(0x1.into()), data: None,
+      access_list: None,
     }, None
     =>
     "eth_call", vec![r#"{"to":"0x0000000000000000000000000000000000000123","value":"0x1"}"#, r#""latest""#];
     Value::String("0x010203".into()) => Bytes(vec![1, 2, 3])
   );
 
+    rpc_test! (
+    Eth:create_access_list, CallRequest {
+      from: None, to: Address::from_low_u64_be(0x123),
+      gas: None, gas_price: None,
+      value: None, data: Some(Bytes(vec![1, 2, 3])),
+      access_list: None,
+    }, None
+    =>
+    "eth_createAccessList", vec![r#"{"data":"0x010203","to":"0x0000000000000000000000000000000000000123"}"#, r#""latest""#];
+    ::serde_json::json!({
+      "accessList": [{
+        "address": "0x0000000000000000000000000000000000000123",
+        "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+      }],
+      "gasUsed": "0x6b3a"
+    })
+    => AccessListWithGasUsed {
+      access_list: vec![AccessListItem {
+        address: Address::from_low_u64_be(0x123),
+        storage_keys: vec![H256::from_low_u64_be(1)],
+      }],
+      gas_used: 0x6b3a.into(),
+    }
+  );
+
     rpc_test! (
     Eth:coinbase => "eth_coinbase";
     Value::String("0x0000000000000000000000000000000000000123".into()) => Address::from_low_u64_be(0x123)
@@ -461,6 +867,7 @@ mod tests {
       from: None, to: Address::from_low_u64_be(0x123),
       gas: None, gas_price: None,
       value: Some(0x1.into()), data: None,
+      access_list: None,
     }, None
     =>
     "eth_estimateGas", vec![r#"{"to":"0x0000000000000000000000000000000000000123","value":"0x1"}"#];
@@ -472,12 +879,28 @@ mod tests {
       from: None, to: Address::from_low_u64_be(0x123),
       gas: None, gas_price: None,
       value: Some(0x1.into()), data: None,
+      access_list: None,
     }, Some(0x123.into())
     =>
     "eth_estimateGas", vec![r#"{"to":"0x0000000000000000000000000000000000000123","value":"0x1"}"#, r#""0x123""#];
     Value::String("0x123".into()) => 0x123
   );
 
+  rpc_test! (
+    Eth:estimate_gas:with_access_list, CallRequest {
+      from: None, to: Address::from_low_u64_be(0x123),
+      gas: None, gas_price: None,
+      value: None, data: None,
+      access_list: Some(vec![AccessListItem {
+        address: Address::from_low_u64_be(0x456),
+        storage_keys: vec![H256::from_low_u64_be(0x1)],
+      }]),
+    }, None
+    =>
+    "eth_estimateGas", vec![r#"{"accessList":[{"address":"0x0000000000000000000000000000000000000456","storageKeys":["0x0000000000000000000000000000000000000000000000000000000000000001"]}],"to":"0x0000000000000000000000000000000000000123"}"#];
+    Value::String("0x123".into()) => 0x123
+  );
+
     rpc_test! (
     Eth:gas_price => "eth_gasPrice";
     Value::String("0x123".into()) => 0x123
@@ -490,12 +913,31 @@ mod tests {
     Value::String("0x123".into()) => 0x123
   );
 
+    rpc_test! (
+    Eth:max_priority_fee_per_gas => "eth_maxPriorityFeePerGas";
+    Value::String("0x3b9aca00".into()) => 1_000_000_000
+  );
+
+    rpc_test! (
+    Eth:blob_base_fee => "eth_blobBaseFee";
+    Value::String("0x3f".into()) => 0x3f
+  );
+
     rpc_test! (
     Eth:logs, FilterBuilder::default().build() => "eth_getLogs", vec!["{}"];
     Value::Array(vec![::serde_json::from_str(EXAMPLE_LOG).unwrap()])
     => vec![::serde_json::from_str::<Log>(EXAMPLE_LOG).unwrap()]
   );
 
+    rpc_test! (
+    Eth:logs:logs_by_block_hash,
+    FilterBuilder::default().from_block(BlockNumber::Earliest).block_hash(H256::from_low_u64_be(0x123)).build()
+    =>
+    "eth_getLogs", vec![r#"{"blockHash":"0x0000000000000000000000000000000000000000000000000000000000000123"}"#];
+    Value::Array(vec![])
+    => vec![]
+  );
+
     rpc_test! (
     Eth:block:block_by_hash, BlockId::Hash(H256::from_low_u64_be(0x123))
     =>
@@ -546,11 +988,27 @@ mod tests {
     Value::Array(vec![]) => vec![]
   );
 
+    rpc_test! (
+    Eth:fee_history, 0x2, BlockNumber::Latest, Some(vec![25.0, 75.0])
+    =>
+    "eth_feeHistory", vec![r#""0x2""#, r#""latest""#, r#"[25.0,75.0]"#];
+    ::serde_json::from_str::<Value>(EXAMPLE_FEE_HISTORY).unwrap()
+    => ::serde_json::from_str::<FeeHistory>(EXAMPLE_FEE_HISTORY).unwrap()
+  );
+
     rpc_test! (
     Eth:chain_id => "eth_chainId";
     Value::String("0x123".into()) => 0x123
   );
 
+    rpc_test! (
+    Eth:proof, Address::from_low_u64_be(0x123), vec![U256::zero()], None
+    =>
+    "eth_getProof", vec![r#""0x0000000000000000000000000000000000000123""#, r#"["0x0"]"#, r#""latest""#];
+    ::serde_json::from_str::<Value>(EXAMPLE_PROOF).unwrap()
+    => ::serde_json::from_str::<Proof>(EXAMPLE_PROOF).unwrap()
+  );
+
     rpc_test! (
     Eth:storage, Address::from_low_u64_be(0x123), 0x456, None
     =>
@@ -688,6 +1146,11 @@ mod tests {
     Value::String("0x123".into()) => "0x123"
   );
 
+    rpc_test! (
+    Eth:protocol_version:protocol_version_decimal => "eth_protocolVersion";
+    Value::String("65".into()) => "65"
+  );
+
     rpc_test! (
     Eth:send_raw_transaction, Bytes(vec![1, 2, 3, 4])
     =>
@@ -699,8 +1162,9 @@ mod tests {
     Eth:send_transaction, TransactionRequest {
       from: Address::from_low_u64_be(0x123), to: Some(Address::from_low_u64_be(0x123)),
       gas: None, gas_price: Some(0x1.into()),
+      max_fee_per_gas: None, max_priority_fee_per_gas: None,
       value: Some(0x1.into()), data: None,
-      nonce: None, condition: None,
+      nonce: None, condition: None, access_list: None, transaction_type: None,
     }
     =>
     "eth_sendTransaction", vec![r#"{"from":"0x0000000000000000000000000000000000000123","gasPrice":"0x1","to":"0x0000000000000000000000000000000000000123","value":"0x1"}"#];
@@ -708,12 +1172,33 @@ mod tests {
   );
 
     rpc_test! (
-    Eth:sign, H256::from_low_u64_be(0x123), Bytes(vec![1, 2, 3, 4])
+    Eth:sign, Address::from_low_u64_be(0x123), Bytes(vec![1, 2, 3, 4])
     =>
     "eth_sign", vec![r#""0x0000000000000000000000000000000000000123""#, r#""0x01020304""#];
     Value::String("0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000123".into()) => H520::from_low_u64_be(0x123)
   );
 
+    rpc_test! (
+    Eth:sign_typed_data, Address::from_low_u64_be(0x123), ::serde_json::from_str::<TypedData>(EXAMPLE_TYPED_DATA).unwrap()
+    =>
+    "eth_signTypedData_v4", vec![r#""0x0000000000000000000000000000000000000123""#, EXAMPLE_TYPED_DATA];
+    Value::String("0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000123".into()) => H520::from_low_u64_be(0x123)
+  );
+
+    rpc_test! (
+    Eth:sign_transaction, TransactionRequest {
+      from: Address::from_low_u64_be(0x123), to: Some(Address::from_low_u64_be(0x123)),
+      gas: None, gas_price: Some(0x1.into()),
+      max_fee_per_gas: None, max_priority_fee_per_gas: None,
+      value: Some(0x1.into()), data: None,
+      nonce: None, condition: None, access_list: None, transaction_type: None,
+    }
+    =>
+    "eth_signTransaction", vec![r#"{"from":"0x0000000000000000000000000000000000000123","gasPrice":"0x1","to":"0x0000000000000000000000000000000000000123","value":"0x1"}"#];
+    ::serde_json::from_str(EXAMPLE_RAW_TX).unwrap()
+    => ::serde_json::from_str::<RawTransaction>(EXAMPLE_RAW_TX).unwrap()
+  );
+
     rpc_test! (
     Eth:submit_hashrate, 0x123, H256::from_low_u64_be(0x456)
     =>
@@ -737,4 +1222,289 @@ mod tests {
       Eth:syncing:not_syncing => "eth_syncing";
       Value::Bool(false) => SyncState::NotSyncing
     }
+
+    fn fill_fees_request() -> TransactionRequest {
+        TransactionRequest {
+            from: Address::from_low_u64_be(0x123),
+            to: Some(Address::from_low_u64_be(0x123)),
+            gas: None,
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            value: Some(0x1.into()),
+            data: None,
+            nonce: None,
+            condition: None,
+            access_list: None,
+            transaction_type: None,
+        }
+    }
+
+    #[test]
+    fn should_compare_gas_with_and_without_access_list() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(json!("0x6d60"));
+        transport.add_response(json!({
+            "accessList": [{
+                "address": "0x0000000000000000000000000000000000000123",
+                "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+            }],
+            "gasUsed": "0x5b3a"
+        }));
+        transport.add_response(json!("0x6b3a"));
+        let request = CallRequest {
+            from: None,
+            to: Address::from_low_u64_be(0x123),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(Bytes(vec![1, 2, 3])),
+            access_list: None,
+        };
+
+        // when
+        let comparison = Eth::new(&transport).compare_access_list(request, None).wait().unwrap();
+
+        // then
+        let request = r#"{"data":"0x010203","to":"0x0000000000000000000000000000000000000123"}"#;
+        transport.assert_request("eth_estimateGas", &[request.into()]);
+        transport.assert_request("eth_createAccessList", &[request.into(), r#""latest""#.into()]);
+        let request = r#"{"accessList":[{"address":"0x0000000000000000000000000000000000000123","storageKeys":["0x0000000000000000000000000000000000000000000000000000000000000001"]}],"data":"0x010203","to":"0x0000000000000000000000000000000000000123"}"#;
+        transport.assert_request("eth_estimateGas", &[request.into()]);
+        transport.assert_no_more_requests();
+        assert_eq!(comparison.gas_without_access_list, 0x6d60.into());
+        assert_eq!(comparison.gas_with_access_list, 0x6b3a.into());
+        assert_eq!(comparison.access_list_cost(), 4300.into());
+        assert!(comparison.saves_gas());
+    }
+
+    #[test]
+    fn account_state_should_batch_requests() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(json!("0x64"));
+        transport.add_response(json!("0x2"));
+        transport.add_response(json!("0x6001"));
+        let batch = Batch::new(transport.clone());
+        let address = Address::from_low_u64_be(0x123);
+
+        // when
+        let state = Eth::new(&batch).account_state(address, None);
+        batch.submit_batch().wait().unwrap();
+
+        // then
+        let address = r#""0x0000000000000000000000000000000000000123""#;
+        transport.assert_request("eth_getBalance", &[address.into(), r#""latest""#.into()]);
+        transport.assert_request("eth_getTransactionCount", &[address.into(), r#""latest""#.into()]);
+        transport.assert_request("eth_getCode", &[address.into(), r#""latest""#.into()]);
+        transport.assert_no_more_requests();
+        assert_eq!(
+            state.wait(),
+            Ok(AccountState {
+                balance: 0x64.into(),
+                nonce: 2.into(),
+                code_hash: keccak256(&[0x60, 0x01]).into(),
+                has_code: true,
+            })
+        );
+    }
+
+    #[test]
+    fn transaction_receipts_should_tolerate_pending() {
+        // given
+        let receipt: Value = ::serde_json::from_str(EXAMPLE_RECEIPT).unwrap();
+        let mut transport = TestTransport::default();
+        transport.add_response(receipt.clone());
+        transport.add_response(Value::Null);
+        transport.add_response(receipt.clone());
+        let batch = Batch::new(transport.clone());
+        let hashes = (1..=3).map(H256::from_low_u64_be).collect::<Vec<_>>();
+
+        // when
+        let receipts = Eth::new(&batch).transaction_receipts(hashes.clone());
+        batch.submit_batch().wait().unwrap();
+
+        // then
+        for hash in &hashes {
+            transport.assert_request("eth_getTransactionReceipt", &[::serde_json::to_string(hash).unwrap()]);
+        }
+        transport.assert_no_more_requests();
+        let receipt: TransactionReceipt = ::serde_json::from_value(receipt).unwrap();
+        assert_eq!(receipts.wait(), Ok(vec![Some(receipt.clone()), None, Some(receipt)]));
+    }
+
+    #[test]
+    fn logs_chunked_should_split_addresses() {
+        // given
+        let log: Value = ::serde_json::from_str(EXAMPLE_LOG).unwrap();
+        let mut transport = TestTransport::default();
+        transport.add_response(Value::Array(vec![log.clone()]));
+        transport.add_response(Value::Array(vec![log]));
+        let filter = FilterBuilder::default()
+            .address((1..=3).map(Address::from_low_u64_be).collect())
+            .build();
+
+        // when
+        let result = Eth::new(&transport).logs_chunked(filter, 2).wait();
+
+        // then
+        transport.assert_request(
+            "eth_getLogs",
+            &[r#"{"address":["0x0000000000000000000000000000000000000001","0x0000000000000000000000000000000000000002"]}"#.into()],
+        );
+        transport.assert_request(
+            "eth_getLogs",
+            &[r#"{"address":"0x0000000000000000000000000000000000000003"}"#.into()],
+        );
+        transport.assert_no_more_requests();
+        assert_eq!(result.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn fill_transaction_fees_should_use_gas_price_before_london() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(::serde_json::from_str(EXAMPLE_BLOCK).unwrap());
+        transport.add_response(Value::String("0x64".into()));
+
+        // when
+        let result = Eth::new(&transport).fill_transaction_fees(fill_fees_request()).wait();
+
+        // then
+        transport.assert_request("eth_getBlockByNumber", &[r#""latest""#.into(), "false".into()]);
+        transport.assert_request("eth_gasPrice", &[]);
+        transport.assert_no_more_requests();
+        let tx = result.unwrap();
+        assert_eq!(tx.gas_price, Some(0x64.into()));
+        assert_eq!(tx.max_fee_per_gas, None);
+        assert_eq!(tx.max_priority_fee_per_gas, None);
+    }
+
+    #[test]
+    fn fill_transaction_fees_should_use_eip1559_fees_after_london() {
+        // given
+        let mut block: Value = ::serde_json::from_str(EXAMPLE_BLOCK).unwrap();
+        block["baseFeePerGas"] = Value::String("0x50".into());
+        let mut transport = TestTransport::default();
+        transport.add_response(block);
+        transport.add_response(Value::String("0x64".into()));
+
+        // when
+        let result = Eth::new(&transport).fill_transaction_fees(fill_fees_request()).wait();
+
+        // then
+        transport.assert_request("eth_getBlockByNumber", &[r#""latest""#.into(), "false".into()]);
+        transport.assert_request("eth_gasPrice", &[]);
+        transport.assert_no_more_requests();
+        let tx = result.unwrap();
+        assert_eq!(tx.gas_price, None);
+        assert_eq!(tx.max_priority_fee_per_gas, Some(0x14.into()));
+        assert_eq!(tx.max_fee_per_gas, Some(0xb4.into()));
+    }
+
+    #[test]
+    fn fill_transaction_fees_should_saturate_max_fee_for_huge_base_fee() {
+        // given
+        let mut block: Value = ::serde_json::from_str(EXAMPLE_BLOCK).unwrap();
+        block["baseFeePerGas"] = Value::String(format!("0x{}", "f".repeat(63) + "e"));
+        let mut transport = TestTransport::default();
+        transport.add_response(block);
+        transport.add_response(Value::String("0x64".into()));
+
+        // when
+        let result = Eth::new(&transport).fill_transaction_fees(fill_fees_request()).wait();
+
+        // then
+        let tx = result.unwrap();
+        assert_eq!(tx.gas_price, None);
+        assert_eq!(tx.max_priority_fee_per_gas, Some(0.into()));
+        assert_eq!(tx.max_fee_per_gas, Some(U256::max_value()));
+    }
+
+    #[test]
+    fn check_condition_should_detect_mined_block() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(::serde_json::from_str(EXAMPLE_BLOCK).unwrap());
+        transport.add_response(::serde_json::from_str(EXAMPLE_BLOCK).unwrap());
+        let eth = Eth::new(&transport);
+
+        // when
+        let past = eth.check_condition(TransactionCondition::Block(0x1b4)).wait();
+        let future = eth.check_condition(TransactionCondition::Block(0x1b5)).wait();
+
+        // then
+        transport.assert_request("eth_getBlockByNumber", &[r#""latest""#.into(), "false".into()]);
+        transport.assert_request("eth_getBlockByNumber", &[r#""latest""#.into(), "false".into()]);
+        transport.assert_no_more_requests();
+        assert_eq!(past, Ok(false));
+        assert_eq!(future, Ok(true));
+    }
+
+    #[test]
+    fn check_condition_should_compare_timestamps() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(::serde_json::from_str(EXAMPLE_BLOCK).unwrap());
+        transport.add_response(::serde_json::from_str(EXAMPLE_BLOCK).unwrap());
+        let eth = Eth::new(&transport);
+
+        // when
+        let past = eth.check_condition(TransactionCondition::Timestamp(0x54e3_4e8e)).wait();
+        let future = eth.check_condition(TransactionCondition::Timestamp(0x54e3_4e8f)).wait();
+
+        // then
+        assert_eq!(past, Ok(false));
+        assert_eq!(future, Ok(true));
+    }
+
+    #[test]
+    fn call_should_use_default_from() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(Value::String("0x010203".into()));
+        transport.add_response(Value::String("0x010203".into()));
+        let eth = Eth::new(&transport).with_default_from(Address::from_low_u64_be(0x5));
+        let req = CallRequest {
+            from: None,
+            to: Address::from_low_u64_be(0x123),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: None,
+            access_list: None,
+        };
+
+        // when
+        let result = eth.call(req.clone(), None).wait();
+        let explicit = eth
+            .call(
+                CallRequest {
+                    from: Some(Address::from_low_u64_be(0x6)),
+                    ..req
+                },
+                None,
+            )
+            .wait();
+
+        // then
+        transport.assert_request(
+            "eth_call",
+            &[
+                r#"{"from":"0x0000000000000000000000000000000000000005","to":"0x0000000000000000000000000000000000000123"}"#.into(),
+                r#""latest""#.into(),
+            ],
+        );
+        transport.assert_request(
+            "eth_call",
+            &[
+                r#"{"from":"0x0000000000000000000000000000000000000006","to":"0x0000000000000000000000000000000000000123"}"#.into(),
+                r#""latest""#.into(),
+            ],
+        );
+        transport.assert_no_more_requests();
+        assert_eq!(result, Ok(Bytes(vec![1, 2, 3])));
+        assert_eq!(explicit, Ok(Bytes(vec![1, 2, 3])));
+    }
 }
@@ -5,9 +5,10 @@ use serde::de::DeserializeOwned;
 use std::marker::PhantomData;
 use std::time::Duration;
 use std::vec;
-use tokio_timer::{Interval, Timer};
+use tokio_timer::Sleep;
 
 use crate::api::Namespace;
+use crate::confirm::Backoff;
 use crate::helpers::{self, CallFuture};
 use crate::types::{Filter, Log, H256};
 use crate::{rpc, Error, Transport};
@@ -16,23 +17,38 @@ use crate::{rpc, Error, Transport};
 #[derive(Debug)]
 pub struct FilterStream<T: Transport, I> {
     base: BaseFilter<T, I>,
-    interval: Interval,
+    backoff: Backoff,
+    delay: Duration,
+    sleep: Sleep,
     state: FilterStreamState<I, T::Out>,
 }
 
 impl<T: Transport, I> FilterStream<T, I> {
-    fn new(base: BaseFilter<T, I>, poll_interval: Duration) -> Self {
+    fn new(base: BaseFilter<T, I>, backoff: Backoff) -> Self {
+        let delay = backoff.initial();
         FilterStream {
             base,
-            interval: Timer::default().interval(poll_interval),
+            backoff,
+            delay,
+            sleep: helpers::timer().sleep(delay),
             state: FilterStreamState::WaitForInterval,
         }
     }
 
+    /// Returns the delay before the next poll.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
     /// Borrow a transport from this filter.
     pub fn transport(&self) -> &T {
         self.base.transport()
     }
+
+    /// Uninstalls the filter polled by this stream.
+    pub(crate) fn uninstall(&self) -> CallFuture<bool, T::Out> {
+        self.base.uninstall_internal()
+    }
 }
 
 #[derive(Debug)]
@@ -50,7 +66,9 @@ impl<T: Transport, I: DeserializeOwned> Stream for FilterStream<T, I> {
         loop {
             let next_state = match self.state {
                 FilterStreamState::WaitForInterval => {
-                    let _ready = try_ready!(self.interval.poll().map_err(|_| Error::Unreachable));
+                    let _ready = try_ready!(self.sleep.poll().map_err(|_| Error::Unreachable));
+                    self.delay = self.backoff.next(self.delay);
+                    self.sleep = self.sleep.timer().sleep(self.delay);
                     let id = helpers::serialize(&self.base.id);
                     let future = CallFuture::new(self.base.transport.execute("eth_getFilterChanges", vec![id]));
                     FilterStreamState::GetFilterChanges(future)
@@ -145,7 +163,12 @@ impl<T: Transport, I> BaseFilter<T, I> {
 
     /// Returns the stream of items which automatically polls the server
     pub fn stream(self, poll_interval: Duration) -> FilterStream<T, I> {
-        FilterStream::new(self, poll_interval)
+        FilterStream::new(self, Backoff::Fixed(poll_interval))
+    }
+
+    /// Returns the stream of items which polls the server with delays given by `backoff`
+    pub fn stream_with_backoff(self, backoff: Backoff) -> FilterStream<T, I> {
+        FilterStream::new(self, backoff)
     }
 
     /// Uninstalls the filter
@@ -256,6 +279,7 @@ mod tests {
     use std::time::Duration;
 
     use crate::api::Namespace;
+    use crate::confirm::Backoff;
     use crate::helpers::tests::TestTransport;
     use crate::types::{Address, Bytes, FilterBuilder, Log, H256};
 
@@ -444,6 +468,42 @@ mod tests {
         transport.assert_request("eth_getFilterChanges", &[r#""0x123""#.into()]);
     }
 
+    #[test]
+    fn blocks_filter_stream_with_backoff() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.set_response(Value::String("0x123".into()));
+        transport.add_response(Value::Array(vec![]));
+        transport.add_response(Value::Array(vec![]));
+        transport.add_response(Value::Array(vec![]));
+        transport.add_response(Value::Array(vec![Value::String(
+            r#"0x0000000000000000000000000000000000000000000000000000000000000456"#.into(),
+        )]));
+        let (result, delay) = {
+            let eth = EthFilter::new(&transport);
+
+            // when
+            let filter = eth.create_blocks_filter().wait().unwrap();
+            let mut stream = filter.stream_with_backoff(Backoff::Exponential {
+                initial: Duration::from_millis(1),
+                max: Duration::from_millis(6),
+            });
+            assert_eq!(stream.delay(), Duration::from_millis(1));
+            let result = stream.by_ref().take(1).collect().wait();
+            (result, stream.delay())
+        };
+
+        // then
+        assert_eq!(result, Ok(vec![H256::from_low_u64_be(0x456)]));
+        assert_eq!(delay, Duration::from_millis(6));
+        transport.assert_request("eth_newBlockFilter", &[]);
+        transport.assert_request("eth_getFilterChanges", &[r#""0x123""#.into()]);
+        transport.assert_request("eth_getFilterChanges", &[r#""0x123""#.into()]);
+        transport.assert_request("eth_getFilterChanges", &[r#""0x123""#.into()]);
+        transport.assert_request("eth_getFilterChanges", &[r#""0x123""#.into()]);
+        transport.assert_no_more_requests();
+    }
+
     #[test]
     fn pending_transactions_filter() {
         // given
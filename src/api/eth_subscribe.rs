@@ -1,11 +1,13 @@
 //! `Eth` namespace, subscriptions
 
 use std::marker::PhantomData;
+use std::time::{Duration, Instant};
 
-use crate::api::Namespace;
+use crate::api::{Eth, Namespace};
 use crate::helpers::{self, CallFuture};
-use crate::types::{BlockHeader, Filter, Log, SyncState, H256};
-use crate::{DuplexTransport, Error};
+use crate::types::{BlockHeader, Filter, Log, SyncState, Transaction, TransactionId, H256};
+use crate::{DuplexTransport, Error, Transport};
+use futures::stream::FuturesUnordered;
 use futures::{Async, Future, Poll, Stream};
 use serde;
 use serde_json;
@@ -46,17 +48,23 @@ impl From<String> for SubscriptionId {
 pub struct SubscriptionStream<T: DuplexTransport, I> {
     transport: T,
     id: SubscriptionId,
+    unsubscribe_method: &'static str,
     rx: T::NotificationStream,
+    created: Instant,
+    last_notification: Option<Instant>,
     _marker: PhantomData<I>,
 }
 
 impl<T: DuplexTransport, I> SubscriptionStream<T, I> {
-    fn new(transport: T, id: SubscriptionId) -> Self {
+    fn new(transport: T, id: SubscriptionId, unsubscribe_method: &'static str) -> Self {
         let rx = transport.subscribe(&id);
         SubscriptionStream {
             transport,
             id,
+            unsubscribe_method,
             rx,
+            created: Instant::now(),
+            last_notification: None,
             _marker: PhantomData,
         }
     }
@@ -66,11 +74,25 @@ impl<T: DuplexTransport, I> SubscriptionStream<T, I> {
         &self.id
     }
 
+    /// Returns the time the last notification was received, `None` if there were none yet.
+    pub fn last_notification(&self) -> Option<Instant> {
+        self.last_notification
+    }
+
+    /// Returns the time elapsed since the last notification (or since subscribing if
+    /// there were no notifications yet).
+    ///
+    /// Can be used to detect a stalled subscription, note that the expected interval
+    /// depends on the subscription type, e.g. new heads should arrive with every block.
+    pub fn time_since_last_notification(&self) -> Duration {
+        self.last_notification.unwrap_or(self.created).elapsed()
+    }
+
     /// Unsubscribe from the event represented by this stream
     pub fn unsubscribe(self) -> CallFuture<bool, T::Out> {
         let &SubscriptionId(ref id) = &self.id;
         let id = helpers::serialize(&id);
-        CallFuture::new(self.transport.execute("eth_unsubscribe", vec![id]))
+        CallFuture::new(self.transport.execute(self.unsubscribe_method, vec![id]))
     }
 }
 
@@ -84,7 +106,10 @@ where
 
     fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
         match self.rx.poll() {
-            Ok(Async::Ready(Some(x))) => serde_json::from_value(x).map(Async::Ready).map_err(Into::into),
+            Ok(Async::Ready(Some(x))) => {
+                self.last_notification = Some(Instant::now());
+                serde_json::from_value(x).map(Async::Ready).map_err(Into::into)
+            }
             Ok(Async::Ready(None)) => Ok(Async::Ready(None)),
             Ok(Async::NotReady) => Ok(Async::NotReady),
             Err(e) => Err(e),
@@ -92,17 +117,72 @@ where
     }
 }
 
+impl<T: DuplexTransport> SubscriptionStream<T, H256> {
+    /// Fetches the full transaction for every pending transaction hash.
+    ///
+    /// At most `max_concurrent` transactions are fetched at the same time. Hashes of
+    /// transactions which can't be found anymore (already mined or dropped) are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrent` is `0`, as no transaction could ever be fetched.
+    pub fn with_full_transactions<E: Transport>(self, eth: Eth<E>, max_concurrent: usize) -> FullTransactions<T, E> {
+        assert!(max_concurrent > 0, "max_concurrent must be greater than 0");
+        FullTransactions {
+            hashes: self,
+            hashes_done: false,
+            eth,
+            pending: FuturesUnordered::new(),
+            max_concurrent,
+        }
+    }
+}
+
 impl<T: DuplexTransport, I> Drop for SubscriptionStream<T, I> {
     fn drop(&mut self) {
         self.transport.unsubscribe(self.id());
     }
 }
 
+/// Stream of full pending transactions, see `SubscriptionStream::with_full_transactions`.
+pub struct FullTransactions<T: DuplexTransport, E: Transport> {
+    hashes: SubscriptionStream<T, H256>,
+    hashes_done: bool,
+    eth: Eth<E>,
+    pending: FuturesUnordered<CallFuture<Option<Transaction>, E::Out>>,
+    max_concurrent: usize,
+}
+
+impl<T: DuplexTransport, E: Transport> Stream for FullTransactions<T, E> {
+    type Item = Transaction;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            while !self.hashes_done && self.pending.len() < self.max_concurrent {
+                match self.hashes.poll()? {
+                    Async::Ready(Some(hash)) => self.pending.push(self.eth.transaction(TransactionId::Hash(hash))),
+                    Async::Ready(None) => self.hashes_done = true,
+                    Async::NotReady => break,
+                }
+            }
+
+            match self.pending.poll()? {
+                Async::Ready(Some(Some(tx))) => return Ok(Async::Ready(Some(tx))),
+                Async::Ready(Some(None)) => log::trace!("Skipping pending transaction which is no longer available"),
+                Async::Ready(None) if self.hashes_done => return Ok(Async::Ready(None)),
+                Async::Ready(None) | Async::NotReady => return Ok(Async::NotReady),
+            }
+        }
+    }
+}
+
 /// A result of calling a subscription.
 #[derive(Debug)]
 pub struct SubscriptionResult<T: DuplexTransport, I> {
     transport: T,
     inner: CallFuture<String, T::Out>,
+    unsubscribe_method: &'static str,
     _marker: PhantomData<I>,
 }
 
@@ -112,9 +192,16 @@ impl<T: DuplexTransport, I> SubscriptionResult<T, I> {
         SubscriptionResult {
             transport,
             inner: id_future,
+            unsubscribe_method: "eth_unsubscribe",
             _marker: PhantomData,
         }
     }
+
+    /// Sets the method used to cancel the subscription, `eth_unsubscribe` by default.
+    pub(crate) fn with_unsubscribe_method(mut self, method: &'static str) -> Self {
+        self.unsubscribe_method = method;
+        self
+    }
 }
 
 impl<T, I> Future for SubscriptionResult<T, I>
@@ -130,6 +217,7 @@ where
             Ok(Async::Ready(id)) => Ok(Async::Ready(SubscriptionStream::new(
                 self.transport.clone(),
                 SubscriptionId(id),
+                self.unsubscribe_method,
             ))),
             Ok(Async::NotReady) => Ok(Async::NotReady),
             Err(e) => Err(e),
@@ -167,3 +255,139 @@ impl<T: DuplexTransport> EthSubscribe<T> {
         SubscriptionResult::new(self.transport().clone(), id_future)
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{EthSubscribe, SubscriptionId};
+    use crate::api::{Eth, Namespace};
+    use crate::helpers::tests::TestTransport;
+    use crate::rpc;
+    use crate::types::Transaction;
+    use crate::{DuplexTransport, Error, RequestId, Transport};
+    use futures::{stream, Async, Future, Stream};
+    use std::thread;
+    use std::time::Duration;
+
+    const EXAMPLE_TX: &str = r#"{
+    "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+    "nonce": "0x0",
+    "blockHash": null,
+    "blockNumber": null,
+    "transactionIndex": null,
+    "from": "0x407d73d8a49eeb85d32cf465507dd71d507100c1",
+    "to": "0x853f43d8a49eeb85d32cf465507dd71d507100c1",
+    "value": "0x7f110",
+    "gas": "0x7f110",
+    "gasPrice": "0x09184e72a000",
+    "input": "0x"
+  }"#;
+
+    #[derive(Debug, Default, Clone)]
+    pub(crate) struct NotifyingTransport {
+        pub(crate) inner: TestTransport,
+        pub(crate) notifications: Vec<rpc::Value>,
+    }
+
+    impl Transport for NotifyingTransport {
+        type Out = <TestTransport as Transport>::Out;
+
+        fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+            self.inner.prepare(method, params)
+        }
+
+        fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+            self.inner.send(id, request)
+        }
+    }
+
+    impl DuplexTransport for NotifyingTransport {
+        type NotificationStream = stream::IterOk<::std::vec::IntoIter<rpc::Value>, Error>;
+
+        fn subscribe(&self, _id: &SubscriptionId) -> Self::NotificationStream {
+            stream::iter_ok(self.notifications.clone())
+        }
+
+        fn unsubscribe(&self, _id: &SubscriptionId) {}
+    }
+
+    #[test]
+    fn should_fetch_full_transactions() {
+        // given
+        let mut transport = NotifyingTransport::default();
+        transport.inner.add_response(rpc::Value::String("0x1".into()));
+        transport
+            .inner
+            .add_response(::serde_json::from_str(EXAMPLE_TX).unwrap());
+        transport.inner.add_response(rpc::Value::Null);
+        transport.notifications = vec![
+            rpc::Value::String("0x0000000000000000000000000000000000000000000000000000000000000001".into()),
+            rpc::Value::String("0x0000000000000000000000000000000000000000000000000000000000000002".into()),
+        ];
+        let eth = Eth::new(transport.inner.clone());
+        let stream = EthSubscribe::new(transport.clone())
+            .subscribe_new_pending_transactions()
+            .wait()
+            .unwrap();
+
+        // when
+        let txs = stream.with_full_transactions(eth, 2).collect().wait().unwrap();
+
+        // then
+        transport
+            .inner
+            .assert_request("eth_subscribe", &[r#""newPendingTransactions""#.into()]);
+        transport.inner.assert_request(
+            "eth_getTransactionByHash",
+            &[r#""0x0000000000000000000000000000000000000000000000000000000000000001""#.into()],
+        );
+        transport.inner.assert_request(
+            "eth_getTransactionByHash",
+            &[r#""0x0000000000000000000000000000000000000000000000000000000000000002""#.into()],
+        );
+        assert_eq!(txs, vec![::serde_json::from_str::<Transaction>(EXAMPLE_TX).unwrap()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "max_concurrent must be greater than 0")]
+    fn should_reject_zero_concurrent_transaction_fetches() {
+        // given
+        let mut transport = NotifyingTransport::default();
+        transport.inner.set_response(rpc::Value::String("0x1".into()));
+        let eth = Eth::new(transport.inner.clone());
+        let stream = EthSubscribe::new(transport)
+            .subscribe_new_pending_transactions()
+            .wait()
+            .unwrap();
+
+        // when
+        let _ = stream.with_full_transactions(eth, 0);
+    }
+
+    #[test]
+    fn should_update_last_notification_time() {
+        // given
+        let mut transport = NotifyingTransport::default();
+        transport.inner.set_response(rpc::Value::String("0x1".into()));
+        transport.notifications = vec![
+            rpc::Value::String("0x0000000000000000000000000000000000000000000000000000000000000001".into()),
+            rpc::Value::String("0x0000000000000000000000000000000000000000000000000000000000000002".into()),
+        ];
+        let mut stream = EthSubscribe::new(transport)
+            .subscribe_new_pending_transactions()
+            .wait()
+            .unwrap();
+        assert_eq!(stream.last_notification(), None);
+
+        // when
+        assert!(stream.poll().unwrap().is_ready());
+        let first = stream.last_notification().unwrap();
+        thread::sleep(Duration::from_millis(10));
+        assert!(stream.poll().unwrap().is_ready());
+        let second = stream.last_notification().unwrap();
+
+        // then
+        assert!(second > first);
+        assert!(stream.time_since_last_notification() < first.elapsed());
+        assert_eq!(stream.poll().unwrap(), Async::Ready(None));
+    }
+}
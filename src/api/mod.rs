@@ -1,6 +1,7 @@
 //! `Web3` implementation
 
 mod accounts;
+mod debug;
 mod eth;
 mod eth_filter;
 mod eth_subscribe;
@@ -8,24 +9,33 @@ mod net;
 mod parity;
 mod parity_accounts;
 mod parity_set;
+mod parity_subscribe;
 mod personal;
 mod traces;
 mod web3;
 
-pub use self::accounts::{Accounts, SignTransactionFuture};
-pub use self::eth::Eth;
+pub use self::accounts::{
+    decode_raw_transaction, decode_transaction, validate_raw_transaction, Accounts, SendTransactionFuture,
+    SignTransactionFuture,
+};
+pub use self::debug::Debug;
+pub use self::eth::{
+    AccessListComparisonFuture, AccountStateFuture, CheckCondition, Eth, FillTransactionFees, LogsChunked,
+};
 pub use self::eth_filter::{BaseFilter, CreateFilter, EthFilter, FilterStream};
 pub use self::eth_subscribe::{EthSubscribe, SubscriptionId, SubscriptionResult, SubscriptionStream};
 pub use self::net::Net;
 pub use self::parity::Parity;
 pub use self::parity_accounts::ParityAccounts;
 pub use self::parity_set::ParitySet;
+pub use self::parity_subscribe::ParitySubscribe;
 pub use self::personal::Personal;
 pub use self::traces::Traces;
 pub use self::web3::Web3 as Web3Api;
 
+use crate::transports::batch::{Batch, BatchFuture};
 use crate::types::{Bytes, TransactionRequest, U64};
-use crate::{confirm, DuplexTransport, Error, Transport};
+use crate::{confirm, BatchTransport, DuplexTransport, Error, Transport};
 use futures::IntoFuture;
 use std::time::Duration;
 
@@ -65,6 +75,11 @@ impl<T: Transport> Web3<T> {
         self.api()
     }
 
+    /// Access methods from `debug` namespace
+    pub fn debug(&self) -> debug::Debug<T> {
+        self.api()
+    }
+
     /// Access methods from `eth` namespace
     pub fn eth(&self) -> eth::Eth<T> {
         self.api()
@@ -145,9 +160,31 @@ impl<T: Transport> Web3<T> {
     }
 }
 
+impl<T: BatchTransport> Web3<T> {
+    /// Returns a client queuing all calls until `submit` is called,
+    /// which sends them to the node as a single batch request.
+    pub fn batch(&self) -> Web3<Batch<T>> {
+        Web3::new(Batch::new(self.transport.clone()))
+    }
+}
+
+impl<T: BatchTransport> Web3<Batch<T>> {
+    /// Sends all queued calls as a single batch request.
+    ///
+    /// Futures of the queued calls resolve once the batch response is received.
+    pub fn submit(&self) -> BatchFuture<T::Batch> {
+        self.transport.submit_batch()
+    }
+}
+
 impl<T: DuplexTransport> Web3<T> {
     /// Access subscribe methods from `eth` namespace
     pub fn eth_subscribe(&self) -> eth_subscribe::EthSubscribe<T> {
         self.api()
     }
+
+    /// Access subscribe methods from `parity` namespace
+    pub fn parity_subscribe(&self) -> parity_subscribe::ParitySubscribe<T> {
+        self.api()
+    }
 }
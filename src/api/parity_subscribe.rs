@@ -0,0 +1,84 @@
+//! `Parity` namespace, subscriptions
+
+use crate::api::{Namespace, SubscriptionResult};
+use crate::helpers::{self, CallFuture};
+use crate::rpc::Value;
+use crate::DuplexTransport;
+
+/// `Parity` namespace, subscriptions
+///
+/// OpenEthereum can turn any method into a subscription: the method is called again
+/// whenever its result may have changed (e.g. on new blocks) and the result is pushed
+/// as a notification.
+#[derive(Debug, Clone)]
+pub struct ParitySubscribe<T> {
+    transport: T,
+}
+
+impl<T: DuplexTransport> Namespace<T> for ParitySubscribe<T> {
+    fn new(transport: T) -> Self
+    where
+        Self: Sized,
+    {
+        ParitySubscribe { transport }
+    }
+
+    fn transport(&self) -> &T {
+        &self.transport
+    }
+}
+
+impl<T: DuplexTransport> ParitySubscribe<T> {
+    /// Subscribe to results of calling `method` with `params`
+    pub fn subscribe(&self, method: &str, params: Vec<Value>) -> SubscriptionResult<T, Value> {
+        let method = helpers::serialize(&method);
+        let params = helpers::serialize(&params);
+        let id_future = CallFuture::new(self.transport.execute("parity_subscribe", vec![method, params]));
+        SubscriptionResult::new(self.transport().clone(), id_future).with_unsubscribe_method("parity_unsubscribe")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParitySubscribe;
+    use crate::api::eth_subscribe::tests::NotifyingTransport;
+    use crate::api::Namespace;
+    use crate::rpc::Value;
+    use futures::{Future, Stream};
+
+    #[test]
+    fn should_subscribe_to_balance() {
+        // given
+        let mut transport = NotifyingTransport::default();
+        transport.inner.add_response(Value::String("0x416d77337e24399d".into()));
+        transport.inner.add_response(Value::Bool(true));
+        transport.notifications = vec![Value::String("0x0".into()), Value::String("0x64".into())];
+        let address = Value::String("0x0000000000000000000000000000000000000123".into());
+
+        // when
+        let mut stream = ParitySubscribe::new(transport.clone())
+            .subscribe("eth_getBalance", vec![address])
+            .wait()
+            .unwrap();
+        let balances = stream.by_ref().collect().wait().unwrap();
+        let unsubscribed = stream.unsubscribe().wait();
+
+        // then
+        transport.inner.assert_request(
+            "parity_subscribe",
+            &[
+                r#""eth_getBalance""#.into(),
+                r#"["0x0000000000000000000000000000000000000123"]"#.into(),
+            ],
+        );
+        transport
+            .inner
+            .assert_request("parity_unsubscribe", &[r#""0x416d77337e24399d""#.into()]);
+        transport.inner.assert_no_more_requests();
+        assert_eq!(
+            balances,
+            vec![Value::String("0x0".into()), Value::String("0x64".into())]
+        );
+        assert_eq!(unsubscribed, Ok(true));
+    }
+}
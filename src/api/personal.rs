@@ -2,9 +2,10 @@
 
 use crate::api::Namespace;
 use crate::helpers::{self, CallFuture};
-use crate::types::{Address, RawTransaction, TransactionRequest, H256};
+use crate::types::{Address, Bytes, RawTransaction, TransactionRequest, H256, H520};
 
 use crate::Transport;
+use rustc_hex::ToHex;
 
 /// `Personal` namespace
 #[derive(Debug, Clone)]
@@ -38,6 +39,18 @@ impl<T: Transport> Personal<T> {
         CallFuture::new(self.transport.execute("personal_newAccount", vec![password]))
     }
 
+    /// Imports an unencrypted private key into the node's keystore, encrypting it with `password`.
+    /// Returns the address of the imported account.
+    pub fn import_raw_key(&self, private_key: H256, password: &str) -> CallFuture<Address, T::Out> {
+        // Geth expects the key as plain hex, without the `0x` prefix.
+        let private_key = helpers::serialize(&private_key.as_bytes().to_hex::<String>());
+        let password = helpers::serialize(&password);
+        CallFuture::new(
+            self.transport
+                .execute("personal_importRawKey", vec![private_key, password]),
+        )
+    }
+
     /// Unlocks the account with given password for some period of time (or single transaction).
     /// Returns `true` if the call was successful.
     pub fn unlock_account(&self, address: Address, password: &str, duration: Option<u16>) -> CallFuture<bool, T::Out> {
@@ -50,6 +63,13 @@ impl<T: Transport> Personal<T> {
         )
     }
 
+    /// Locks the account, removing its unlocked key from memory before the unlock duration expires.
+    /// Returns `true` if the call was successful.
+    pub fn lock_account(&self, address: Address) -> CallFuture<bool, T::Out> {
+        let address = helpers::serialize(&address);
+        CallFuture::new(self.transport.execute("personal_lockAccount", vec![address]))
+    }
+
     /// Sends a transaction from locked account.
     /// Returns transaction hash.
     pub fn send_transaction(&self, transaction: TransactionRequest, password: &str) -> CallFuture<H256, T::Out> {
@@ -76,6 +96,23 @@ impl<T: Transport> Personal<T> {
                 .execute("personal_signTransaction", vec![transaction, password]),
         )
     }
+
+    /// Signs arbitrary data with the key of a locked account.
+    /// The data is prefixed with `"\x19Ethereum Signed Message:\n" + len(data)` by the node before hashing.
+    /// Returns the 65-byte signature.
+    pub fn sign(&self, data: Bytes, account: Address, password: &str) -> CallFuture<H520, T::Out> {
+        let data = helpers::serialize(&data);
+        let account = helpers::serialize(&account);
+        let password = helpers::serialize(&password);
+        CallFuture::new(self.transport.execute("personal_sign", vec![data, account, password]))
+    }
+
+    /// Recovers the address of the account which signed `data` with `personal_sign`.
+    pub fn ec_recover(&self, data: Bytes, signature: H520) -> CallFuture<Address, T::Out> {
+        let data = helpers::serialize(&data);
+        let signature = helpers::serialize(&signature);
+        CallFuture::new(self.transport.execute("personal_ecRecover", vec![data, signature]))
+    }
 }
 
 #[cfg(test)]
@@ -83,8 +120,10 @@ mod tests {
     use futures::Future;
 
     use crate::api::Namespace;
+    use crate::helpers::tests::TestTransport;
     use crate::rpc::Value;
-    use crate::types::{Address, RawTransaction, TransactionRequest};
+    use crate::signing::verify_signature;
+    use crate::types::{Address, Bytes, RawTransaction, TransactionRequest, H256, H520};
     use rustc_hex::FromHex;
 
     use super::Personal;
@@ -116,6 +155,13 @@ mod tests {
     Value::String("0x0000000000000000000000000000000000000123".into()) => Address::from_low_u64_be(0x123)
   );
 
+    rpc_test! (
+    Personal:import_raw_key, H256::from_low_u64_be(0xab), "hunter2"
+    =>
+    "personal_importRawKey", vec![r#""00000000000000000000000000000000000000000000000000000000000000ab""#, r#""hunter2""#];
+    Value::String("0x0000000000000000000000000000000000000123".into()) => Address::from_low_u64_be(0x123)
+  );
+
     rpc_test! (
     Personal:unlock_account, Address::from_low_u64_be(0x123), "hunter2", None
     =>
@@ -123,12 +169,20 @@ mod tests {
     Value::Bool(true) => true
   );
 
+    rpc_test! (
+    Personal:lock_account, Address::from_low_u64_be(0x123)
+    =>
+    "personal_lockAccount", vec![r#""0x0000000000000000000000000000000000000123""#];
+    Value::Bool(true) => true
+  );
+
     rpc_test! (
     Personal:send_transaction, TransactionRequest {
       from: Address::from_low_u64_be(0x123), to: Some(Address::from_low_u64_be(0x123)),
       gas: None, gas_price: Some(0x1.into()),
+      max_fee_per_gas: None, max_priority_fee_per_gas: None,
       value: Some(0x1.into()), data: None,
-      nonce: None, condition: None,
+      nonce: None, condition: None, access_list: None, transaction_type: None,
     }, "hunter2"
     =>
     "personal_sendTransaction", vec![r#"{"from":"0x0000000000000000000000000000000000000123","gasPrice":"0x1","to":"0x0000000000000000000000000000000000000123","value":"0x1"}"#, r#""hunter2""#];
@@ -141,10 +195,14 @@ mod tests {
       to: Some("853f43d8a49eeb85d32cf465507dd71d507100c1".parse().unwrap()),
       gas: Some(0x7f110.into()),
       gas_price: Some(0x09184e72a000u64.into()),
+      max_fee_per_gas: None,
+      max_priority_fee_per_gas: None,
       value: Some(0x7f110.into()),
       data: Some(FromHex::from_hex::<Vec<u8>>("603880600c6000396000f300603880600c6000396000f3603880600c6000396000f360").unwrap().into()),
       nonce: Some(0x0.into()),
       condition: None,
+      access_list: None,
+      transaction_type: None,
     }, "hunter2"
     =>
     "personal_signTransaction", vec![r#"{"data":"0x603880600c6000396000f300603880600c6000396000f3603880600c6000396000f360","from":"0x407d73d8a49eeb85d32cf465507dd71d507100c1","gas":"0x7f110","gasPrice":"0x9184e72a000","nonce":"0x0","to":"0x853f43d8a49eeb85d32cf465507dd71d507100c1","value":"0x7f110"}"#, r#""hunter2""#];
@@ -152,4 +210,46 @@ mod tests {
     => ::serde_json::from_str::<RawTransaction>(EXAMPLE_TX).unwrap()
   );
 
+    rpc_test! (
+    Personal:sign, Bytes(vec![0xde, 0xad, 0xbe, 0xef]), Address::from_low_u64_be(0x123), "hunter2"
+    =>
+    "personal_sign", vec![r#""0xdeadbeef""#, r#""0x0000000000000000000000000000000000000123""#, r#""hunter2""#];
+    Value::String(format!("0x{}1b", "00".repeat(64))) => H520::from_low_u64_be(0x1b)
+  );
+
+    rpc_test! (
+    Personal:ec_recover, Bytes(vec![0xde, 0xad, 0xbe, 0xef]), H520::from_low_u64_be(0x1b)
+    =>
+    "personal_ecRecover", vec![r#""0xdeadbeef""#, r#""0x000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001b""#];
+    Value::String("0x0000000000000000000000000000000000000123".into()) => Address::from_low_u64_be(0x123)
+  );
+
+    // signature taken from:
+    // https://web3js.readthedocs.io/en/v1.2.2/web3-eth-accounts.html#sign
+    const SIGNATURE: &str = "0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+
+    #[test]
+    fn should_recover_signer_of_signed_message() {
+        // given
+        let signer: Address = "2c7536e3605d9c16a7a3d7b1898e529396a65c23".parse().unwrap();
+        let mut transport = TestTransport::default();
+        transport.add_response(Value::String(SIGNATURE.into()));
+        transport.add_response(::serde_json::to_value(signer).unwrap());
+        let personal = Personal::new(&transport);
+        let message = Bytes(b"Some data".to_vec());
+
+        // when
+        let signature = personal.sign(message.clone(), signer, "hunter2").wait().unwrap();
+        let recovered = personal.ec_recover(message, signature).wait();
+
+        // then
+        assert_eq!(recovered, Ok(signer));
+        assert!(verify_signature(b"Some data", &signature, signer));
+        let message = r#""0x536f6d652064617461""#.to_owned();
+        let signer = r#""0x2c7536e3605d9c16a7a3d7b1898e529396a65c23""#.into();
+        transport.assert_request("personal_sign", &[message.clone(), signer, r#""hunter2""#.into()]);
+        transport.assert_request("personal_ecRecover", &[message, format!(r#""{}""#, SIGNATURE)]);
+        transport.assert_no_more_requests();
+    }
+
 }
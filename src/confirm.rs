@@ -1,13 +1,112 @@
 //! Easy to use utilities for confirmations.
 
+use std::io;
 use std::time::Duration;
 
 use crate::api::{CreateFilter, Eth, EthFilter, FilterStream, Namespace};
-use crate::helpers::CallFuture;
-use crate::types::{Bytes, TransactionReceipt, TransactionRequest, H256, U64};
+use crate::helpers::{self, CallFuture};
+use crate::types::{
+    Address, Block, BlockNumber, Bytes, Filter, Log, TransactionReceipt, TransactionRequest, H256, U256, U64,
+};
 use crate::{Error, Transport};
+use futures::future::Join;
 use futures::stream::Skip;
-use futures::{Future, IntoFuture, Poll, Stream};
+use futures::{Async, Future, IntoFuture, Poll, Stream};
+use tokio_timer::{Sleep, Timer};
+
+/// Strategy used to space out consecutive polls of the node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backoff {
+    /// Poll at a fixed interval.
+    Fixed(Duration),
+    /// Start with `initial` interval and double it after every poll, up to `max`.
+    Exponential {
+        /// Interval before the first poll.
+        initial: Duration,
+        /// Maximal interval between polls.
+        max: Duration,
+    },
+}
+
+impl Backoff {
+    /// Returns the interval before the first poll.
+    pub fn initial(&self) -> Duration {
+        match *self {
+            Backoff::Fixed(interval) => interval,
+            Backoff::Exponential { initial, max } => std::cmp::min(initial, max),
+        }
+    }
+
+    /// Returns the interval following `current`.
+    pub fn next(&self, current: Duration) -> Duration {
+        match *self {
+            Backoff::Fixed(interval) => interval,
+            Backoff::Exponential { max, .. } => current.checked_mul(2).map_or(max, |next| std::cmp::min(next, max)),
+        }
+    }
+}
+
+impl From<Duration> for Backoff {
+    fn from(interval: Duration) -> Self {
+        Backoff::Fixed(interval)
+    }
+}
+
+/// Finality conventions of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Finality {
+    /// Suggested number of confirmations before a transaction is considered final.
+    pub confirmations: usize,
+    /// Whether the chain supports the `finalized` block tag, which should be preferred when available.
+    ///
+    /// When set, transactions are considered final once included in the `finalized` block
+    /// and `confirmations` is not used.
+    pub use_finalized_tag: bool,
+}
+
+impl Default for Finality {
+    /// Conservative defaults for chains without known conventions.
+    fn default() -> Self {
+        Finality {
+            confirmations: 12,
+            use_finalized_tag: false,
+        }
+    }
+}
+
+/// Returns suggested finality settings for the chain with given `chain_id`.
+///
+/// Unknown chains get conservative defaults (see `Finality::default`).
+pub fn finality(chain_id: u64) -> Finality {
+    match chain_id {
+        // Ethereum mainnet and PoS testnets (Goerli, Sepolia, Holesky)
+        1 | 5 | 11_155_111 | 17_000 => Finality {
+            confirmations: 12,
+            use_finalized_tag: true,
+        },
+        // Rollups sequencing transactions instantly (Optimism, Arbitrum One, Base, zkSync Era)
+        10 | 42_161 | 8_453 | 324 => Finality {
+            confirmations: 1,
+            use_finalized_tag: true,
+        },
+        // Polygon PoS
+        137 => Finality {
+            confirmations: 128,
+            use_finalized_tag: false,
+        },
+        // BNB Smart Chain
+        56 => Finality {
+            confirmations: 15,
+            use_finalized_tag: false,
+        },
+        // Local development chains (Ganache, Hardhat, Anvil)
+        1337 | 31_337 => Finality {
+            confirmations: 0,
+            use_finalized_tag: false,
+        },
+        _ => Finality::default(),
+    }
+}
 
 /// Checks whether an event has been confirmed.
 pub trait ConfirmationCheck {
@@ -90,7 +189,7 @@ where
 struct CreateWaitForConfirmations<T: Transport, V> {
     eth: Option<Eth<T>>,
     create_filter: CreateFilter<T, H256>,
-    poll_interval: Duration,
+    backoff: Backoff,
     confirmation_check: Option<V>,
     confirmations: usize,
 }
@@ -106,12 +205,12 @@ pub struct Confirmations<T: Transport, V, F> {
 }
 
 impl<T: Transport, V, F> Confirmations<T, V, F> {
-    fn new(eth: Eth<T>, eth_filter: EthFilter<T>, poll_interval: Duration, confirmations: usize, check: V) -> Self {
+    fn new(eth: Eth<T>, eth_filter: EthFilter<T>, backoff: Backoff, confirmations: usize, check: V) -> Self {
         Confirmations {
             state: ConfirmationsState::Create(CreateWaitForConfirmations {
                 eth: Some(eth),
                 create_filter: eth_filter.create_blocks_filter(),
-                poll_interval,
+                backoff,
                 confirmation_check: Some(check),
                 confirmations,
             }),
@@ -136,7 +235,9 @@ where
                     let future = WaitForConfirmations {
                         eth: create.eth.take().expect("future polled after ready; qed"),
                         state: WaitForConfirmationsState::WaitForNextBlock,
-                        filter_stream: filter.stream(create.poll_interval).skip(create.confirmations as u64),
+                        filter_stream: filter
+                            .stream_with_backoff(create.backoff)
+                            .skip(create.confirmations as u64),
                         confirmation_check: create
                             .confirmation_check
                             .take()
@@ -165,7 +266,23 @@ where
     V: ConfirmationCheck<Check = F>,
     F: IntoFuture<Item = Option<U64>, Error = Error>,
 {
-    Confirmations::new(eth, eth_filter, poll_interval, confirmations, check)
+    Confirmations::new(eth, eth_filter, Backoff::Fixed(poll_interval), confirmations, check)
+}
+
+/// Should be used to wait for confirmations, spacing the polls according to `backoff`
+pub fn wait_for_confirmations_with_backoff<T, V, F>(
+    eth: Eth<T>,
+    eth_filter: EthFilter<T>,
+    backoff: Backoff,
+    confirmations: usize,
+    check: V,
+) -> Confirmations<T, V, F::Future>
+where
+    T: Transport,
+    V: ConfirmationCheck<Check = F>,
+    F: IntoFuture<Item = Option<U64>, Error = Error>,
+{
+    Confirmations::new(eth, eth_filter, backoff, confirmations, check)
 }
 
 struct TransactionReceiptBlockNumber<T: Transport> {
@@ -205,7 +322,16 @@ impl<T: Transport> ConfirmationCheck for TransactionReceiptBlockNumberCheck<T> {
 
 enum SendTransactionWithConfirmationState<T: Transport> {
     Error(Option<Error>),
+    EstimateGas(
+        Option<TransactionRequest>,
+        GasScaling,
+        Box<Join<CallFuture<U256, T::Out>, BlockFuture<T>>>,
+    ),
     SendTransaction(CallFuture<H256, T::Out>),
+    CheckReceipt(
+        H256,
+        Box<Join<CallFuture<Option<TransactionReceipt>, T::Out>, CallFuture<U64, T::Out>>>,
+    ),
     WaitForConfirmations(
         H256,
         Confirmations<T, TransactionReceiptBlockNumberCheck<T>, TransactionReceiptBlockNumber<T>>,
@@ -217,7 +343,7 @@ enum SendTransactionWithConfirmationState<T: Transport> {
 pub struct SendTransactionWithConfirmation<T: Transport> {
     state: SendTransactionWithConfirmationState<T>,
     transport: T,
-    poll_interval: Duration,
+    backoff: Backoff,
     confirmations: usize,
 }
 
@@ -226,7 +352,7 @@ impl<T: Transport> SendTransactionWithConfirmation<T> {
         SendTransactionWithConfirmation {
             state: SendTransactionWithConfirmationState::SendTransaction(Eth::new(&transport).send_transaction(tx)),
             transport,
-            poll_interval,
+            backoff: Backoff::Fixed(poll_interval),
             confirmations,
         }
     }
@@ -235,21 +361,17 @@ impl<T: Transport> SendTransactionWithConfirmation<T> {
         SendTransactionWithConfirmation {
             state: SendTransactionWithConfirmationState::SendTransaction(Eth::new(&transport).send_raw_transaction(tx)),
             transport,
-            poll_interval,
+            backoff: Backoff::Fixed(poll_interval),
             confirmations,
         }
     }
 
-    fn hash(transport: T, hash: H256, poll_interval: Duration, confirmations: usize) -> Self {
+    fn hash(transport: T, hash: H256, backoff: Backoff, confirmations: usize) -> Self {
         let state = if confirmations > 0 {
             let confirmation_check = TransactionReceiptBlockNumberCheck::new(Eth::new(transport.clone()), hash.clone());
             let eth = Eth::new(transport.clone());
             let eth_filter = EthFilter::new(transport.clone());
-            let wait = wait_for_confirmations(eth,
-                                              eth_filter,
-                                              poll_interval,
-                                              confirmations,
-                                              confirmation_check);
+            let wait = wait_for_confirmations_with_backoff(eth, eth_filter, backoff, confirmations, confirmation_check);
             SendTransactionWithConfirmationState::WaitForConfirmations(hash, wait)
         } else {
             let receipt_future = Eth::new(&transport).transaction_receipt(hash);
@@ -259,7 +381,35 @@ impl<T: Transport> SendTransactionWithConfirmation<T> {
         SendTransactionWithConfirmation {
             state,
             transport,
-            poll_interval,
+            backoff,
+            confirmations,
+        }
+    }
+
+    fn from_receipt(transport: T, hash: H256, backoff: Backoff, confirmations: usize) -> Self {
+        let eth = Eth::new(&transport);
+        let check = eth.transaction_receipt(hash).join(eth.block_number());
+        SendTransactionWithConfirmation {
+            state: SendTransactionWithConfirmationState::CheckReceipt(hash, Box::new(check)),
+            transport,
+            backoff,
+            confirmations,
+        }
+    }
+
+    pub(crate) fn estimated(
+        transport: T,
+        tx: TransactionRequest,
+        scaling: GasScaling,
+        poll_interval: Duration,
+        confirmations: usize,
+    ) -> Self {
+        let estimate = CallFuture::new(transport.execute("eth_estimateGas", vec![helpers::serialize(&tx)]));
+        let block = Eth::new(&transport).block(BlockNumber::Latest.into());
+        SendTransactionWithConfirmation {
+            state: SendTransactionWithConfirmationState::EstimateGas(Some(tx), scaling, Box::new(estimate.join(block))),
+            transport,
+            backoff: Backoff::Fixed(poll_interval),
             confirmations,
         }
     }
@@ -268,7 +418,7 @@ impl<T: Transport> SendTransactionWithConfirmation<T> {
         SendTransactionWithConfirmation {
             state: SendTransactionWithConfirmationState::Error(Some(err.into())),
             transport,
-            poll_interval: Duration::from_secs(1),
+            backoff: Backoff::Fixed(Duration::from_secs(1)),
             confirmations: 1,
         }
     }
@@ -286,15 +436,46 @@ impl<T: Transport> Future for SendTransactionWithConfirmation<T> {
                         .take()
                         .expect("Error is initialized initially; future polled only once; qed"));
                 }
+                SendTransactionWithConfirmationState::EstimateGas(ref mut tx, scaling, ref mut future) => {
+                    let (estimate, block) = try_ready!(future.poll());
+                    let gas = scaling.apply(estimate);
+                    if let Some(block) = block {
+                        if gas > block.gas_limit {
+                            return Err(Error::GasLimitExceeded {
+                                gas,
+                                block_gas_limit: block.gas_limit,
+                            });
+                        }
+                    }
+                    let mut tx = tx
+                        .take()
+                        .expect("Transaction is initialized initially; future polled only once; qed");
+                    tx.gas = Some(gas);
+                    SendTransactionWithConfirmationState::SendTransaction(
+                        Eth::new(&self.transport).send_transaction(tx),
+                    )
+                }
                 SendTransactionWithConfirmationState::SendTransaction(ref mut future) => {
                     Self::hash(
                         self.transport.clone(),
                         try_ready!(future.poll()),
-                        self.poll_interval,
+                        self.backoff,
                         self.confirmations,
                     ).state
 
                 }
+                SendTransactionWithConfirmationState::CheckReceipt(hash, ref mut future) => {
+                    let (receipt, block_number) = try_ready!(future.poll());
+                    let mined = receipt.as_ref().and_then(|receipt| receipt.block_number);
+                    match (receipt, mined) {
+                        (Some(receipt), Some(mined))
+                            if mined.low_u64() + self.confirmations as u64 <= block_number.low_u64() =>
+                        {
+                            return Ok(receipt.into());
+                        }
+                        _ => Self::hash(self.transport.clone(), hash, self.backoff, self.confirmations).state,
+                    }
+                }
                 SendTransactionWithConfirmationState::WaitForConfirmations(hash, ref mut future) => {
                     let _confirmed = try_ready!(Future::poll(future));
                     let receipt_future = Eth::new(&self.transport).transaction_receipt(hash);
@@ -311,6 +492,27 @@ impl<T: Transport> Future for SendTransactionWithConfirmation<T> {
     }
 }
 
+type BlockFuture<T> = CallFuture<Option<Block<H256>>, <T as Transport>::Out>;
+
+/// Adjustment applied to a gas estimate before the transaction is sent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GasScaling {
+    pub(crate) multiplier: f64,
+    pub(crate) cap: Option<U256>,
+}
+
+impl GasScaling {
+    fn apply(self, estimate: U256) -> U256 {
+        // Scale in thousandths to stay in integer arithmetic.
+        let permille = U256::from((self.multiplier * 1000.0).round() as u64);
+        let gas = estimate.saturating_mul(permille) / 1000;
+        match self.cap {
+            Some(cap) if gas > cap => cap,
+            _ => gas,
+        }
+    }
+}
+
 /// Sends transaction and returns future resolved after transaction is confirmed
 pub fn send_transaction_with_confirmation<T>(
     transport: T,
@@ -347,17 +549,315 @@ pub fn wait_for_transaction_confirmation<T>(
 where
     T: Transport,
 {
-    SendTransactionWithConfirmation::hash(transport, hash, poll_interval, confirmations)
+    SendTransactionWithConfirmation::hash(transport, hash, Backoff::Fixed(poll_interval), confirmations)
+}
+
+/// Given a transaction hash, returns future resolved after transaction is confirmed.
+/// Polls the node with delays given by `backoff`.
+pub fn wait_for_transaction_confirmation_with_backoff<T>(
+    transport: T,
+    hash: H256,
+    backoff: Backoff,
+    confirmations: usize,
+) -> SendTransactionWithConfirmation<T>
+where
+    T: Transport,
+{
+    SendTransactionWithConfirmation::hash(transport, hash, backoff, confirmations)
+}
+
+/// Given a transaction hash, returns future resolved after transaction is confirmed.
+///
+/// Unlike `wait_for_transaction_confirmation`, the receipt is fetched right away, so a transaction
+/// which is already mined and confirmed resolves without waiting for new blocks.
+/// Otherwise falls back to polling the node with delays given by `backoff`.
+pub fn wait_for_transaction_confirmation_from_receipt<T>(
+    transport: T,
+    hash: H256,
+    backoff: Backoff,
+    confirmations: usize,
+) -> SendTransactionWithConfirmation<T>
+where
+    T: Transport,
+{
+    SendTransactionWithConfirmation::from_receipt(transport, hash, backoff, confirmations)
+}
+
+type FinalizedCheck<O> = Join<CallFuture<Option<Block<H256>>, O>, CallFuture<Option<TransactionReceipt>, O>>;
+
+enum WaitForFinalityState<T: Transport> {
+    SendTransaction(CallFuture<H256, T::Out>),
+    WaitForConfirmations(Box<SendTransactionWithConfirmation<T>>),
+    CheckFinalized(H256, Box<FinalizedCheck<T::Out>>),
+    WaitForNextPoll(H256, Sleep),
+}
+
+/// Future resolved with the receipt of a transaction once it's final according to `finality`.
+pub struct WaitForFinality<T: Transport> {
+    eth: Eth<T>,
+    finality: Finality,
+    poll_interval: Duration,
+    timer: Timer,
+    state: WaitForFinalityState<T>,
+}
+
+impl<T: Transport> WaitForFinality<T> {
+    fn new(eth: Eth<T>, finality: Finality, poll_interval: Duration, state: WaitForFinalityState<T>) -> Self {
+        WaitForFinality {
+            eth,
+            finality,
+            poll_interval,
+            timer: helpers::timer(),
+            state,
+        }
+    }
+
+    fn wait(eth: &Eth<T>, finality: Finality, poll_interval: Duration, hash: H256) -> WaitForFinalityState<T> {
+        if finality.use_finalized_tag {
+            Self::check_finalized(eth, hash)
+        } else {
+            let transport = eth.transport().clone();
+            let backoff = Backoff::Fixed(poll_interval);
+            let future = SendTransactionWithConfirmation::hash(transport, hash, backoff, finality.confirmations);
+            WaitForFinalityState::WaitForConfirmations(Box::new(future))
+        }
+    }
+
+    fn check_finalized(eth: &Eth<T>, hash: H256) -> WaitForFinalityState<T> {
+        let finalized = eth.block(BlockNumber::Finalized.into());
+        let receipt = eth.transaction_receipt(hash);
+        WaitForFinalityState::CheckFinalized(hash, Box::new(finalized.join(receipt)))
+    }
+}
+
+impl<T: Transport> Future for WaitForFinality<T> {
+    type Item = TransactionReceipt;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let next_state = match self.state {
+                WaitForFinalityState::SendTransaction(ref mut future) => {
+                    let hash = try_ready!(future.poll());
+                    Self::wait(&self.eth, self.finality, self.poll_interval, hash)
+                }
+                WaitForFinalityState::WaitForConfirmations(ref mut future) => return future.poll(),
+                WaitForFinalityState::CheckFinalized(hash, ref mut future) => {
+                    let (block, receipt) = try_ready!(future.poll());
+                    let finalized = block.and_then(|block| block.number);
+                    match receipt {
+                        Some(receipt) if receipt.block_number.is_some() && receipt.block_number <= finalized => {
+                            return Ok(Async::Ready(receipt));
+                        }
+                        _ => WaitForFinalityState::WaitForNextPoll(hash, self.timer.sleep(self.poll_interval)),
+                    }
+                }
+                WaitForFinalityState::WaitForNextPoll(hash, ref mut sleep) => {
+                    let _ready = try_ready!(sleep.poll().map_err(|_| Error::Unreachable));
+                    Self::check_finalized(&self.eth, hash)
+                }
+            };
+            self.state = next_state;
+        }
+    }
+}
+
+/// Given a transaction hash, returns future resolved after transaction is final according
+/// to `finality(chain_id)`: included in the `finalized` block on chains supporting the tag,
+/// confirmed by the suggested number of blocks otherwise.
+pub fn wait_for_transaction_finality<T>(
+    transport: T,
+    hash: H256,
+    poll_interval: Duration,
+    chain_id: u64,
+) -> WaitForFinality<T>
+where
+    T: Transport,
+{
+    let eth = Eth::new(transport);
+    let finality = finality(chain_id);
+    let state = WaitForFinality::wait(&eth, finality, poll_interval, hash);
+    WaitForFinality::new(eth, finality, poll_interval, state)
+}
+
+/// Sends raw transaction and returns future resolved after transaction is final according
+/// to `finality(chain_id)`, see `wait_for_transaction_finality`.
+pub fn send_raw_transaction_with_finality<T>(
+    transport: T,
+    tx: Bytes,
+    poll_interval: Duration,
+    chain_id: u64,
+) -> WaitForFinality<T>
+where
+    T: Transport,
+{
+    let eth = Eth::new(transport);
+    let state = WaitForFinalityState::SendTransaction(eth.send_raw_transaction(tx));
+    WaitForFinality::new(eth, finality(chain_id), poll_interval, state)
+}
+
+enum WaitForNonceState<O> {
+    CheckNonce(CallFuture<U256, O>),
+    WaitForNextPoll(Sleep),
+}
+
+/// Future resolved when the transaction count of an account reaches the target nonce.
+pub struct WaitForNonce<T: Transport> {
+    eth: Eth<T>,
+    address: Address,
+    target_nonce: U256,
+    poll_interval: Duration,
+    timer: Timer,
+    state: WaitForNonceState<T::Out>,
+}
+
+impl<T: Transport> Future for WaitForNonce<T> {
+    type Item = U256;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let next_state = match self.state {
+                WaitForNonceState::CheckNonce(ref mut future) => {
+                    let nonce = try_ready!(future.poll());
+                    if nonce >= self.target_nonce {
+                        return Ok(Async::Ready(nonce));
+                    }
+                    WaitForNonceState::WaitForNextPoll(self.timer.sleep(self.poll_interval))
+                }
+                WaitForNonceState::WaitForNextPoll(ref mut sleep) => {
+                    let _ready = try_ready!(sleep.poll().map_err(|_| Error::Unreachable));
+                    let future = self.eth.transaction_count(self.address, Some(BlockNumber::Latest));
+                    WaitForNonceState::CheckNonce(future)
+                }
+            };
+            self.state = next_state;
+        }
+    }
+}
+
+/// Returns future resolved once the nonce of `address` (at the latest block) reaches or exceeds
+/// `target_nonce`, i.e. all transactions sent from it before have been mined.
+pub fn wait_for_nonce<T>(transport: T, address: Address, target_nonce: U256, poll_interval: Duration) -> WaitForNonce<T>
+where
+    T: Transport,
+{
+    let eth = Eth::new(transport);
+    let future = eth.transaction_count(address, Some(BlockNumber::Latest));
+    WaitForNonce {
+        eth,
+        address,
+        target_nonce,
+        poll_interval,
+        timer: helpers::timer(),
+        state: WaitForNonceState::CheckNonce(future),
+    }
+}
+
+enum WaitForEventState<T: Transport> {
+    CreateFilter(CreateFilter<T, Log>),
+    WaitForEvent(FilterStream<T, Log>),
+    Uninstall(CallFuture<bool, T::Out>, Option<Result<Log, Error>>),
+}
+
+/// Future resolved with the first log matching a predicate.
+pub struct WaitForEvent<T: Transport, P> {
+    predicate: P,
+    poll_interval: Duration,
+    timeout: Sleep,
+    state: WaitForEventState<T>,
+}
+
+/// Polls the timeout, failing with a `TimedOut` I/O error once it elapses.
+fn poll_timeout(timeout: &mut Sleep) -> Poll<(), Error> {
+    match timeout.poll() {
+        Ok(Async::Ready(())) => Err(Error::Io(io::ErrorKind::TimedOut.into())),
+        Ok(Async::NotReady) => Ok(Async::NotReady),
+        Err(err) => Err(Error::Transport(format!("{:?}", err))),
+    }
+}
+
+impl<T, P> Future for WaitForEvent<T, P>
+where
+    T: Transport,
+    P: FnMut(&Log) -> bool,
+{
+    type Item = Log;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let next_state = match self.state {
+                WaitForEventState::CreateFilter(ref mut future) => match future.poll()? {
+                    Async::Ready(filter) => WaitForEventState::WaitForEvent(filter.stream(self.poll_interval)),
+                    Async::NotReady => return poll_timeout(&mut self.timeout).map(|_| Async::NotReady),
+                },
+                WaitForEventState::WaitForEvent(ref mut stream) => {
+                    let result = match stream.poll() {
+                        Ok(Async::Ready(Some(log))) if (self.predicate)(&log) => Ok(log),
+                        Ok(Async::Ready(Some(_))) => continue,
+                        Ok(Async::Ready(None)) => Err(Error::Internal),
+                        Ok(Async::NotReady) => match poll_timeout(&mut self.timeout) {
+                            Ok(_) => return Ok(Async::NotReady),
+                            Err(err) => Err(err),
+                        },
+                        Err(err) => Err(err),
+                    };
+                    WaitForEventState::Uninstall(stream.uninstall(), Some(result))
+                }
+                WaitForEventState::Uninstall(ref mut future, ref mut result) => {
+                    // the filter is gone either way, failing to uninstall it doesn't change the outcome
+                    if let Ok(Async::NotReady) = future.poll() {
+                        return Ok(Async::NotReady);
+                    }
+                    let result = result.take().expect("future polled after ready; qed");
+                    return result.map(Async::Ready);
+                }
+            };
+            self.state = next_state;
+        }
+    }
+}
+
+/// Returns future resolved with the first new log matching `filter` for which `predicate` returns `true`.
+///
+/// Logs are polled through a filter installed on the node, so any transport is supported.
+/// The filter is uninstalled once the future resolves. The future fails with a `TimedOut`
+/// I/O error if no matching log appears within `timeout`.
+pub fn wait_for_event<T, P>(
+    transport: T,
+    filter: Filter,
+    predicate: P,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> WaitForEvent<T, P>
+where
+    T: Transport,
+    P: FnMut(&Log) -> bool,
+{
+    let future = EthFilter::new(transport).create_logs_filter(filter);
+    WaitForEvent {
+        predicate,
+        poll_interval,
+        timeout: helpers::timer().sleep(timeout),
+        state: WaitForEventState::CreateFilter(future),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::send_transaction_with_confirmation;
+    use super::{
+        finality, send_raw_transaction_with_finality, send_transaction_with_confirmation, wait_for_event,
+        wait_for_nonce, wait_for_transaction_confirmation_from_receipt, wait_for_transaction_finality, Backoff,
+        Finality,
+    };
     use crate::helpers::tests::TestTransport;
     use crate::rpc::Value;
-    use crate::types::{Address, TransactionReceipt, TransactionRequest, H256, U64};
+    use crate::types::{Address, Block, Bytes, FilterBuilder, Log, TransactionReceipt, TransactionRequest, H256, U64};
+    use crate::Error;
     use futures::Future;
     use serde_json::json;
+    use std::io;
     use std::time::Duration;
 
     #[test]
@@ -369,10 +869,14 @@ mod tests {
             to: Some(Address::from_low_u64_be(0x123)),
             gas: None,
             gas_price: Some(1.into()),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
             value: Some(1.into()),
             data: None,
             nonce: None,
             condition: None,
+            access_list: None,
+            transaction_type: None,
         };
 
         let transaction_receipt = TransactionReceipt {
@@ -451,4 +955,273 @@ mod tests {
         transport.assert_no_more_requests();
         assert_eq!(confirmation, Ok(transaction_receipt));
     }
+
+    #[test]
+    fn should_confirm_mined_transaction_from_receipt() {
+        let mut transport = TestTransport::default();
+        let hash = H256::from_low_u64_be(0x111);
+        let receipt = TransactionReceipt {
+            transaction_hash: hash,
+            block_number: Some(2.into()),
+            status: Some(1.into()),
+            ..Default::default()
+        };
+        transport.add_response(json!(receipt));
+        transport.add_response(Value::String("0x5".into()));
+
+        let backoff = Backoff::Fixed(Duration::from_secs(0));
+        let confirmation = wait_for_transaction_confirmation_from_receipt(&transport, hash, backoff, 3).wait();
+
+        transport.assert_request(
+            "eth_getTransactionReceipt",
+            &[r#""0x0000000000000000000000000000000000000000000000000000000000000111""#.into()],
+        );
+        transport.assert_request("eth_blockNumber", &[]);
+        transport.assert_no_more_requests();
+        assert_eq!(confirmation, Ok(receipt));
+    }
+
+    #[test]
+    fn exponential_backoff_should_grow_until_cap() {
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            max: Duration::from_millis(1000),
+        };
+
+        let mut delays = vec![backoff.initial()];
+        for _ in 0..5 {
+            let next = backoff.next(*delays.last().unwrap());
+            delays.push(next);
+        }
+
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1000, 1000]
+                .iter()
+                .map(|ms| Duration::from_millis(*ms))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Backoff::from(Duration::from_secs(1)).next(Duration::from_secs(1)),
+            Duration::from_secs(1)
+        );
+        // no cap
+        let unbounded = Backoff::Exponential {
+            initial: Duration::from_secs(1),
+            max: Duration::MAX,
+        };
+        assert_eq!(
+            unbounded.next(Duration::MAX / 2 + Duration::from_secs(1)),
+            Duration::MAX
+        );
+    }
+
+    #[test]
+    fn should_wait_for_nonce() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(Value::String("0x1".into()));
+        transport.add_response(Value::String("0x2".into()));
+        transport.add_response(Value::String("0x3".into()));
+
+        // when
+        let result = wait_for_nonce(
+            &transport,
+            Address::from_low_u64_be(0x123),
+            3.into(),
+            Duration::from_millis(1),
+        )
+        .wait();
+
+        // then
+        for _ in 0..3 {
+            transport.assert_request(
+                "eth_getTransactionCount",
+                &[
+                    r#""0x0000000000000000000000000000000000000123""#.into(),
+                    r#""latest""#.into(),
+                ],
+            );
+        }
+        transport.assert_no_more_requests();
+        assert_eq!(result, Ok(3.into()));
+    }
+
+    #[test]
+    fn should_wait_for_matching_event() {
+        // given
+        let log = |data: u8| Log {
+            address: Address::from_low_u64_be(0x123),
+            topics: vec![H256::from_low_u64_be(1)],
+            data: Bytes(vec![data]),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        let mut transport = TestTransport::default();
+        transport.add_response(Value::String("0x123".into()));
+        transport.add_response(json!([log(1), log(2)]));
+        transport.add_response(json!([log(3)]));
+        transport.add_response(Value::Bool(true));
+        let filter = FilterBuilder::default()
+            .address(vec![Address::from_low_u64_be(0x123)])
+            .build();
+
+        // when
+        let result = wait_for_event(
+            &transport,
+            filter,
+            |log| log.data.0 == [3],
+            Duration::from_millis(1),
+            Duration::from_secs(10),
+        )
+        .wait();
+
+        // then
+        transport.assert_request(
+            "eth_newFilter",
+            &[r#"{"address":"0x0000000000000000000000000000000000000123"}"#.into()],
+        );
+        transport.assert_request("eth_getFilterChanges", &[r#""0x123""#.into()]);
+        transport.assert_request("eth_getFilterChanges", &[r#""0x123""#.into()]);
+        transport.assert_request("eth_uninstallFilter", &[r#""0x123""#.into()]);
+        transport.assert_no_more_requests();
+        assert_eq!(result, Ok(log(3)));
+    }
+
+    #[test]
+    fn should_uninstall_event_filter_on_error_with_long_timeout() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(Value::String("0x123".into()));
+        let filter = FilterBuilder::default().build();
+
+        // when
+        // the timeout exceeds the 409.6 seconds supported by a default `Timer`
+        let day = Duration::from_secs(24 * 60 * 60);
+        let result = wait_for_event(&transport, filter, |_| true, Duration::from_millis(1), day).wait();
+
+        // then
+        transport.assert_request("eth_newFilter", &["{}".into()]);
+        transport.assert_request("eth_getFilterChanges", &[r#""0x123""#.into()]);
+        transport.assert_request("eth_uninstallFilter", &[r#""0x123""#.into()]);
+        transport.assert_no_more_requests();
+        assert_eq!(result, Err(Error::Unreachable));
+    }
+
+    #[test]
+    fn should_uninstall_event_filter_on_timeout() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(Value::String("0x123".into()));
+        transport.add_response(Value::Bool(true));
+        let filter = FilterBuilder::default().build();
+
+        // when
+        let poll_interval = Duration::from_secs(60);
+        let result = wait_for_event(&transport, filter, |_| true, poll_interval, Duration::from_millis(1)).wait();
+
+        // then
+        transport.assert_request("eth_newFilter", &["{}".into()]);
+        transport.assert_request("eth_uninstallFilter", &[r#""0x123""#.into()]);
+        transport.assert_no_more_requests();
+        assert_eq!(result, Err(Error::Io(io::ErrorKind::TimedOut.into())));
+    }
+
+    fn receipt_in_block(number: u64) -> TransactionReceipt {
+        TransactionReceipt {
+            transaction_hash: H256::from_low_u64_be(0x111),
+            transaction_index: U64::zero(),
+            block_hash: Some(H256::zero()),
+            block_number: Some(number.into()),
+            cumulative_gas_used: 0.into(),
+            gas_used: Some(0.into()),
+            contract_address: None,
+            logs: vec![],
+            status: Some(1.into()),
+            logs_bloom: Default::default(),
+        }
+    }
+
+    #[test]
+    fn should_wait_for_finalized_block_on_chains_with_finalized_tag() {
+        // given
+        let mut transport = TestTransport::default();
+        let finalized = |number: u64| {
+            json!(Block::<H256> {
+                number: Some(number.into()),
+                ..Default::default()
+            })
+        };
+        transport.add_response(finalized(1));
+        transport.add_response(json!(receipt_in_block(2)));
+        transport.add_response(finalized(2));
+        transport.add_response(json!(receipt_in_block(2)));
+        let hash = H256::from_low_u64_be(0x111);
+
+        // when
+        let receipt = wait_for_transaction_finality(&transport, hash, Duration::from_millis(1), 1).wait();
+
+        // then
+        let hash = r#""0x0000000000000000000000000000000000000000000000000000000000000111""#;
+        for _ in 0..2 {
+            transport.assert_request("eth_getBlockByNumber", &[r#""finalized""#.into(), "false".into()]);
+            transport.assert_request("eth_getTransactionReceipt", &[hash.into()]);
+        }
+        transport.assert_no_more_requests();
+        assert_eq!(receipt, Ok(receipt_in_block(2)));
+    }
+
+    #[test]
+    fn should_send_and_wait_for_suggested_confirmations() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(json!(H256::from_low_u64_be(0x111)));
+        transport.add_response(json!(receipt_in_block(2)));
+
+        // when
+        let tx = Bytes(vec![1, 2, 3, 4]);
+        let receipt = send_raw_transaction_with_finality(&transport, tx, Duration::from_millis(1), 31_337).wait();
+
+        // then
+        transport.assert_request("eth_sendRawTransaction", &[r#""0x01020304""#.into()]);
+        transport.assert_request(
+            "eth_getTransactionReceipt",
+            &[r#""0x0000000000000000000000000000000000000000000000000000000000000111""#.into()],
+        );
+        transport.assert_no_more_requests();
+        assert_eq!(receipt, Ok(receipt_in_block(2)));
+    }
+
+    #[test]
+    fn should_suggest_finality_for_chain() {
+        assert_eq!(
+            finality(1),
+            Finality {
+                confirmations: 12,
+                use_finalized_tag: true,
+            }
+        );
+        assert_eq!(
+            finality(42_161),
+            Finality {
+                confirmations: 1,
+                use_finalized_tag: true,
+            }
+        );
+        assert_eq!(
+            finality(137),
+            Finality {
+                confirmations: 128,
+                use_finalized_tag: false,
+            }
+        );
+        assert_eq!(finality(31_337).confirmations, 0);
+        assert_eq!(finality(987_654_321), Finality::default());
+    }
 }
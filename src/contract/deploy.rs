@@ -6,10 +6,10 @@ use rustc_hex::{FromHex, ToHex};
 use std::{collections::HashMap, time};
 
 use crate::api::{Eth, Namespace};
-use crate::confirm;
+use crate::confirm::{self, GasScaling, SendTransactionWithConfirmation};
 use crate::contract::tokens::Tokenize;
 use crate::contract::{Contract, Options};
-use crate::types::{Address, Bytes, TransactionReceipt, TransactionRequest};
+use crate::types::{Address, Bytes, TransactionReceipt, TransactionRequest, U256};
 use crate::Transport;
 
 pub use crate::contract::error::deploy::Error;
@@ -23,6 +23,8 @@ pub struct Builder<T: Transport> {
     pub(crate) confirmations: usize,
     pub(crate) poll_interval: time::Duration,
     pub(crate) linker: HashMap<String, Address>,
+    pub(crate) gas_multiplier: Option<f64>,
+    pub(crate) gas_cap: Option<U256>,
 }
 
 impl<T: Transport> Builder<T> {
@@ -44,7 +46,35 @@ impl<T: Transport> Builder<T> {
         self
     }
 
+    /// Multiplier applied to the estimated deployment gas.
+    ///
+    /// Only used when gas is not set explicitly in `options`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the multiplier is not finite or less than `1.0`.
+    pub fn gas_multiplier(mut self, multiplier: f64) -> Self {
+        assert!(
+            multiplier.is_finite() && multiplier >= 1.0,
+            "Gas multiplier must be finite and at least 1.0, got: {}",
+            multiplier
+        );
+        self.gas_multiplier = Some(multiplier);
+        self
+    }
+
+    /// Upper bound for the (scaled) estimated deployment gas.
+    ///
+    /// Only used when gas is not set explicitly in `options`.
+    pub fn gas_cap(mut self, cap: U256) -> Self {
+        self.gas_cap = Some(cap);
+        self
+    }
+
     /// Execute deployment passing code and contructor parameters.
+    ///
+    /// If a gas multiplier or cap is configured, the gas is estimated first
+    /// and the deployment fails if the adjusted value exceeds the latest block gas limit.
     pub fn execute<P, V>(self, code: V, params: P, from: Address) -> Result<PendingContract<T>, ethabi::Error>
     where
         P: Tokenize,
@@ -53,9 +83,19 @@ impl<T: Transport> Builder<T> {
         let transport = self.eth.transport().clone();
         let poll_interval = self.poll_interval;
         let confirmations = self.confirmations;
+        let scaling = match (self.gas_multiplier, self.gas_cap) {
+            (None, None) => None,
+            (multiplier, cap) => Some(GasScaling {
+                multiplier: multiplier.unwrap_or(1.0),
+                cap,
+            }),
+        };
 
-        self.do_execute(code, params, from, move |tx| {
-            confirm::send_transaction_with_confirmation(transport, tx, poll_interval, confirmations)
+        self.do_execute(code, params, from, move |tx| match scaling {
+            Some(scaling) if tx.gas.is_none() => {
+                SendTransactionWithConfirmation::estimated(transport, tx, scaling, poll_interval, confirmations)
+            }
+            _ => confirm::send_transaction_with_confirmation(transport, tx, poll_interval, confirmations),
         })
     }
     /// Execute deployment passing code and contructor parameters.
@@ -138,10 +178,14 @@ impl<T: Transport> Builder<T> {
             to: None,
             gas: options.gas,
             gas_price: options.gas_price,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
             value: options.value,
             nonce: options.nonce,
             data: Some(Bytes(data)),
             condition: options.condition,
+            access_list: None,
+            transaction_type: None,
         };
 
         let waiting = send(tx);
@@ -188,10 +232,11 @@ impl<T: Transport, F: Future<Item = TransactionReceipt, Error = crate::error::Er
 #[cfg(test)]
 mod tests {
     use crate::api::{self, Namespace};
+    use crate::contract::deploy::Error;
     use crate::contract::{Contract, Options};
     use crate::helpers::tests::TestTransport;
     use crate::rpc;
-    use crate::types::{Address, U256};
+    use crate::types::{Address, Block, TransactionReceipt, H256, U256};
     use futures::Future;
     use serde_json::Value;
     use std::collections::HashMap;
@@ -259,6 +304,78 @@ mod tests {
         transport.assert_no_more_requests();
     }
 
+    #[test]
+    fn should_sign_and_deploy_a_payable_contract() {
+        // given
+        let mut transport = TestTransport::default();
+        // personal_signTransaction
+        transport.add_response(
+            ::serde_json::from_str::<rpc::Value>(
+                r#"{
+              "raw": "0xd46e8dd67c5d32be8d46e8dd67c5d32be8058bb8eb970870f072445675058bb8eb970870f072445675",
+              "tx": {
+                "hash": "0x70ae45a5067fdf3356aa615ca08d925a38c7ff21b486a61e79d5af3969ebc1a1",
+                "nonce": "0x0",
+                "blockHash": null,
+                "blockNumber": null,
+                "transactionIndex": null,
+                "from": "0x0000000000000000000000000000000000000005",
+                "to": null,
+                "value": "0xde0b6b3a7640000",
+                "gas": "0x7f110",
+                "gasPrice": "0x9184e72a000",
+                "input": "0x01020304"
+              }
+            }"#,
+            )
+            .unwrap(),
+        );
+        // Transaction Hash
+        transport.add_response(rpc::Value::String(
+            "0x70ae45a5067fdf3356aa615ca08d925a38c7ff21b486a61e79d5af3969ebc1a1".into(),
+        ));
+        // receipt
+        let receipt = ::serde_json::from_str::<rpc::Value>(
+        "{\"blockHash\":\"0xd5311584a9867d8e129113e1ec9db342771b94bd4533aeab820a5bcc2c54878f\",\"blockNumber\":\"0x256\",\"contractAddress\":\"0x600515dfe465f600f0c9793fa27cd2794f3ec0e1\",\"cumulativeGasUsed\":\"0xe57e0\",\"gasUsed\":\"0xe57e0\",\"logs\":[],\"logsBloom\":\"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000\",\"root\":null,\"transactionHash\":\"0x70ae45a5067fdf3356aa615ca08d925a38c7ff21b486a61e79d5af3969ebc1a1\",\"transactionIndex\":\"0x0\", \"status\": \"0x1\"}"
+      ).unwrap();
+        transport.add_response(receipt);
+
+        let address = {
+            let builder = Contract::deploy(api::Eth::new(&transport), include_bytes!("./res/token.json")).unwrap();
+
+            // when
+            builder
+                .options(Options::with(|opt| opt.value = Some(U256::exp10(18))))
+                .confirmations(0)
+                .sign_and_execute(
+                    "0x01020304",
+                    (U256::from(1_000_000), "My Token".to_owned(), 3u64, "MT".to_owned()),
+                    Address::from_low_u64_be(5),
+                    "hunter2",
+                )
+                .unwrap()
+                .wait()
+                .unwrap()
+                .address()
+        };
+
+        // then
+        transport.assert_request("personal_signTransaction", &[
+      "{\"data\":\"0x0102030400000000000000000000000000000000000000000000000000000000000f42400000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000000000000000000000000000000000000c000000000000000000000000000000000000000000000000000000000000000084d7920546f6b656e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000024d54000000000000000000000000000000000000000000000000000000000000\",\"from\":\"0x0000000000000000000000000000000000000005\",\"value\":\"0xde0b6b3a7640000\"}".into(),
+      "\"hunter2\"".into(),
+    ]);
+        transport.assert_request(
+            "eth_sendRawTransaction",
+            &["\"0xd46e8dd67c5d32be8d46e8dd67c5d32be8058bb8eb970870f072445675058bb8eb970870f072445675\"".into()],
+        );
+        transport.assert_request(
+            "eth_getTransactionReceipt",
+            &["\"0x70ae45a5067fdf3356aa615ca08d925a38c7ff21b486a61e79d5af3969ebc1a1\"".into()],
+        );
+        transport.assert_no_more_requests();
+        assert_eq!(address, "600515dfe465f600f0c9793fa27cd2794f3ec0e1".parse().unwrap());
+    }
+
     #[test]
     fn deploy_linked_contract() {
         use serde_json::{to_string, to_vec};
@@ -346,4 +463,108 @@ mod tests {
         );
         transport.assert_no_more_requests();
     }
+
+    fn gas_scaling_responses(transport: &mut TestTransport, block_gas_limit: u64) {
+        // estimateGas
+        transport.add_response(rpc::Value::String("0x20000".into()));
+        // latest block
+        let block = Block::<H256> {
+            gas_limit: block_gas_limit.into(),
+            ..Default::default()
+        };
+        transport.add_response(serde_json::to_value(block).unwrap());
+    }
+
+    #[test]
+    fn should_deploy_with_scaled_and_capped_gas() {
+        // given
+        let mut transport = TestTransport::default();
+        gas_scaling_responses(&mut transport, 0x100000);
+        // Transaction Hash
+        let hash = H256::from_low_u64_be(0x123);
+        transport.add_response(serde_json::to_value(hash).unwrap());
+        // receipt
+        let receipt = TransactionReceipt {
+            transaction_hash: hash,
+            contract_address: Some(Address::from_low_u64_be(0x456)),
+            status: Some(1.into()),
+            ..Default::default()
+        };
+        transport.add_response(serde_json::to_value(receipt).unwrap());
+
+        // when
+        let contract = Contract::deploy(api::Eth::new(&transport), b"[]")
+            .unwrap()
+            .confirmations(0)
+            .gas_multiplier(1.5)
+            .gas_cap(0x28000.into())
+            .execute("0x01020304", (), Address::from_low_u64_be(5))
+            .unwrap()
+            .wait()
+            .unwrap();
+
+        // then
+        assert_eq!(contract.address(), Address::from_low_u64_be(0x456));
+        transport.assert_request(
+            "eth_estimateGas",
+            &[r#"{"data":"0x01020304","from":"0x0000000000000000000000000000000000000005"}"#.into()],
+        );
+        transport.assert_request("eth_getBlockByNumber", &[r#""latest""#.into(), "false".into()]);
+        transport.assert_request(
+            "eth_sendTransaction",
+            &[r#"{"data":"0x01020304","from":"0x0000000000000000000000000000000000000005","gas":"0x28000"}"#.into()],
+        );
+        transport.assert_request("eth_getTransactionReceipt", &[serde_json::to_string(&hash).unwrap()]);
+        transport.assert_no_more_requests();
+    }
+
+    #[test]
+    fn should_not_deploy_when_gas_exceeds_block_gas_limit() {
+        // given
+        let mut transport = TestTransport::default();
+        gas_scaling_responses(&mut transport, 0x25000);
+
+        // when
+        let result = Contract::deploy(api::Eth::new(&transport), b"[]")
+            .unwrap()
+            .gas_multiplier(1.5)
+            .gas_cap(0x28000.into())
+            .execute("0x01020304", (), Address::from_low_u64_be(5))
+            .unwrap()
+            .wait();
+
+        // then
+        match result {
+            Err(Error::Api(crate::Error::GasLimitExceeded { gas, block_gas_limit })) => {
+                assert_eq!(gas, 0x28000.into());
+                assert_eq!(block_gas_limit, 0x25000.into());
+            }
+            Err(other) => panic!("Expected gas limit error, got: {:?}", other),
+            Ok(_) => panic!("Expected gas limit error"),
+        }
+        transport.assert_request(
+            "eth_estimateGas",
+            &[r#"{"data":"0x01020304","from":"0x0000000000000000000000000000000000000005"}"#.into()],
+        );
+        transport.assert_request("eth_getBlockByNumber", &[r#""latest""#.into(), "false".into()]);
+        transport.assert_no_more_requests();
+    }
+
+    #[test]
+    #[should_panic(expected = "Gas multiplier must be finite and at least 1.0")]
+    fn should_reject_gas_multiplier_below_one() {
+        let transport = TestTransport::default();
+        let _ = Contract::deploy(api::Eth::new(&transport), b"[]")
+            .unwrap()
+            .gas_multiplier(0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Gas multiplier must be finite and at least 1.0")]
+    fn should_reject_non_finite_gas_multiplier() {
+        let transport = TestTransport::default();
+        let _ = Contract::deploy(api::Eth::new(&transport), b"[]")
+            .unwrap()
+            .gas_multiplier(f64::NAN);
+    }
 }
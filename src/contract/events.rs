@@ -0,0 +1,83 @@
+//! Decoding of events emitted by transactions.
+
+use ethabi;
+use futures::{Async, Future, Poll};
+
+use crate::contract::Error;
+use crate::helpers;
+use crate::types::{Address, Log, TransactionReceipt, U256};
+use crate::Error as ApiError;
+
+/// An event decoded from a log using a contract ABI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent {
+    /// Address of the contract which emitted the event
+    pub address: Address,
+    /// Index of the log in the block
+    pub log_index: Option<U256>,
+    /// Name of the event
+    pub name: String,
+    /// Decoded event parameters
+    pub params: Vec<ethabi::LogParam>,
+}
+
+/// Finds the non-anonymous event of `abi` matching the signature topic of `log`.
+fn find_event<'a>(abi: &'a ethabi::Contract, log: &Log) -> Option<&'a ethabi::Event> {
+    abi.events()
+        .find(|event| !event.anonymous && log.topics.first() == Some(&event.signature()))
+}
+
+/// Decodes a log using the event of `abi` matching its signature topic.
+pub(crate) fn decode_log(abi: &ethabi::Contract, log: Log) -> Result<DecodedEvent, Error> {
+    let event = find_event(abi, &log)
+        .ok_or_else(|| ethabi::Error::Other(format!("No event matching topics {:?}", log.topics)))?;
+    decode_event_log(event, log)
+}
+
+fn decode_event_log(event: &ethabi::Event, log: Log) -> Result<DecodedEvent, Error> {
+    let address = log.address;
+    let log_index = log.log_index;
+    let decoded = super::parse_event_log(event, log)?;
+
+    Ok(DecodedEvent {
+        address,
+        log_index,
+        name: event.name.clone(),
+        params: decoded.params,
+    })
+}
+
+/// Future resolving to the events emitted by a transaction, see `Contract::transaction_events`.
+#[derive(Debug)]
+pub struct TransactionEvents<F> {
+    abi: ethabi::Contract,
+    receipt: helpers::CallFuture<Option<TransactionReceipt>, F>,
+}
+
+impl<F> TransactionEvents<F> {
+    pub(crate) fn new(abi: ethabi::Contract, receipt: helpers::CallFuture<Option<TransactionReceipt>, F>) -> Self {
+        TransactionEvents { abi, receipt }
+    }
+}
+
+impl<F> Future for TransactionEvents<F>
+where
+    F: Future<Item = crate::rpc::Value, Error = ApiError>,
+{
+    type Item = Option<Vec<Result<DecodedEvent, Error>>>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let receipt = try_ready!(self.receipt.poll());
+        let abi = &self.abi;
+        // logs of other events are skipped, logs of known events are reported even if they fail to decode
+        let events = receipt.map(|receipt| {
+            receipt
+                .logs
+                .into_iter()
+                .filter_map(|log| find_event(abi, &log).map(|event| decode_event_log(event, log)))
+                .collect()
+        });
+        Ok(Async::Ready(events))
+    }
+}
@@ -1,21 +1,31 @@
 //! Ethereum Contract Interface
 
 use ethabi;
+use rustc_hex::ToHex;
 
 use crate::api::{Eth, Namespace};
 use crate::confirm;
 use crate::contract::tokens::{Detokenize, Tokenize};
-use crate::types::{Address, BlockNumber, Bytes, CallRequest, TransactionCondition, TransactionRequest, H256, U256};
+use crate::signing::keccak256;
+use crate::types::{
+    Address, BlockNumber, Bytes, CallRequest, Log, TransactionCondition, TransactionRequest, H256, U256,
+};
 use crate::Transport;
 use std::{collections::HashMap, hash::Hash, time};
 
 pub mod deploy;
 mod error;
+mod events;
+mod packed;
 mod result;
+mod summary;
 pub mod tokens;
 
 pub use crate::contract::error::Error;
-pub use crate::contract::result::{CallFuture, QueryResult};
+pub use crate::contract::events::{DecodedEvent, TransactionEvents};
+pub use crate::contract::packed::encode_packed;
+pub use crate::contract::result::{CallFuture, QueryResult, StringDecoding};
+pub use crate::contract::summary::{summarize_transaction, TransactionSummary};
 
 /// Contract Call/Query Options
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -63,6 +73,8 @@ impl<T: Transport> Contract<T> {
             confirmations: 1,
             poll_interval: time::Duration::from_secs(7),
             linker: HashMap::default(),
+            gas_multiplier: None,
+            gas_cap: None,
         })
     }
 
@@ -84,6 +96,8 @@ impl<T: Transport> Contract<T> {
             confirmations: 1,
             poll_interval: time::Duration::from_secs(7),
             linker,
+            gas_multiplier: None,
+            gas_cap: None,
         })
     }
 }
@@ -128,10 +142,14 @@ impl<T: Transport> Contract<T> {
                         to: Some(self.address),
                         gas,
                         gas_price,
+                        max_fee_per_gas: None,
+                        max_priority_fee_per_gas: None,
                         value,
                         nonce,
                         data: Some(Bytes(data)),
                         condition,
+                        access_list: None,
+                        transaction_type: None,
                     })
                     .into()
             })
@@ -149,6 +167,64 @@ impl<T: Transport> Contract<T> {
 
     }
 
+    /// Decodes transaction input data back into the called function name and its parameters.
+    ///
+    /// The leading 4-byte selector is matched against functions in the ABI.
+    pub fn decode_input(&self, data: &[u8]) -> Result<(String, Vec<ethabi::Token>), Error> {
+        if data.len() < 4 {
+            return Err(ethabi::Error::InvalidData.into());
+        }
+        let (selector, params) = data.split_at(4);
+
+        let function = self
+            .abi
+            .functions()
+            .find(|function| {
+                let types = function
+                    .inputs
+                    .iter()
+                    .map(|param| param.kind.to_string())
+                    .collect::<Vec<_>>();
+                let signature = format!("{}({})", function.name, types.join(","));
+                keccak256(signature.as_bytes())[..4] == *selector
+            })
+            .ok_or_else(|| {
+                ethabi::Error::Other(format!(
+                    "No function matching selector 0x{}",
+                    selector.to_hex::<String>()
+                ))
+            })?;
+
+        let tokens = function.decode_input(params)?;
+        Ok((function.name.clone(), tokens))
+    }
+
+    /// Decodes a log emitted by the given event of this contract.
+    ///
+    /// Indexed `address` parameters are returned as `Token::Address` taken from the low
+    /// 20 bytes of the topic, topics with non-zero upper 12 bytes are rejected as invalid.
+    pub fn parse_log(&self, event: &str, log: Log) -> Result<ethabi::Log, Error> {
+        parse_event_log(self.abi.event(event)?, log)
+    }
+
+    /// Decodes a log using the event of this contract's ABI matching its signature topic.
+    ///
+    /// The log may have been emitted by any contract sharing the event definition.
+    pub fn decode_log(&self, log: Log) -> Result<DecodedEvent, Error> {
+        events::decode_log(&self.abi, log)
+    }
+
+    /// Fetches the receipt of a transaction and decodes all of its logs matching
+    /// an event of this contract's ABI, regardless of the emitting contract.
+    ///
+    /// Logs not matching any event of the ABI are skipped. Logs matching an event are decoded
+    /// one by one, so a log which fails to decode (e.g. an ERC-721 `Transfer` decoded with an
+    /// ERC-20 ABI) yields an error without hiding the other events. Resolves to `None` if the
+    /// transaction is not mined yet.
+    pub fn transaction_events(&self, hash: H256) -> TransactionEvents<T::Out> {
+        TransactionEvents::new(self.abi.clone(), self.eth.transaction_receipt(hash))
+    }
+
     /// Execute a contract function and wait for confirmations
     pub fn call_with_confirmations<P>(
         &self,
@@ -172,10 +248,14 @@ impl<T: Transport> Contract<T> {
                     to: Some(self.address),
                     gas: options.gas,
                     gas_price: options.gas_price,
+                    max_fee_per_gas: None,
+                    max_priority_fee_per_gas: None,
                     value: options.value,
                     nonce: options.nonce,
                     data: Some(Bytes(fn_data)),
                     condition: options.condition,
+                    access_list: None,
+                    transaction_type: None,
                 };
 
                 confirm::send_transaction_with_confirmation(
@@ -227,6 +307,7 @@ impl<T: Transport> Contract<T> {
                             gas_price: options.gas_price,
                             value: options.value,
                             data: Some(Bytes(data)),
+                            access_list: None,
                         },
                         None,
                     )
@@ -266,6 +347,7 @@ impl<T: Transport> Contract<T> {
                         gas_price: options.gas_price,
                         value: options.value,
                         data: Some(Bytes(call)),
+                        access_list: None,
                     },
                     block.into(),
                 );
@@ -275,13 +357,29 @@ impl<T: Transport> Contract<T> {
     }
 }
 
+fn parse_event_log(event: &ethabi::Event, log: Log) -> Result<ethabi::Log, Error> {
+    let skip = if event.anonymous { 0 } else { 1 };
+    let indexed = event.inputs.iter().filter(|param| param.indexed);
+
+    for (param, topic) in indexed.zip(log.topics.iter().skip(skip)) {
+        if param.kind == ethabi::ParamType::Address && topic[..12].iter().any(|byte| *byte != 0) {
+            return Err(ethabi::Error::InvalidData.into());
+        }
+    }
+
+    Ok(event.parse_log(ethabi::RawLog {
+        topics: log.topics,
+        data: log.data.0,
+    })?)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Contract, Options};
+    use super::{Contract, Options, StringDecoding};
     use crate::api::{self, Namespace};
     use crate::helpers::tests::TestTransport;
     use crate::rpc;
-    use crate::types::{Address, BlockNumber, H256, U256};
+    use crate::types::{Address, BlockNumber, Bytes, Log, TransactionReceipt, H256, U256};
     use crate::Transport;
     use futures::Future;
 
@@ -318,6 +416,40 @@ mod tests {
         assert_eq!(result, "Hello World!".to_owned());
     }
 
+    #[test]
+    fn should_decode_non_utf8_string() {
+        // given
+        // "Hello" followed by an invalid UTF-8 byte (0xff)
+        let response = "0x0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000648656c6c6fff0000000000000000000000000000000000000000000000000000";
+        let mut transport = TestTransport::default();
+        transport.set_response(rpc::Value::String(response.into()));
+        transport.add_response(rpc::Value::String(response.into()));
+        transport.add_response(rpc::Value::String(response.into()));
+
+        let (strict, lossy, bytes) = {
+            let token = contract(&transport);
+
+            // when
+            let strict: Result<String, _> = token.query("name", (), None, Options::default(), None).wait();
+            let lossy: String = token
+                .query("name", (), None, Options::default(), None)
+                .with_string_decoding(StringDecoding::Lossy)
+                .wait()
+                .unwrap();
+            let bytes: Vec<u8> = token
+                .query("name", (), None, Options::default(), None)
+                .with_string_decoding(StringDecoding::Bytes)
+                .wait()
+                .unwrap();
+            (strict, lossy, bytes)
+        };
+
+        // then
+        assert!(strict.is_err());
+        assert_eq!(lossy, "Hello\u{fffd}".to_owned());
+        assert_eq!(bytes, b"Hello\xff".to_vec());
+    }
+
     #[test]
     fn should_query_with_params() {
         // given
@@ -416,4 +548,266 @@ mod tests {
         assert_eq!(result, 0x20.into());
     }
 
+    #[test]
+    fn should_query_function_returning_a_tuple() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.set_response(rpc::Value::String("0x00000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000001".into()));
+        let abi = br#"[{"constant":true,"inputs":[],"name":"status","outputs":[{"name":"","type":"uint256"},{"name":"","type":"bool"}],"payable":false,"stateMutability":"view","type":"function"}]"#;
+
+        let result: (U256, bool) = {
+            let eth = api::Eth::new(&transport);
+            let contract = Contract::from_json(eth, Address::from_low_u64_be(1), abi).unwrap();
+
+            // when
+            contract
+                .query("status", (), None, Options::default(), None)
+                .wait()
+                .unwrap()
+        };
+
+        // then
+        transport.assert_request(
+            "eth_call",
+            &[
+                "{\"data\":\"0x200d2ed2\",\"to\":\"0x0000000000000000000000000000000000000001\"}".into(),
+                "\"latest\"".into(),
+            ],
+        );
+        transport.assert_no_more_requests();
+        assert_eq!(result, (0x20.into(), true));
+    }
+
+    #[test]
+    fn should_decode_function_input() {
+        // given
+        let transport = TestTransport::default();
+        let token = contract(&transport);
+        let data = token
+            .get_function_data("transfer", (Address::from_low_u64_be(5), U256::from(1_000)))
+            .unwrap();
+        assert_eq!(&data[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+
+        // when
+        let result = token.decode_input(&data).unwrap();
+
+        // then
+        assert_eq!(
+            result,
+            (
+                "transfer".to_owned(),
+                vec![
+                    ethabi::Token::Address(Address::from_low_u64_be(5)),
+                    ethabi::Token::Uint(1_000.into())
+                ]
+            )
+        );
+        assert!(token.decode_input(&[0xde, 0xad, 0xbe, 0xef]).is_err());
+    }
+
+    #[test]
+    fn should_parse_transfer_log() {
+        // given
+        let transport = TestTransport::default();
+        let token = contract(&transport);
+        let transfer: H256 = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            .parse()
+            .unwrap();
+        let mut log = Log {
+            address: Address::from_low_u64_be(1),
+            topics: vec![transfer, H256::from_low_u64_be(5), H256::from_low_u64_be(6)],
+            data: Bytes(ethabi::encode(&[ethabi::Token::Uint(1_000.into())])),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+
+        // when
+        let result = token.parse_log("Transfer", log.clone()).unwrap();
+
+        // then
+        let params = result
+            .params
+            .into_iter()
+            .map(|param| (param.name, param.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            params,
+            vec![
+                ("from".to_owned(), ethabi::Token::Address(Address::from_low_u64_be(5))),
+                ("to".to_owned(), ethabi::Token::Address(Address::from_low_u64_be(6))),
+                ("value".to_owned(), ethabi::Token::Uint(1_000.into())),
+            ]
+        );
+
+        // upper 12 bytes of an address topic have to be zero
+        log.topics[1].as_bytes_mut()[0] = 1;
+        assert!(token.parse_log("Transfer", log).is_err());
+    }
+
+    #[test]
+    fn should_decode_events_of_transaction() {
+        // given
+        let transfer: H256 = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            .parse()
+            .unwrap();
+        let log = |address: u64, topics: Vec<H256>, value: u64, log_index: u64| Log {
+            address: Address::from_low_u64_be(address),
+            topics,
+            data: Bytes(ethabi::encode(&[ethabi::Token::Uint(value.into())])),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: Some(log_index.into()),
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        let first = vec![transfer, H256::from_low_u64_be(5), H256::from_low_u64_be(6)];
+        let second = vec![transfer, H256::from_low_u64_be(6), H256::from_low_u64_be(5)];
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::from_low_u64_be(0x123),
+            transaction_index: 0.into(),
+            block_hash: Some(H256::zero()),
+            block_number: Some(1.into()),
+            cumulative_gas_used: 50_000.into(),
+            gas_used: Some(50_000.into()),
+            contract_address: None,
+            logs: vec![
+                log(1, first, 1_000, 0),
+                log(7, vec![H256::from_low_u64_be(0xdead)], 1, 1),
+                log(8, second, 500, 2),
+            ],
+            status: Some(1.into()),
+            logs_bloom: Default::default(),
+        };
+        let mut transport = TestTransport::default();
+        transport.set_response(serde_json::to_value(&receipt).unwrap());
+
+        let result = {
+            let token = contract(&transport);
+
+            // when
+            token.transaction_events(H256::from_low_u64_be(0x123)).wait().unwrap()
+        };
+
+        // then
+        transport.assert_request(
+            "eth_getTransactionReceipt",
+            &[r#""0x0000000000000000000000000000000000000000000000000000000000000123""#.into()],
+        );
+        transport.assert_no_more_requests();
+        let events = result
+            .unwrap()
+            .into_iter()
+            .map(|event| {
+                let event = event.unwrap();
+                let values = event.params.into_iter().map(|param| param.value).collect::<Vec<_>>();
+                (event.address, event.log_index, event.name, values)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                (
+                    Address::from_low_u64_be(1),
+                    Some(0.into()),
+                    "Transfer".to_owned(),
+                    vec![
+                        ethabi::Token::Address(Address::from_low_u64_be(5)),
+                        ethabi::Token::Address(Address::from_low_u64_be(6)),
+                        ethabi::Token::Uint(1_000.into()),
+                    ]
+                ),
+                (
+                    Address::from_low_u64_be(8),
+                    Some(2.into()),
+                    "Transfer".to_owned(),
+                    vec![
+                        ethabi::Token::Address(Address::from_low_u64_be(6)),
+                        ethabi::Token::Address(Address::from_low_u64_be(5)),
+                        ethabi::Token::Uint(500.into()),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_transaction_events_not_matching_abi_separately() {
+        // given
+        let transfer: H256 = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+            .parse()
+            .unwrap();
+        let log = |topics: Vec<H256>, data: Vec<u8>, log_index: u64| Log {
+            address: Address::from_low_u64_be(1),
+            topics,
+            data: Bytes(data),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: Some(log_index.into()),
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::from_low_u64_be(0x123),
+            transaction_index: 0.into(),
+            block_hash: Some(H256::zero()),
+            block_number: Some(1.into()),
+            cumulative_gas_used: 50_000.into(),
+            gas_used: Some(50_000.into()),
+            contract_address: None,
+            logs: vec![
+                // ERC-721 `Transfer`: same signature, but the token id is indexed and the data is empty
+                log(
+                    vec![
+                        transfer,
+                        H256::from_low_u64_be(5),
+                        H256::from_low_u64_be(6),
+                        H256::from_low_u64_be(42),
+                    ],
+                    vec![],
+                    0,
+                ),
+                // ERC-20 `Transfer`
+                log(
+                    vec![transfer, H256::from_low_u64_be(5), H256::from_low_u64_be(6)],
+                    ethabi::encode(&[ethabi::Token::Uint(1_000.into())]),
+                    1,
+                ),
+            ],
+            status: Some(1.into()),
+            logs_bloom: Default::default(),
+        };
+        let mut transport = TestTransport::default();
+        transport.set_response(serde_json::to_value(&receipt).unwrap());
+
+        // when
+        let hash = H256::from_low_u64_be(0x123);
+        let result = contract(&transport).transaction_events(hash).wait().unwrap().unwrap();
+
+        // then
+        assert_eq!(result.len(), 2);
+        assert!(result[0].is_err(), "Expected decoding error, got: {:?}", result[0]);
+        let event = result[1].as_ref().unwrap();
+        assert_eq!(event.name, "Transfer");
+        assert_eq!(event.log_index, Some(1.into()));
+        assert_eq!(
+            event.params.iter().map(|param| param.value.clone()).collect::<Vec<_>>(),
+            vec![
+                ethabi::Token::Address(Address::from_low_u64_be(5)),
+                ethabi::Token::Address(Address::from_low_u64_be(6)),
+                ethabi::Token::Uint(1_000.into()),
+            ]
+        );
+    }
 }
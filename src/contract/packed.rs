@@ -0,0 +1,89 @@
+//! Non-standard packed ABI encoding.
+
+use ethabi::Token;
+
+/// Encodes tokens the same way as Solidity's `abi.encodePacked`.
+///
+/// Static types are concatenated without padding, dynamic types (`bytes`, `string`) are
+/// concatenated in place without length prefix, array elements are padded to 32 bytes.
+///
+/// Note that `Token` does not carry the bit width of integers, so `Int` and `Uint` are always
+/// encoded as 256-bit values; use `FixedBytes` of the right length for narrower types.
+/// Packed encoding is ambiguous, e.g. `("a", "bc")` and `("ab", "c")` produce the same output,
+/// so it should not be used to hash more than one dynamic value without separators.
+pub fn encode_packed(tokens: &[Token]) -> Vec<u8> {
+    let mut result = vec![];
+    for token in tokens {
+        encode_token(token, &mut result, false);
+    }
+    result
+}
+
+fn encode_token(token: &Token, out: &mut Vec<u8>, in_array: bool) {
+    match *token {
+        Token::Address(_) | Token::Bool(_) if in_array => {
+            out.extend_from_slice(&ethabi::encode(std::slice::from_ref(token)))
+        }
+        Token::Address(ref address) => out.extend_from_slice(address.as_bytes()),
+        Token::Bool(value) => out.push(value as u8),
+        Token::Int(ref value) | Token::Uint(ref value) => {
+            let mut bytes = [0u8; 32];
+            value.to_big_endian(&mut bytes);
+            out.extend_from_slice(&bytes);
+        }
+        Token::FixedBytes(ref bytes) if in_array => {
+            out.extend_from_slice(bytes);
+            out.resize(out.len() + (32 - bytes.len() % 32) % 32, 0);
+        }
+        Token::FixedBytes(ref bytes) | Token::Bytes(ref bytes) => out.extend_from_slice(bytes),
+        Token::String(ref s) => out.extend_from_slice(s.as_bytes()),
+        Token::FixedArray(ref tokens) | Token::Array(ref tokens) | Token::Tuple(ref tokens) => {
+            for token in tokens {
+                encode_token(token, out, true);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_packed;
+    use crate::types::Address;
+    use ethabi::Token;
+    use rustc_hex::FromHex;
+
+    #[test]
+    fn should_encode_address_and_uint() {
+        // abi.encodePacked(address(0x8e...), uint256(1))
+        let address: Address = "8ee2e3b5bc47df5e4f0a7c1ab7bb7b8a03f4e0c1".parse().unwrap();
+        let encoded = encode_packed(&[Token::Address(address), Token::Uint(1.into())]);
+
+        let expected: Vec<u8> =
+            "8ee2e3b5bc47df5e4f0a7c1ab7bb7b8a03f4e0c10000000000000000000000000000000000000000000000000000000000000001"
+                .from_hex()
+                .unwrap();
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn should_encode_dynamic_types_and_arrays() {
+        // abi.encodePacked("Hello", true, bytes2(0x1234), [uint256(1), uint256(2)])
+        let encoded = encode_packed(&[
+            Token::String("Hello".into()),
+            Token::Bool(true),
+            Token::FixedBytes(vec![0x12, 0x34]),
+            Token::FixedArray(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+        ]);
+
+        let expected: Vec<u8> = concat!(
+            "48656c6c6f",
+            "01",
+            "1234",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "0000000000000000000000000000000000000000000000000000000000000002",
+        )
+        .from_hex()
+        .unwrap();
+        assert_eq!(encoded, expected);
+    }
+}
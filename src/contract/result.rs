@@ -1,4 +1,4 @@
-use ethabi;
+use ethabi::{self, ParamType, Token};
 use futures::{Async, Future, Poll};
 use serde;
 use std::mem;
@@ -45,12 +45,27 @@ where
     }
 }
 
+/// Controls how `string` outputs of a contract function are decoded.
+///
+/// Some contracts return `string` values that aren't valid UTF-8
+/// (e.g. `bytes32` tokens converted to strings).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringDecoding {
+    /// Fail with an ABI error if the string is not valid UTF-8 (default).
+    Strict,
+    /// Replace invalid UTF-8 sequences with `U+FFFD` and return `Token::String`.
+    Lossy,
+    /// Return the raw bytes as `Token::Bytes`.
+    Bytes,
+}
+
 /// Function-specific bytes-decoder future.
 /// Takes any type which is deserializable from `Vec<ethabi::Token>`,
 /// a function definition and a future which yields that type.
 #[derive(Debug)]
 pub struct QueryResult<T, F> {
     inner: ResultType<T, F>,
+    string_decoding: StringDecoding,
 }
 
 impl<T, F, E> From<E> for QueryResult<T, F>
@@ -60,6 +75,7 @@ where
     fn from(e: E) -> Self {
         QueryResult {
             inner: ResultType::Constant(Err(e.into())),
+            string_decoding: StringDecoding::Strict,
         }
     }
 }
@@ -69,7 +85,74 @@ impl<T, F> QueryResult<T, F> {
     pub fn new(inner: helpers::CallFuture<Bytes, F>, function: ethabi::Function) -> Self {
         QueryResult {
             inner: ResultType::Decodable(inner, function),
+            string_decoding: StringDecoding::Strict,
+        }
+    }
+
+    /// Sets how `string` outputs are decoded.
+    pub fn with_string_decoding(mut self, string_decoding: StringDecoding) -> Self {
+        self.string_decoding = string_decoding;
+        self
+    }
+}
+
+fn decode_output(
+    function: &ethabi::Function,
+    data: &[u8],
+    string_decoding: StringDecoding,
+) -> Result<Vec<Token>, ethabi::Error> {
+    if string_decoding == StringDecoding::Strict {
+        return function.decode_output(data);
+    }
+
+    // `string` and `bytes` share the same encoding, so decode as bytes and convert afterwards.
+    let types: Vec<_> = function
+        .outputs
+        .iter()
+        .map(|param| string_as_bytes(&param.kind))
+        .collect();
+    let tokens = ethabi::decode(&types, data)?;
+
+    Ok(match string_decoding {
+        StringDecoding::Lossy => function
+            .outputs
+            .iter()
+            .zip(tokens)
+            .map(|(param, token)| bytes_as_string(&param.kind, token))
+            .collect(),
+        _ => tokens,
+    })
+}
+
+fn string_as_bytes(kind: &ParamType) -> ParamType {
+    match *kind {
+        ParamType::String => ParamType::Bytes,
+        ParamType::Array(ref inner) => ParamType::Array(Box::new(string_as_bytes(inner))),
+        ParamType::FixedArray(ref inner, len) => ParamType::FixedArray(Box::new(string_as_bytes(inner)), len),
+        ParamType::Tuple(ref inner) => {
+            ParamType::Tuple(inner.iter().map(|kind| Box::new(string_as_bytes(kind))).collect())
+        }
+        ref other => other.clone(),
+    }
+}
+
+fn bytes_as_string(kind: &ParamType, token: Token) -> Token {
+    match (kind, token) {
+        (ParamType::String, Token::Bytes(bytes)) => Token::String(String::from_utf8_lossy(&bytes).into_owned()),
+        (ParamType::Array(inner), Token::Array(tokens)) => {
+            Token::Array(tokens.into_iter().map(|token| bytes_as_string(inner, token)).collect())
+        }
+        (ParamType::FixedArray(inner, _), Token::FixedArray(tokens)) => {
+            Token::FixedArray(tokens.into_iter().map(|token| bytes_as_string(inner, token)).collect())
         }
+        (ParamType::Tuple(inner), Token::Tuple(tokens)) => Token::Tuple(
+            inner
+                .iter()
+                .zip(tokens)
+                .map(|(kind, token)| bytes_as_string(kind, token))
+                .collect(),
+        ),
+        (_, token) => token,
     }
 }
 
@@ -83,7 +166,8 @@ where
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
         if let ResultType::Decodable(ref mut inner, ref function) = self.inner {
             let bytes: Bytes = try_ready!(inner.poll());
-            return Ok(Async::Ready(T::from_tokens(function.decode_output(&bytes.0)?)?));
+            let tokens = decode_output(function, &bytes.0, self.string_decoding)?;
+            return Ok(Async::Ready(T::from_tokens(tokens)?));
         }
 
         match mem::replace(&mut self.inner, ResultType::Done) {
@@ -0,0 +1,132 @@
+//! Human-readable summaries of transactions, e.g. for display on hardware wallets.
+
+use ethabi::Token;
+
+use crate::signing::keccak256;
+use crate::types::{Address, TransactionRequest, U256};
+
+/// Breakdown of an unsigned transaction for clear-signing displays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionSummary {
+    /// Recipient (None for contract creation)
+    pub to: Option<Address>,
+    /// Transferred value
+    pub value: U256,
+    /// Gas limit
+    pub gas: Option<U256>,
+    /// Legacy gas price
+    pub gas_price: Option<U256>,
+    /// Max fee per gas (EIP-1559)
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas (EIP-1559)
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// First four bytes of the call data
+    pub selector: Option<[u8; 4]>,
+    /// Signature of the called function, if it was found in the supplied ABI
+    pub function: Option<String>,
+    /// Named arguments of the called function, empty if the call data couldn't be decoded
+    pub arguments: Vec<(String, Token)>,
+}
+
+/// Summarizes given transaction, decoding the called function when `abi` is supplied.
+pub fn summarize_transaction(tx: &TransactionRequest, abi: Option<&ethabi::Contract>) -> TransactionSummary {
+    let data = tx.data.as_ref().map(|data| &data.0[..]).unwrap_or_default();
+    let selector = if data.len() >= 4 {
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&data[..4]);
+        Some(selector)
+    } else {
+        None
+    };
+
+    let function = match (abi, selector) {
+        (Some(abi), Some(selector)) => abi.functions().find(|function| function_selector(function) == selector),
+        _ => None,
+    };
+    let arguments = function
+        .and_then(|function| {
+            let tokens = function.decode_input(&data[4..]).ok()?;
+            let names = function.inputs.iter().map(|param| param.name.clone());
+            Some(names.zip(tokens).collect())
+        })
+        .unwrap_or_default();
+
+    TransactionSummary {
+        to: tx.to,
+        value: tx.value.unwrap_or_default(),
+        gas: tx.gas,
+        gas_price: tx.gas_price,
+        max_fee_per_gas: tx.max_fee_per_gas,
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+        selector,
+        function: function.map(canonical_signature),
+        arguments,
+    }
+}
+
+fn canonical_signature(function: &ethabi::Function) -> String {
+    let inputs = function
+        .inputs
+        .iter()
+        .map(|param| param.kind.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{}({})", function.name, inputs)
+}
+
+fn function_selector(function: &ethabi::Function) -> [u8; 4] {
+    let hash = keccak256(canonical_signature(function).as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Bytes;
+
+    #[test]
+    fn should_summarize_transfer_call() {
+        let abi = ethabi::Contract::load(&include_bytes!("./res/token.json")[..]).unwrap();
+        let recipient = Address::from_low_u64_be(0x123);
+        let data = abi
+            .function("transfer")
+            .unwrap()
+            .encode_input(&[Token::Address(recipient), Token::Uint(1000.into())])
+            .unwrap();
+        let tx = TransactionRequest {
+            from: Address::from_low_u64_be(1),
+            to: Some(Address::from_low_u64_be(2)),
+            gas: Some(60_000.into()),
+            gas_price: None,
+            max_fee_per_gas: Some(100.into()),
+            max_priority_fee_per_gas: Some(2.into()),
+            value: None,
+            data: Some(Bytes(data)),
+            nonce: None,
+            condition: None,
+            access_list: None,
+            transaction_type: None,
+        };
+
+        let summary = summarize_transaction(&tx, Some(&abi));
+
+        assert_eq!(summary.to, Some(Address::from_low_u64_be(2)));
+        assert_eq!(summary.value, U256::zero());
+        assert_eq!(summary.max_fee_per_gas, Some(100.into()));
+        assert_eq!(summary.max_priority_fee_per_gas, Some(2.into()));
+        assert_eq!(summary.selector, Some([0xa9, 0x05, 0x9c, 0xbb]));
+        assert_eq!(summary.function, Some("transfer(address,uint256)".into()));
+        assert_eq!(
+            summary.arguments,
+            vec![
+                ("_to".into(), Token::Address(recipient)),
+                ("_value".into(), Token::Uint(1000.into())),
+            ]
+        );
+
+        let without_abi = summarize_transaction(&tx, None);
+        assert_eq!(without_abi.selector, Some([0xa9, 0x05, 0x9c, 0xbb]));
+        assert_eq!(without_abi.function, None);
+        assert!(without_abi.arguments.is_empty());
+    }
+}
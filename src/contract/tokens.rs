@@ -291,7 +291,7 @@ macro_rules! tokenizable_item {
 }
 
 tokenizable_item! {
-    Token, String, Address, H256, U256, U128, bool, Vec<u8>,
+    Token, String, Address, H256, U256, U128, bool, Bytes, Vec<u8>,
     i8, i16, i32, i64, i128, u16, u32, u64, u128,
 }
 
@@ -419,10 +419,37 @@ impl_fixed_types!(1024);
 
 #[cfg(test)]
 mod tests {
-    use super::{Detokenize, Tokenizable};
-    use crate::types::{Address, U256};
+    use super::{Detokenize, Tokenizable, Tokenize};
+    use crate::contract::error::Error;
+    use crate::types::{Address, Bytes, U256};
     use ethabi::Token;
 
+    #[derive(Debug, Clone, PartialEq)]
+    struct Position {
+        owner: Address,
+        amount: U256,
+        active: bool,
+        proofs: Vec<Bytes>,
+    }
+
+    impl Detokenize for Position {
+        fn from_tokens(tokens: Vec<Token>) -> Result<Self, Error> {
+            let (owner, amount, active, proofs) = Detokenize::from_tokens(tokens)?;
+            Ok(Position {
+                owner,
+                amount,
+                active,
+                proofs,
+            })
+        }
+    }
+
+    impl Tokenize for Position {
+        fn into_tokens(self) -> Vec<Token> {
+            (self.owner, self.amount, self.active, self.proofs).into_tokens()
+        }
+    }
+
     fn output<R: Detokenize>() -> R {
         unimplemented!()
     }
@@ -446,6 +473,35 @@ mod tests {
 
         let _ints: (i8, i16, i32, i64, i128) = output();
         let _uints: (u16, u32, u64, u128) = output();
+        let _custom: Position = output();
+    }
+
+    #[test]
+    fn should_round_trip_custom_type() {
+        let position = Position {
+            owner: Address::from_low_u64_be(5),
+            amount: 1_000.into(),
+            active: true,
+            proofs: vec![Bytes(vec![1, 2]), Bytes(vec![3])],
+        };
+
+        let tokens = position.clone().into_tokens();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Address(Address::from_low_u64_be(5)),
+                Token::Uint(1_000.into()),
+                Token::Bool(true),
+                Token::Array(vec![Token::Bytes(vec![1, 2]), Token::Bytes(vec![3])]),
+            ]
+        );
+        assert_eq!(Position::from_tokens(tokens).unwrap(), position);
+    }
+
+    #[test]
+    fn should_reject_custom_type_with_wrong_arity() {
+        let tokens = vec![Token::Address(Address::from_low_u64_be(5)), Token::Bool(true)];
+        assert!(Position::from_tokens(tokens).is_err());
     }
 
     #[test]
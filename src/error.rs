@@ -1,10 +1,19 @@
 //! Web3 Error
-use crate::rpc::error::Error as RPCError;
+use crate::rpc::error::{Error as RPCError, ErrorCode};
+use crate::types::U256;
 use derive_more::{Display, From};
+use rlp::DecoderError;
 use secp256k1::Error as Secp256k1Error;
-use serde_json::Error as SerdeError;
+use serde_json::{Error as SerdeError, Value};
 use std::io::Error as IoError;
 
+/// Error code used by nodes to signal that a request exceeded a rate or resource limit.
+const LIMIT_EXCEEDED: i64 = -32005;
+/// Error code used by Geth for reverted execution carrying revert data.
+const GETH_EXECUTION_REVERTED: i64 = 3;
+/// Error code used by Parity for failed VM execution.
+const PARITY_VM_EXECUTION_ERROR: i64 = -32015;
+
 /// Errors which can occur when attempting to generate resource uri.
 #[derive(Debug, Display, From)]
 pub enum Error {
@@ -31,16 +40,71 @@ pub enum Error {
     /// signing error
     #[display(fmt = "Signing error: {}", _0)]
     Signing(Secp256k1Error),
+    /// transaction transfers value to the zero address
+    #[display(fmt = "Refusing to transfer value to the zero address")]
+    ZeroAddressRecipient,
+    /// transaction sets both a legacy gas price and EIP-1559 fees
+    #[display(fmt = "Transaction sets both a gas price and EIP-1559 fees")]
+    ConflictingFees,
+    /// estimated transaction gas exceeds the gas limit of the latest block
+    #[display(fmt = "Gas {} exceeds the block gas limit {}", gas, block_gas_limit)]
+    #[from(ignore)]
+    GasLimitExceeded {
+        /// Gas the transaction would be sent with
+        gas: U256,
+        /// Gas limit of the latest block
+        block_gas_limit: U256,
+    },
     /// web3 internal error
     #[display(fmt = "Internal Web3 error")]
     Internal,
 }
 
+impl Error {
+    /// Returns `true` if the failure is likely temporary and the request may succeed when retried.
+    ///
+    /// Transport and I/O failures are retryable, as are RPC errors signalling that the node
+    /// is rate limiting. Reverts and decoding errors are not.
+    pub fn is_retryable(&self) -> bool {
+        use self::Error::*;
+        match *self {
+            Unreachable | Transport(_) | Io(_) => true,
+            Rpc(ref e) => e.code == ErrorCode::ServerError(LIMIT_EXCEEDED),
+            Decoder(_)
+            | InvalidResponse(_)
+            | Signing(_)
+            | ZeroAddressRecipient
+            | ConflictingFees
+            | GasLimitExceeded { .. }
+            | Internal => false,
+        }
+    }
+
+    /// Returns `true` if the node reported that the execution of a call or transaction reverted.
+    ///
+    /// Geth reports reverts with code `3` and an `execution reverted` message, Parity with
+    /// code `-32015` and a `Reverted 0x...` error data.
+    pub fn is_revert(&self) -> bool {
+        let error = match *self {
+            Error::Rpc(ref error) => error,
+            _ => return false,
+        };
+        match (&error.code, &error.data) {
+            (&ErrorCode::ServerError(GETH_EXECUTION_REVERTED), _) => error.message.starts_with("execution reverted"),
+            (&ErrorCode::ServerError(PARITY_VM_EXECUTION_ERROR), &Some(Value::String(ref data))) => {
+                data.starts_with("Reverted 0x")
+            }
+            _ => false,
+        }
+    }
+}
+
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         use self::Error::*;
         match *self {
-            Unreachable | Decoder(_) | InvalidResponse(_) | Transport(_) | Internal => None,
+            Unreachable | Decoder(_) | InvalidResponse(_) | Transport(_) | ZeroAddressRecipient | Internal => None,
+            ConflictingFees | GasLimitExceeded { .. } => None,
             Rpc(ref e) => Some(e),
             Io(ref e) => Some(e),
             Signing(ref e) => Some(e),
@@ -54,6 +118,12 @@ impl From<SerdeError> for Error {
     }
 }
 
+impl From<DecoderError> for Error {
+    fn from(err: DecoderError) -> Self {
+        Error::Decoder(format!("{:?}", err))
+    }
+}
+
 impl Clone for Error {
     fn clone(&self) -> Self {
         use self::Error::*;
@@ -65,6 +135,12 @@ impl Clone for Error {
             Rpc(e) => Rpc(e.clone()),
             Io(e) => Io(IoError::from(e.kind())),
             Signing(e) => Signing(*e),
+            ZeroAddressRecipient => ZeroAddressRecipient,
+            ConflictingFees => ConflictingFees,
+            GasLimitExceeded { gas, block_gas_limit } => GasLimitExceeded {
+                gas: *gas,
+                block_gas_limit: *block_gas_limit,
+            },
             Internal => Internal,
         }
     }
@@ -74,14 +150,95 @@ impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
         use self::Error::*;
         match (self, other) {
-            (Unreachable, Unreachable) | (Internal, Internal) => true,
+            (Unreachable, Unreachable)
+            | (ZeroAddressRecipient, ZeroAddressRecipient)
+            | (ConflictingFees, ConflictingFees)
+            | (Internal, Internal) => true,
             (Decoder(a), Decoder(b)) | (InvalidResponse(a), InvalidResponse(b)) | (Transport(a), Transport(b)) => {
                 a == b
             }
             (Rpc(a), Rpc(b)) => a == b,
             (Io(a), Io(b)) => a.kind() == b.kind(),
             (Signing(a), Signing(b)) => a == b,
+            (
+                GasLimitExceeded {
+                    gas: a,
+                    block_gas_limit: x,
+                },
+                GasLimitExceeded {
+                    gas: b,
+                    block_gas_limit: y,
+                },
+            ) => a == b && x == y,
             _ => false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+    use crate::helpers;
+    use crate::rpc;
+    use serde_json;
+    use std::io;
+
+    fn failure(json: &str) -> Error {
+        let output: rpc::Output = serde_json::from_str(json).unwrap();
+        helpers::to_result_from_output(output).unwrap_err()
+    }
+
+    #[test]
+    fn should_map_failures_to_variants() {
+        let revert = failure(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":3,"message":"execution reverted: nope","data":"0x08c379a0"}}"#,
+        );
+        match revert {
+            Error::Rpc(ref e) => assert_eq!(e.message, "execution reverted: nope"),
+            ref other => panic!("Expected RPC error, got: {:?}", other),
+        }
+        assert!(revert.is_revert());
+        assert!(!revert.is_retryable());
+
+        let parity_revert = failure(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32015,"message":"VM execution error.","data":"Reverted 0x"}}"#,
+        );
+        assert!(parity_revert.is_revert());
+
+        let not_reverts = [
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":3,"message":"invalid opcode"}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"execution reverted by gas cap"}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"invalid","data":"Reverted 0x"}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32015,"message":"VM execution error.","data":"Reverted"}}"#,
+        ];
+        for json in &not_reverts {
+            assert!(!failure(json).is_revert(), "{}", json);
+        }
+
+        let limited = failure(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"limit exceeded"}}"#);
+        assert_eq!(
+            limited,
+            Error::Rpc(rpc::Error {
+                code: rpc::ErrorCode::ServerError(-32005),
+                message: "limit exceeded".into(),
+                data: None,
+            })
+        );
+        assert!(limited.is_retryable());
+
+        let invalid = failure(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"invalid params"}}"#);
+        assert_eq!(invalid, Error::Rpc(rpc::Error::invalid_params("invalid params")));
+        assert!(!invalid.is_retryable());
+    }
+
+    #[test]
+    fn should_classify_retryable_errors() {
+        assert!(Error::Transport("connection refused".into()).is_retryable());
+        assert!(Error::Io(io::ErrorKind::TimedOut.into()).is_retryable());
+        assert!(Error::Unreachable.is_retryable());
+        assert!(!Error::Decoder("invalid hex".into()).is_retryable());
+        assert!(!Error::InvalidResponse("Expected single, got batch.".into()).is_retryable());
+        assert!(!Error::Internal.is_retryable());
+        assert!(!Error::Rpc(rpc::Error::internal_error()).is_retryable());
+    }
+}
@@ -1,11 +1,16 @@
 //! Web3 helpers.
 
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
+use std::sync::OnceLock;
+use std::time::Duration;
 
 use crate::rpc;
+use crate::RequestId;
 use futures::{Async, Future, Poll};
 use serde;
 use serde_json;
+use tokio_timer::Timer;
 
 use crate::error::Error;
 
@@ -64,9 +69,44 @@ pub fn build_request(id: usize, method: &str, params: Vec<rpc::Value>) -> rpc::C
     })
 }
 
+/// Longest delay accepted by the shared timer.
+const MAX_TIMEOUT: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Returns a handle to the timer shared by the crate.
+///
+/// Every `Timer` runs its own thread and by default rejects delays over 409.6 seconds,
+/// while the shared one accepts delays of up to a year.
+pub(crate) fn timer() -> Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    TIMER
+        .get_or_init(|| tokio_timer::wheel().max_timeout(MAX_TIMEOUT).build())
+        .clone()
+}
+
 /// Parse bytes slice into JSON-RPC response.
 pub fn to_response_from_slice(response: &[u8]) -> Result<rpc::Response, Error> {
-    serde_json::from_slice(response).map_err(|e| Error::InvalidResponse(format!("{:?}", e)))
+    serde_json::from_slice(response).map_err(|e| invalid_response(response, e))
+}
+
+/// Some providers respond with neither `result` nor `error`, which doesn't match any
+/// output variant. Report that explicitly instead of the generic deserialization error.
+fn invalid_response(response: &[u8], err: serde_json::Error) -> Error {
+    let outputs = match serde_json::from_slice(response) {
+        Ok(rpc::Value::Array(outputs)) => outputs,
+        Ok(output) => vec![output],
+        Err(_) => vec![],
+    };
+
+    for output in outputs {
+        if let rpc::Value::Object(ref output) = output {
+            if !output.contains_key("result") && !output.contains_key("error") {
+                let id = output.get("id").cloned().unwrap_or(rpc::Value::Null);
+                return Error::InvalidResponse(format!("Response has neither result nor error (id: {})", id));
+            }
+        }
+    }
+
+    Error::InvalidResponse(format!("{:?}", err))
 }
 
 /// Parse bytes slice into JSON-RPC notification.
@@ -79,6 +119,47 @@ pub fn to_results_from_outputs(outputs: Vec<rpc::Output>) -> Result<Vec<Result<r
     Ok(outputs.into_iter().map(to_result_from_output).collect())
 }
 
+/// Parse a Vec of `rpc::Output` into `Result`, ordered the same way as requests with given `ids`.
+/// Servers are free to respond to a batch in any order, so outputs are matched by id.
+pub fn to_results_from_outputs_by_id(
+    ids: &[RequestId],
+    outputs: Vec<rpc::Output>,
+) -> Result<Vec<Result<rpc::Value, Error>>, Error> {
+    let mut outputs = outputs
+        .into_iter()
+        .filter_map(|output| match *output.id() {
+            rpc::Id::Num(num) => Some((num as RequestId, output)),
+            _ => None,
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    Ok(ids
+        .iter()
+        .map(|id| match outputs.remove(id) {
+            Some(output) => to_result_from_output(output),
+            None => Err(Error::InvalidResponse(format!(
+                "Missing response for request (id: {})",
+                id
+            ))),
+        })
+        .collect())
+}
+
+/// Returns id of the pending request the outputs are a response to.
+/// A batch is registered under the id of its first request, which might not be the first output.
+pub fn pending_request_id<V>(pending: &BTreeMap<RequestId, V>, outputs: &[rpc::Output]) -> rpc::Id {
+    outputs
+        .iter()
+        .map(|output| output.id())
+        .find(|id| match **id {
+            rpc::Id::Num(num) => pending.contains_key(&(num as RequestId)),
+            _ => false,
+        })
+        .or_else(|| outputs.first().map(|output| output.id()))
+        .cloned()
+        .unwrap_or(rpc::Id::Num(0))
+}
+
 /// Parse `rpc::Output` into `Result`.
 pub fn to_result_from_output(output: rpc::Output) -> Result<rpc::Value, Error> {
     match output {
@@ -90,9 +171,10 @@ pub fn to_result_from_output(output: rpc::Output) -> Result<rpc::Value, Error> {
 #[macro_use]
 #[cfg(test)]
 pub mod tests {
+    use super::{to_response_from_slice, to_results_from_outputs_by_id};
     use crate::error::Error;
     use crate::rpc;
-    use crate::{RequestId, Result, Transport};
+    use crate::{BatchTransport, RequestId, Result, Transport};
     use futures;
     use serde_json;
     use std::cell::RefCell;
@@ -126,6 +208,28 @@ pub mod tests {
         }
     }
 
+    impl BatchTransport for TestTransport {
+        type Batch = Result<Vec<::std::result::Result<rpc::Value, Error>>>;
+
+        fn send_batch<T>(&self, requests: T) -> Self::Batch
+        where
+            T: IntoIterator<Item = (RequestId, rpc::Call)>,
+        {
+            let mut responses = self.responses.borrow_mut();
+            let results = requests
+                .into_iter()
+                .map(|(id, request)| match responses.pop_front() {
+                    Some(response) => Ok(response),
+                    None => {
+                        println!("Unexpected request (id: {:?}): {:?}", id, request);
+                        Err(Error::Unreachable)
+                    }
+                })
+                .collect();
+            Box::new(futures::finished(results))
+        }
+    }
+
     impl TestTransport {
         pub fn set_response(&mut self, value: rpc::Value) {
             *self.responses.borrow_mut() = vec![value].into();
@@ -156,6 +260,53 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn should_reject_response_without_result_and_error() {
+        assert_eq!(
+            to_response_from_slice(br#"{"jsonrpc":"2.0","id":1}"#).err(),
+            Some(Error::InvalidResponse(
+                "Response has neither result nor error (id: 1)".into()
+            ))
+        );
+        assert_eq!(
+            to_response_from_slice(br#"[{"jsonrpc":"2.0","id":1,"result":"0x1"},{"jsonrpc":"2.0","id":2}]"#).err(),
+            Some(Error::InvalidResponse(
+                "Response has neither result nor error (id: 2)".into()
+            ))
+        );
+        assert!(to_response_from_slice(br#"{"jsonrpc":"2.0","id":1,"result":null}"#).is_ok());
+    }
+
+    #[test]
+    fn should_match_batch_outputs_by_id() {
+        // given
+        let outputs: Vec<rpc::Output> = serde_json::from_str(
+            r#"[
+              {"jsonrpc":"2.0","id":3,"result":"0x3"},
+              {"jsonrpc":"2.0","id":1,"result":"0x1"},
+              {"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"failed"}}
+            ]"#,
+        )
+        .unwrap();
+
+        // when
+        let results = to_results_from_outputs_by_id(&[1, 2, 3, 4], outputs).unwrap();
+
+        // then
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0], Ok(rpc::Value::String("0x1".into())));
+        assert_eq!(
+            results[1],
+            Err(Error::Rpc(rpc::Error {
+                code: rpc::ErrorCode::ServerError(-32000),
+                message: "failed".into(),
+                data: None,
+            }))
+        );
+        assert_eq!(results[2], Ok(rpc::Value::String("0x3".into())));
+        assert!(results[3].is_err());
+    }
+
     macro_rules! rpc_test {
     // With parameters
     (
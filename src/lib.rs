@@ -23,6 +23,7 @@ pub mod helpers;
 pub mod api;
 pub mod contract;
 pub mod error;
+pub mod signing;
 pub mod transports;
 pub mod types;
 
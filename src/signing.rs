@@ -0,0 +1,271 @@
+//! Signing and hashing primitives.
+//!
+//! All hashing done by the crate goes through this module, so that the
+//! Keccak implementation can be swapped in a single place.
+
+use crate::types::{Address, Recovery, H256, H520, U256};
+use ethabi::Token;
+use rustc_hex::ToHex;
+use secp256k1::{Message, PublicKey, Secp256k1};
+use tiny_keccak::{Hasher, Keccak};
+
+pub mod eip712;
+
+/// Compute the Keccak-256 hash of input bytes.
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(bytes);
+    hasher.finalize(&mut output);
+    output
+}
+
+/// Hash a message according to EIP-191.
+///
+/// The data is enveloped as `"\x19Ethereum Signed Message:\n" + message.length + message`
+/// and hashed using keccak256.
+pub fn hash_message(message: &[u8]) -> H256 {
+    let mut eth_message = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    eth_message.extend_from_slice(message);
+
+    keccak256(&eth_message).into()
+}
+
+/// Checks whether `signature` of an EIP-191 `message` was created by `expected`.
+///
+/// The signature is expected in 'Electrum' notation, as returned by `Accounts::sign`
+/// and `Personal::sign`. Signatures with an invalid recovery value never verify.
+pub fn verify_signature(message: &[u8], signature: &H520, expected: Address) -> bool {
+    let message_hash = hash_message(message);
+    let recovery = Recovery::from_raw_signature(message_hash, signature).expect("H520 is 65 bytes long; qed");
+    let signature = match recovery.as_signature() {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let message = Message::from_slice(message_hash.as_bytes()).expect("hash is 32 bytes; qed");
+
+    match Secp256k1::verification_only().recover(&message, &signature) {
+        Ok(public_key) => public_key_address(&public_key) == expected,
+        Err(_) => false,
+    }
+}
+
+/// Computes the EIP-712 domain separator of a contract with an
+/// `EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)` domain.
+pub fn domain_separator(name: &str, version: &str, chain_id: U256, verifying_contract: Address) -> H256 {
+    let type_hash = keccak256(b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)");
+    keccak256(&ethabi::encode(&[
+        Token::FixedBytes(type_hash.to_vec()),
+        Token::FixedBytes(keccak256(name.as_bytes()).to_vec()),
+        Token::FixedBytes(keccak256(version.as_bytes()).to_vec()),
+        Token::Uint(chain_id),
+        Token::Address(verifying_contract),
+    ]))
+    .into()
+}
+
+/// Computes the EIP-712 digest to sign, given the domain separator and the hash of the signed struct.
+pub fn typed_data_hash(domain_separator: H256, struct_hash: H256) -> H256 {
+    let mut message = Vec::with_capacity(66);
+    message.extend_from_slice(b"\x19\x01");
+    message.extend_from_slice(domain_separator.as_bytes());
+    message.extend_from_slice(struct_hash.as_bytes());
+
+    keccak256(&message).into()
+}
+
+/// Parameters of an EIP-2612 `permit` approval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Permit {
+    /// Owner of the tokens
+    pub owner: Address,
+    /// Address allowed to spend the tokens
+    pub spender: Address,
+    /// Allowed amount
+    pub value: U256,
+    /// Current permit nonce of the owner (`nonces(owner)`)
+    pub nonce: U256,
+    /// Timestamp after which the permit is no longer valid
+    pub deadline: U256,
+}
+
+impl Permit {
+    /// Returns the EIP-712 hash of the `Permit` struct.
+    pub fn struct_hash(&self) -> H256 {
+        let type_hash =
+            keccak256(b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)");
+        keccak256(&ethabi::encode(&[
+            Token::FixedBytes(type_hash.to_vec()),
+            Token::Address(self.owner),
+            Token::Address(self.spender),
+            Token::Uint(self.value),
+            Token::Uint(self.nonce),
+            Token::Uint(self.deadline),
+        ]))
+        .into()
+    }
+}
+
+/// Computes the digest the owner signs to approve `permit` on the token
+/// deployed at `verifying_contract`.
+///
+/// `name` and `version` must match the token's EIP-712 domain.
+pub fn permit_digest(name: &str, version: &str, chain_id: U256, verifying_contract: Address, permit: &Permit) -> H256 {
+    typed_data_hash(
+        domain_separator(name, version, chain_id, verifying_contract),
+        permit.struct_hash(),
+    )
+}
+
+/// Formats `address` with the mixed-case checksum defined by EIP-55.
+pub fn to_checksum(address: &Address) -> String {
+    let lowercase = address.as_bytes().to_hex::<String>();
+    let hash = keccak256(lowercase.as_bytes());
+
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (i, c) in lowercase.chars().enumerate() {
+        let byte = hash[i / 2];
+        let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0xf };
+        if nibble >= 8 {
+            checksummed.push(c.to_ascii_uppercase());
+        } else {
+            checksummed.push(c);
+        }
+    }
+    checksummed
+}
+
+/// Gets the address of a public key.
+///
+/// The public address is defined as the low 20 bytes of the keccak hash of
+/// the public key. Note that the public key returned from the `secp256k1`
+/// crate is 65 bytes long, that is because it is prefixed by `0x04` to
+/// indicate an uncompressed public key; this first byte is ignored when
+/// computing the hash.
+pub(crate) fn public_key_address(public_key: &PublicKey) -> Address {
+    let public_key = public_key.serialize_uncompressed();
+
+    debug_assert_eq!(public_key[0], 0x04);
+    let hash = keccak256(&public_key[1..]);
+
+    Address::from_slice(&hash[12..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{domain_separator, keccak256, permit_digest, to_checksum, typed_data_hash, verify_signature, Permit};
+    use crate::types::{Address, H256, H520, U256};
+    use rustc_hex::FromHex;
+
+    // test vector taken from:
+    // https://web3js.readthedocs.io/en/v1.2.2/web3-eth-accounts.html#sign
+    const SIGNATURE: &str = "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+
+    #[test]
+    fn should_hash_known_vectors() {
+        let vectors: &[(&[u8], &str)] = &[
+            (b"", "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+            (
+                b"hello",
+                "1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8",
+            ),
+            (
+                b"transfer(address,uint256)",
+                "a9059cbb2ab09eb219583f4a59a5d0623ade346d962bcd4e46b11da047c9049b",
+            ),
+        ];
+
+        for &(input, expected) in vectors {
+            assert_eq!(keccak256(input).to_vec(), expected.from_hex::<Vec<u8>>().unwrap());
+        }
+    }
+
+    #[test]
+    fn should_verify_signature_of_matching_address() {
+        let signer: Address = "2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse().unwrap();
+        let signature: H520 = SIGNATURE.parse().unwrap();
+
+        assert!(verify_signature(b"Some data", &signature, signer));
+    }
+
+    #[test]
+    fn should_not_verify_signature_of_other_address() {
+        let signer: Address = "2c7536E3605D9C16a7a3D7b1898e529396a65c23".parse().unwrap();
+        let signature: H520 = SIGNATURE.parse().unwrap();
+        let mut invalid_recovery = signature;
+        invalid_recovery.as_bytes_mut()[64] = 0;
+
+        assert!(!verify_signature(b"Some data", &signature, Address::from_low_u64_be(1)));
+        assert!(!verify_signature(b"Other data", &signature, signer));
+        assert!(!verify_signature(b"Some data", &invalid_recovery, signer));
+    }
+
+    #[test]
+    fn should_compute_typed_data_hash() {
+        // example from EIP-712:
+        // https://github.com/ethereum/EIPs/blob/master/assets/eip-712/Example.js
+        let domain_separator = domain_separator(
+            "Ether Mail",
+            "1",
+            1.into(),
+            "CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC".parse().unwrap(),
+        );
+        let mail_hash: H256 = "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            domain_separator,
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            typed_data_hash(domain_separator, mail_hash),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_compute_permit_digest() {
+        let usdc: Address = "A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".parse().unwrap();
+        let permit = Permit {
+            owner: Address::from_low_u64_be(1),
+            spender: Address::from_low_u64_be(2),
+            value: 1_000_000.into(),
+            nonce: 0.into(),
+            deadline: U256::max_value(),
+        };
+
+        assert_eq!(
+            domain_separator("USD Coin", "2", 1.into(), usdc),
+            "06c37168a7db5138defc7866392bb87a741f9b3d104deb5094588ce041cae335"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(
+            permit_digest("USD Coin", "2", 1.into(), usdc, &permit),
+            "c5b92557af2f8f8379f90bc4eb5f3d40a0ad621371acd7bfd8668d814a9bc434"
+                .parse()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_checksum_addresses() {
+        // test vectors taken from EIP-55
+        let vectors = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for expected in &vectors {
+            let address: Address = expected[2..].to_lowercase().parse().unwrap();
+            assert_eq!(to_checksum(&address), *expected);
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! EIP-712 typed structured data hashing.
+//!
+//! Typed data is given in the JSON format accepted by `eth_signTypedData_v4`.
+
+use crate::error::Error;
+use crate::signing::{keccak256, typed_data_hash};
+use crate::types::{Address, H256, U256};
+use rustc_hex::FromHex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Name of the domain struct type.
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// Member of a struct type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberType {
+    /// Name of the member
+    pub name: String,
+    /// Type of the member, e.g. `address`, `uint256[]` or the name of another struct type
+    #[serde(rename = "type")]
+    pub member_type: String,
+}
+
+/// Typed structured data to hash and sign (EIP-712).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypedData {
+    /// Struct types by name, including the `EIP712Domain` type
+    pub types: BTreeMap<String, Vec<MemberType>>,
+    /// Type of the message
+    #[serde(rename = "primaryType")]
+    pub primary_type: String,
+    /// Values of the `EIP712Domain` members
+    pub domain: Value,
+    /// Values of the primary type members
+    pub message: Value,
+}
+
+impl TypedData {
+    /// Returns the digest to sign, `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+    pub fn hash(&self) -> Result<H256, Error> {
+        let domain_separator = self.hash_struct(DOMAIN_TYPE, &self.domain)?;
+        let message_hash = self.hash_struct(&self.primary_type, &self.message)?;
+        Ok(typed_data_hash(domain_separator, message_hash))
+    }
+
+    /// Returns the hash of a struct of type `name` with given member values.
+    pub fn hash_struct(&self, name: &str, value: &Value) -> Result<H256, Error> {
+        let members = self.members(name)?;
+        let mut encoded = Vec::with_capacity(32 * (members.len() + 1));
+        encoded.extend_from_slice(self.type_hash(name)?.as_bytes());
+        for member in members {
+            let value = value
+                .get(&member.name)
+                .ok_or_else(|| invalid(format!("Missing value of {}.{}", name, member.name)))?;
+            encoded.extend_from_slice(&self.encode_value(&member.member_type, value)?);
+        }
+        Ok(keccak256(&encoded).into())
+    }
+
+    /// Returns the hash of the encoded type `name`.
+    pub fn type_hash(&self, name: &str) -> Result<H256, Error> {
+        Ok(keccak256(self.encode_type(name)?.as_bytes()).into())
+    }
+
+    /// Encodes type `name` followed by all struct types it references, sorted by name,
+    /// e.g. `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    pub fn encode_type(&self, name: &str) -> Result<String, Error> {
+        let mut dependencies = BTreeSet::new();
+        self.collect_dependencies(name, &mut dependencies)?;
+        dependencies.remove(name);
+
+        let mut encoded = String::new();
+        for name in Some(name).into_iter().chain(dependencies.iter().cloned()) {
+            let members = self
+                .members(name)?
+                .iter()
+                .map(|member| format!("{} {}", member.member_type, member.name))
+                .collect::<Vec<_>>();
+            encoded.push_str(&format!("{}({})", name, members.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    fn members(&self, name: &str) -> Result<&[MemberType], Error> {
+        self.types
+            .get(name)
+            .map(|members| members.as_slice())
+            .ok_or_else(|| invalid(format!("Unknown type: {}", name)))
+    }
+
+    fn collect_dependencies<'a>(&'a self, name: &'a str, dependencies: &mut BTreeSet<&'a str>) -> Result<(), Error> {
+        if !dependencies.insert(name) {
+            return Ok(());
+        }
+        for member in self.members(name)? {
+            let member_type = element_type(&member.member_type);
+            if self.types.contains_key(member_type) {
+                self.collect_dependencies(member_type, dependencies)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes a member value as a 32-byte word.
+    fn encode_value(&self, member_type: &str, value: &Value) -> Result<[u8; 32], Error> {
+        if member_type.ends_with(']') {
+            let open = member_type
+                .rfind('[')
+                .ok_or_else(|| invalid(format!("Unknown type: {}", member_type)))?;
+            let item_type = &member_type[..open];
+            let length = match &member_type[open + 1..member_type.len() - 1] {
+                "" => None,
+                length => Some(parse_size(length).ok_or_else(|| invalid(format!("Unknown type: {}", member_type)))?),
+            };
+            let items = value
+                .as_array()
+                .ok_or_else(|| invalid(format!("Expected array of {}, got: {}", item_type, value)))?;
+            if length.is_some() && length != Some(items.len()) {
+                return Err(invalid(format!("Expected {}, got: {}", member_type, value)));
+            }
+            let mut encoded = Vec::with_capacity(32 * items.len());
+            for item in items {
+                encoded.extend_from_slice(&self.encode_value(item_type, item)?);
+            }
+            return Ok(keccak256(&encoded));
+        }
+        if self.types.contains_key(member_type) {
+            return Ok(self.hash_struct(member_type, value)?.to_fixed_bytes());
+        }
+
+        let mut word = [0u8; 32];
+        match member_type {
+            "string" => {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| invalid(format!("Expected string, got: {}", value)))?;
+                word = keccak256(value.as_bytes());
+            }
+            "bytes" => word = keccak256(&parse_bytes(value)?),
+            "bool" => {
+                let value = value
+                    .as_bool()
+                    .ok_or_else(|| invalid(format!("Expected bool, got: {}", value)))?;
+                word[31] = value as u8;
+            }
+            "address" => {
+                let address = value
+                    .as_str()
+                    .and_then(|value| value.trim_start_matches("0x").parse::<Address>().ok())
+                    .ok_or_else(|| invalid(format!("Expected address, got: {}", value)))?;
+                word[12..].copy_from_slice(address.as_bytes());
+            }
+            _ => {
+                let bytes_size = member_type.strip_prefix("bytes").and_then(parse_size);
+                let uint_bits = member_type.strip_prefix("uint").and_then(parse_size);
+                let int_bits = member_type.strip_prefix("int").and_then(parse_size);
+                match (bytes_size, uint_bits, int_bits) {
+                    (Some(size @ 1..=32), _, _) => {
+                        let bytes = parse_bytes(value)?;
+                        if bytes.len() != size {
+                            return Err(invalid(format!("Expected {}, got: {}", member_type, value)));
+                        }
+                        word[..size].copy_from_slice(&bytes);
+                    }
+                    (_, Some(bits), _) if is_integer_width(bits) => {
+                        parse_integer(member_type, value, bits, false)?.to_big_endian(&mut word)
+                    }
+                    (_, _, Some(bits)) if is_integer_width(bits) => {
+                        parse_integer(member_type, value, bits, true)?.to_big_endian(&mut word)
+                    }
+                    _ => return Err(invalid(format!("Unknown type: {}", member_type))),
+                }
+            }
+        }
+        Ok(word)
+    }
+}
+
+/// Strips array suffixes from a type, e.g. `Person[][2]` becomes `Person`.
+fn element_type(member_type: &str) -> &str {
+    member_type.split('[').next().unwrap_or(member_type)
+}
+
+fn parse_bytes(value: &Value) -> Result<Vec<u8>, Error> {
+    value
+        .as_str()
+        .and_then(|value| value.trim_start_matches("0x").from_hex().ok())
+        .ok_or_else(|| invalid(format!("Expected hex bytes, got: {}", value)))
+}
+
+/// Parses the decimal size of a type, e.g. `8` of `uint8` or `2` of `Person[2]`.
+/// Only the canonical form without sign or leading zeros is accepted.
+fn parse_size(size: &str) -> Option<usize> {
+    if size.starts_with('0') || !size.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    size.parse().ok()
+}
+
+/// Returns `true` for the width of a valid `uintN` or `intN` type.
+fn is_integer_width(bits: usize) -> bool {
+    (8..=256).contains(&bits) && bits.is_multiple_of(8)
+}
+
+/// Parses a JSON number or a decimal or `0x`-prefixed hex string that fits an integer
+/// type `bits` wide. Negative values (allowed if `signed`) are encoded in two's complement.
+fn parse_integer(member_type: &str, value: &Value, bits: usize, signed: bool) -> Result<U256, Error> {
+    let (negative, magnitude) = match *value {
+        Value::Number(ref number) => match (number.as_u64(), number.as_i64()) {
+            (Some(number), _) => (false, Some(U256::from(number))),
+            (None, Some(number)) => (true, Some(U256::from(number.unsigned_abs()))),
+            (None, None) => (false, None),
+        },
+        Value::String(ref number) => {
+            let negative = number.starts_with('-');
+            let number = number.trim_start_matches('-');
+            let magnitude = match number.strip_prefix("0x") {
+                Some(hex) => hex.parse::<U256>().ok(),
+                None => U256::from_dec_str(number).ok(),
+            };
+            (negative, magnitude)
+        }
+        _ => (false, None),
+    };
+
+    let magnitude = magnitude.ok_or_else(|| invalid(format!("Expected integer, got: {}", value)))?;
+    // unsigned values are below 2^bits, signed ones in -2^(bits-1)..2^(bits-1)
+    let fits = match (signed, negative) {
+        (false, false) => magnitude.bits() <= bits,
+        (false, true) => magnitude.is_zero(),
+        (true, false) => magnitude.bits() < bits,
+        (true, true) => magnitude.bits() < bits || magnitude == U256::one() << (bits - 1),
+    };
+    if !fits {
+        return Err(invalid(format!("Expected {}, got: {}", member_type, value)));
+    }
+
+    if negative {
+        Ok((!magnitude).overflowing_add(U256::one()).0)
+    } else {
+        Ok(magnitude)
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::Decoder(format!("Invalid typed data: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypedData;
+    use crate::types::{H256, U256};
+    use serde_json::json;
+
+    // example from EIP-712:
+    // https://github.com/ethereum/EIPs/blob/master/assets/eip-712/Example.js
+    fn mail() -> TypedData {
+        serde_json::from_value(json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": {
+                    "name": "Cow",
+                    "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+                },
+                "to": {
+                    "name": "Bob",
+                    "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+                },
+                "contents": "Hello, Bob!"
+            }
+        }))
+        .unwrap()
+    }
+
+    fn h256(hex: &str) -> H256 {
+        hex.parse().unwrap()
+    }
+
+    #[test]
+    fn should_hash_canonical_example() {
+        let mail = mail();
+
+        assert_eq!(
+            mail.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            mail.type_hash("Mail").unwrap(),
+            h256("a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2")
+        );
+        assert_eq!(
+            mail.hash_struct("Mail", &mail.message).unwrap(),
+            h256("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+        );
+        assert_eq!(
+            mail.hash_struct("EIP712Domain", &mail.domain).unwrap(),
+            h256("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+        );
+        assert_eq!(
+            mail.hash().unwrap(),
+            h256("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+    }
+
+    #[test]
+    fn should_encode_arrays_of_nested_structs() {
+        let mut group = mail();
+        group.types.insert(
+            "Group".into(),
+            serde_json::from_value(json!([
+                { "name": "members", "type": "Person[]" },
+                { "name": "mail", "type": "Mail" }
+            ]))
+            .unwrap(),
+        );
+        let members = json!([group.message["from"], group.message["to"]]);
+        let value = json!({ "members": members, "mail": group.message });
+
+        let person_hashes = [&group.message["from"], &group.message["to"]]
+            .iter()
+            .flat_map(|person| group.hash_struct("Person", person).unwrap().to_fixed_bytes().to_vec())
+            .collect::<Vec<_>>();
+        let mut expected = group.type_hash("Group").unwrap().as_bytes().to_vec();
+        expected.extend_from_slice(&crate::signing::keccak256(&person_hashes));
+        expected.extend_from_slice(group.hash_struct("Mail", &group.message).unwrap().as_bytes());
+
+        assert_eq!(
+            group.encode_type("Group").unwrap(),
+            "Group(Person[] members,Mail mail)Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            group.hash_struct("Group", &value).unwrap(),
+            crate::signing::keccak256(&expected).into()
+        );
+    }
+
+    #[test]
+    fn should_encode_atomic_values_as_words() {
+        let data = mail();
+        let encode = |member_type: &str, value| data.encode_value(member_type, &value).unwrap();
+        let word = |value: U256| {
+            let mut word = [0u8; 32];
+            value.to_big_endian(&mut word);
+            word
+        };
+
+        assert_eq!(encode("uint8", json!(255)), word(255.into()));
+        assert_eq!(encode("uint256", json!("0x100")), word(256.into()));
+        assert_eq!(encode("uint256", json!("1000000000000000000")), word(U256::exp10(18)));
+        assert_eq!(encode("int256", json!(-1)), [0xff; 32]);
+        assert_eq!(encode("int64", json!("-2")), word(U256::max_value() - 1));
+        assert_eq!(encode("bool", json!(true)), word(1.into()));
+        let mut bytes4 = [0u8; 32];
+        bytes4[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(encode("bytes4", json!("0xdeadbeef")), bytes4);
+        assert_eq!(
+            encode("bytes", json!("0xdeadbeef")),
+            crate::signing::keccak256(&[0xde, 0xad, 0xbe, 0xef])
+        );
+        assert!(data.encode_value("uint256", &json!(-1)).is_err());
+        assert!(data.encode_value("Unknown", &json!({})).is_err());
+    }
+
+    #[test]
+    fn should_encode_values_at_bounds_of_their_type() {
+        let data = mail();
+        let encode = |member_type: &str, value| data.encode_value(member_type, &value).unwrap();
+
+        assert_eq!(encode("int8", json!(-128))[31], 0x80);
+        assert_eq!(encode("int8", json!(127))[31], 0x7f);
+        assert_eq!(encode("int256", json!(format!("-{}", U256::one() << 255))), {
+            let mut word = [0u8; 32];
+            word[0] = 0x80;
+            word
+        });
+        assert_eq!(encode("uint256", json!(format!("{}", U256::max_value()))), [0xff; 32]);
+        assert_eq!(encode("bytes32", json!(format!("0x{}", "ab".repeat(32)))), [0xab; 32]);
+        assert_eq!(
+            encode("uint8[2]", json!([1, 2])),
+            crate::signing::keccak256(&[encode("uint8", json!(1)), encode("uint8", json!(2))].concat())
+        );
+    }
+
+    #[test]
+    fn should_reject_values_out_of_range_of_their_type() {
+        let data = mail();
+        let reject = |member_type: &str, value| data.encode_value(member_type, &value).unwrap_err();
+
+        reject("uint8", json!(300));
+        reject("uint8", json!("0x100"));
+        reject("int8", json!(128));
+        reject("int8", json!(-129));
+        reject("int64", json!(format!("-{}", (U256::one() << 63) + 1)));
+        reject("bytes4", json!(format!("0x{}", "ab".repeat(32))));
+        reject("bytes4", json!("0xdead"));
+        reject("uint8[2]", json!([1, 2, 3]));
+        reject("uint8[2]", json!([1]));
+    }
+
+    #[test]
+    fn should_reject_invalid_types() {
+        let data = mail();
+
+        for member_type in &[
+            "bytes0",
+            "bytes33",
+            "uint",
+            "uint0",
+            "uint7",
+            "uint264",
+            "int",
+            "int9",
+            "uint08",
+            "uint8[x]",
+            "uint8[02]",
+        ] {
+            assert!(
+                data.encode_value(member_type, &json!(1)).is_err(),
+                "{} should be rejected",
+                member_type
+            );
+        }
+    }
+}
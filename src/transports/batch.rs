@@ -145,3 +145,62 @@ impl Future for SingleResult {
         res.map(futures::Async::Ready)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Batch;
+    use crate::api::{Eth, Namespace};
+    use crate::helpers::tests::TestTransport;
+    use crate::types::{Address, U256};
+    use crate::{Error, Web3};
+    use futures::Future;
+    use serde_json::json;
+
+    #[test]
+    fn should_fail_single_call_without_failing_batch() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(json!("0x1"));
+        let batch = Batch::new(transport.clone());
+        let eth = Eth::new(&batch);
+
+        // when
+        let block_number = eth.block_number();
+        let gas_price = eth.gas_price();
+        let results = batch.submit_batch().wait().unwrap();
+
+        // then
+        assert_eq!(results, vec![Ok(json!("0x1")), Err(Error::Unreachable)]);
+        assert_eq!(block_number.wait(), Ok(1.into()));
+        assert_eq!(gas_price.wait(), Err::<U256, _>(Error::Unreachable));
+        transport.assert_request("eth_blockNumber", &[]);
+        transport.assert_request("eth_gasPrice", &[]);
+        transport.assert_no_more_requests();
+    }
+
+    #[test]
+    fn should_submit_queued_calls_of_batch_client() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(json!("0x1"));
+        transport.add_response(json!("0x2"));
+        let web3 = Web3::new(transport.clone());
+        let batch = web3.batch();
+
+        // when
+        let first = batch.eth().balance(Address::from_low_u64_be(0x123), None);
+        let second = batch.eth().balance(Address::from_low_u64_be(0x456), None);
+        let results = batch.submit().wait().unwrap();
+
+        // then
+        assert_eq!(results, vec![Ok(json!("0x1")), Ok(json!("0x2"))]);
+        assert_eq!(first.wait(), Ok(1.into()));
+        assert_eq!(second.wait(), Ok(2.into()));
+        let latest = r#""latest""#.to_owned();
+        let first_address = r#""0x0000000000000000000000000000000000000123""#.to_owned();
+        let second_address = r#""0x0000000000000000000000000000000000000456""#.to_owned();
+        transport.assert_request("eth_getBalance", &[first_address, latest.clone()]);
+        transport.assert_request("eth_getBalance", &[second_address, latest]);
+        transport.assert_no_more_requests();
+    }
+}
@@ -0,0 +1,124 @@
+//! Checksummed Addresses Transport
+
+use crate::rpc;
+use crate::signing::to_checksum;
+use crate::types::Address;
+use crate::{BatchTransport, RequestId, Transport};
+
+/// Transport sending addresses in request params with the EIP-55 checksum.
+///
+/// Requests are serialized with lowercase hex addresses by default, which some strict
+/// endpoints reject. This wrapper rewrites every 20-byte hex string of the params
+/// (including nested ones) to its mixed-case form. Hex is case-insensitive, so
+/// non-address values of the same length are unaffected.
+#[derive(Debug, Clone)]
+pub struct Checksummed<T> {
+    transport: T,
+}
+
+impl<T: Transport> Checksummed<T> {
+    /// Wraps existing transport, checksumming addresses of every request.
+    pub fn new(transport: T) -> Self {
+        Checksummed { transport }
+    }
+
+    /// Returns the underlying transport.
+    pub fn inner(&self) -> &T {
+        &self.transport
+    }
+}
+
+impl<T: Transport> Transport for Checksummed<T> {
+    type Out = T::Out;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        let params = params.into_iter().map(checksum).collect();
+        self.transport.prepare(method, params)
+    }
+
+    fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+        self.transport.send(id, request)
+    }
+}
+
+impl<T: BatchTransport> BatchTransport for Checksummed<T> {
+    type Batch = T::Batch;
+
+    fn send_batch<I>(&self, requests: I) -> Self::Batch
+    where
+        I: IntoIterator<Item = (RequestId, rpc::Call)>,
+    {
+        self.transport.send_batch(requests)
+    }
+}
+
+fn checksum(value: rpc::Value) -> rpc::Value {
+    match value {
+        rpc::Value::String(ref s) if s.len() == 42 && s.starts_with("0x") => match s[2..].parse::<Address>() {
+            Ok(address) => rpc::Value::String(to_checksum(&address)),
+            Err(_) => value,
+        },
+        rpc::Value::Array(values) => rpc::Value::Array(values.into_iter().map(checksum).collect()),
+        rpc::Value::Object(fields) => {
+            rpc::Value::Object(fields.into_iter().map(|(key, value)| (key, checksum(value))).collect())
+        }
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Checksummed;
+    use crate::api::{Eth, Namespace};
+    use crate::helpers::tests::TestTransport;
+    use crate::types::{Address, CallRequest, H256};
+    use futures::Future;
+    use serde_json::json;
+
+    #[test]
+    fn should_serialize_lowercase_or_checksummed_addresses() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(json!("0x1"));
+        transport.add_response(json!("0x1"));
+        transport.add_response(json!("0x"));
+        let address: Address = "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap();
+        let checksummed = Checksummed::new(&transport);
+
+        // when
+        let lowercase = Eth::new(&transport).balance(address, None).wait();
+        let mixed_case = Eth::new(&checksummed).balance(address, None).wait();
+        let request = CallRequest {
+            from: Some(address),
+            to: address,
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(H256::from_low_u64_be(0xab).as_bytes().to_vec().into()),
+            access_list: None,
+        };
+        let call = Eth::new(&checksummed).call(request, None).wait();
+
+        // then
+        assert_eq!(lowercase, Ok(1.into()));
+        assert_eq!(mixed_case, Ok(1.into()));
+        assert!(call.is_ok());
+        let latest = r#""latest""#.to_owned();
+        transport.assert_request(
+            "eth_getBalance",
+            &[r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed""#.into(), latest.clone()],
+        );
+        transport.assert_request(
+            "eth_getBalance",
+            &[r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed""#.into(), latest.clone()],
+        );
+        transport.assert_request(
+            "eth_call",
+            &[
+                r#"{"data":"0x00000000000000000000000000000000000000000000000000000000000000ab","from":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","to":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"}"#.into(),
+                latest,
+            ],
+        );
+        transport.assert_no_more_requests();
+    }
+}
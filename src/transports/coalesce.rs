@@ -0,0 +1,308 @@
+//! Request Coalescing Transport
+
+use crate::rpc;
+use crate::{Error, RequestId, Transport};
+use futures::future::Shared;
+use futures::{Async, Future, Poll};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+
+/// Read-only methods whose identical concurrent calls can share a single response.
+const IDEMPOTENT_METHODS: &[&str] = &[
+    "eth_blockNumber",
+    "eth_call",
+    "eth_chainId",
+    "eth_estimateGas",
+    "eth_gasPrice",
+    "eth_getBalance",
+    "eth_getBlockByHash",
+    "eth_getBlockByNumber",
+    "eth_getCode",
+    "eth_getLogs",
+    "eth_getStorageAt",
+    "eth_getTransactionByHash",
+    "eth_getTransactionCount",
+    "eth_getTransactionReceipt",
+    "net_version",
+];
+
+type InFlight<F> = Arc<Mutex<HashMap<String, Pending<F>>>>;
+
+/// A request shared by all identical calls made while it's pending.
+struct Pending<F: Future<Item = rpc::Value, Error = Error>> {
+    generation: usize,
+    response: Shared<Completion<F>>,
+    waiters: usize,
+}
+
+/// Removes the request with given `key` and `generation` from the in-flight requests.
+fn forget<F: Future<Item = rpc::Value, Error = Error>>(
+    in_flight: &Mutex<HashMap<String, Pending<F>>>,
+    key: &str,
+    generation: usize,
+) -> Option<Pending<F>> {
+    let mut in_flight = in_flight.lock();
+    match in_flight.get(key) {
+        Some(pending) if pending.generation == generation => in_flight.remove(key),
+        _ => None,
+    }
+}
+
+/// Transport coalescing identical in-flight read requests.
+///
+/// When a call to an idempotent read method (e.g. `eth_call`) is made while an
+/// identical one (same method and params) is still pending, the caller is attached
+/// to the pending request instead of sending a duplicate one.
+/// All other methods are passed through unchanged.
+pub struct Coalesce<T: Transport> {
+    transport: T,
+    in_flight: InFlight<T::Out>,
+    generation: Arc<AtomicUsize>,
+}
+
+impl<T: Transport> Clone for Coalesce<T> {
+    fn clone(&self) -> Self {
+        Coalesce {
+            transport: self.transport.clone(),
+            in_flight: self.in_flight.clone(),
+            generation: self.generation.clone(),
+        }
+    }
+}
+
+impl<T: Transport> fmt::Debug for Coalesce<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Coalesce")
+            .field("transport", &self.transport)
+            .field("in_flight", &self.in_flight.lock().len())
+            .finish()
+    }
+}
+
+impl<T: Transport> Coalesce<T> {
+    /// Wraps existing transport, coalescing identical concurrent reads.
+    pub fn new(transport: T) -> Self {
+        Coalesce {
+            transport,
+            in_flight: Default::default(),
+            generation: Default::default(),
+        }
+    }
+
+    /// Returns the underlying transport.
+    pub fn inner(&self) -> &T {
+        &self.transport
+    }
+}
+
+impl<T: Transport> Transport for Coalesce<T> {
+    type Out = CoalesceFuture<T::Out>;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        self.transport.prepare(method, params)
+    }
+
+    fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+        let key = match request {
+            rpc::Call::MethodCall(ref call) if IDEMPOTENT_METHODS.contains(&call.method.as_str()) => {
+                format!("{}{}", call.method, crate::helpers::to_string(&call.params))
+            }
+            _ => return CoalesceFuture(Inner::Direct(self.transport.send(id, request))),
+        };
+
+        let mut in_flight = self.in_flight.lock();
+        let pending = in_flight.entry(key.clone()).or_insert_with(|| {
+            let generation = self.generation.fetch_add(1, Ordering::Relaxed);
+            let completion = Completion {
+                inner: self.transport.send(id, request),
+                in_flight: Arc::downgrade(&self.in_flight),
+                key: key.clone(),
+                generation,
+            };
+            Pending {
+                generation,
+                response: completion.shared(),
+                waiters: 0,
+            }
+        });
+        pending.waiters += 1;
+
+        CoalesceFuture(Inner::Shared {
+            inner: pending.response.clone(),
+            in_flight: self.in_flight.clone(),
+            key,
+            generation: pending.generation,
+        })
+    }
+}
+
+/// Request future removing itself from the in-flight requests once resolved,
+/// so that subsequent identical calls hit the node again.
+struct Completion<F: Future<Item = rpc::Value, Error = Error>> {
+    inner: F,
+    in_flight: Weak<Mutex<HashMap<String, Pending<F>>>>,
+    key: String,
+    generation: usize,
+}
+
+impl<F: Future<Item = rpc::Value, Error = Error>> Future for Completion<F> {
+    type Item = rpc::Value;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let result = match self.inner.poll() {
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            result => result,
+        };
+        if let Some(in_flight) = self.in_flight.upgrade() {
+            forget(&in_flight, &self.key, self.generation);
+        }
+        result
+    }
+}
+
+enum Inner<F: Future<Item = rpc::Value, Error = Error>> {
+    Direct(F),
+    Shared {
+        inner: Shared<Completion<F>>,
+        in_flight: InFlight<F>,
+        key: String,
+        generation: usize,
+    },
+}
+
+/// A future resolving to the (possibly shared) response of the request.
+pub struct CoalesceFuture<F: Future<Item = rpc::Value, Error = Error>>(Inner<F>);
+
+impl<F: Future<Item = rpc::Value, Error = Error>> fmt::Debug for CoalesceFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Inner::Direct(_) => f.debug_tuple("CoalesceFuture::Direct").finish(),
+            Inner::Shared { ref key, .. } => f.debug_tuple("CoalesceFuture::Shared").field(key).finish(),
+        }
+    }
+}
+
+impl<F: Future<Item = rpc::Value, Error = Error>> Future for CoalesceFuture<F> {
+    type Item = rpc::Value;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.0 {
+            Inner::Direct(ref mut inner) => inner.poll(),
+            Inner::Shared { ref mut inner, .. } => match inner.poll() {
+                Ok(Async::NotReady) => Ok(Async::NotReady),
+                Ok(Async::Ready(value)) => Ok(Async::Ready((*value).clone())),
+                Err(err) => Err((*err).clone()),
+            },
+        }
+    }
+}
+
+impl<F: Future<Item = rpc::Value, Error = Error>> Drop for CoalesceFuture<F> {
+    fn drop(&mut self) {
+        if let Inner::Shared {
+            ref in_flight,
+            ref key,
+            generation,
+            ..
+        } = self.0
+        {
+            // The request is cancelled once the last waiter goes away.
+            let abandoned = {
+                let mut in_flight = in_flight.lock();
+                let waiters = match in_flight.get_mut(key) {
+                    Some(pending) if pending.generation == generation => {
+                        pending.waiters -= 1;
+                        pending.waiters
+                    }
+                    _ => return,
+                };
+                if waiters == 0 {
+                    in_flight.remove(key)
+                } else {
+                    None
+                }
+            };
+            drop(abandoned);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Coalesce;
+    use crate::api::{Eth, Namespace};
+    use crate::helpers::tests::TestTransport;
+    use crate::rpc;
+    use crate::types::{Address, CallRequest};
+    use crate::Error;
+    use futures::Future;
+
+    #[test]
+    fn should_coalesce_identical_concurrent_calls() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(rpc::Value::String("0x010203".into()));
+        let eth = Eth::new(Coalesce::new(transport.clone()));
+        let req = CallRequest {
+            from: None,
+            to: Address::from_low_u64_be(0x123),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: None,
+            access_list: None,
+        };
+
+        // when
+        let first = eth.call(req.clone(), None);
+        let second = eth.call(req, None);
+
+        // then
+        assert_eq!(first.wait(), Ok(vec![1, 2, 3].into()));
+        assert_eq!(second.wait(), Ok(vec![1, 2, 3].into()));
+        // only one response was queued, a second RPC would have failed
+        assert_eq!(eth.block_number().wait(), Err(Error::Unreachable));
+    }
+
+    #[test]
+    fn should_not_coalesce_writes() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(rpc::Value::String(
+            "0x0000000000000000000000000000000000000000000000000000000000000001".into(),
+        ));
+        let eth = Eth::new(Coalesce::new(transport.clone()));
+
+        // when
+        let first = eth.send_raw_transaction(vec![1].into());
+        let second = eth.send_raw_transaction(vec![1].into());
+
+        // then
+        assert!(first.wait().is_ok());
+        assert_eq!(second.wait(), Err(Error::Unreachable));
+    }
+
+    #[test]
+    fn should_send_again_after_all_callers_dropped() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.add_response(rpc::Value::String("0x1".into()));
+        transport.add_response(rpc::Value::String("0x2".into()));
+        let eth = Eth::new(Coalesce::new(transport.clone()));
+
+        // when
+        drop(eth.block_number());
+        let second = eth.block_number();
+
+        // then
+        assert_eq!(second.wait(), Ok(2.into()));
+        transport.assert_request("eth_blockNumber", &[]);
+        transport.assert_request("eth_blockNumber", &[]);
+        transport.assert_no_more_requests();
+    }
+}
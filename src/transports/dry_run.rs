@@ -0,0 +1,120 @@
+//! Dry Run Transport
+
+use crate::helpers;
+use crate::rpc;
+use crate::{BatchTransport, Error, RequestId, Transport};
+use futures::future::{self, FutureResult};
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Transport recording serialized JSON-RPC requests instead of sending them.
+///
+/// Useful to inspect the exact payload a namespace call produces:
+/// ```rust,ignore
+/// let dry_run = DryRun::default();
+/// let _ = Eth::new(&dry_run).call(request, None);
+/// println!("{}", dry_run.requests()[0]);
+/// ```
+/// Every call fails with a `Transport` error, since no response is ever received.
+#[derive(Debug, Clone, Default)]
+pub struct DryRun {
+    id: Arc<AtomicUsize>,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl DryRun {
+    /// Returns JSON of all requests recorded so far, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().clone()
+    }
+
+    /// Returns JSON of all requests recorded so far and clears the record.
+    pub fn take_requests(&self) -> Vec<String> {
+        std::mem::take(&mut *self.requests.lock())
+    }
+
+    fn record(&self, request: &rpc::Request) -> Error {
+        self.requests.lock().push(helpers::to_string(request));
+        Error::Transport("Dry run, request was not sent".into())
+    }
+}
+
+impl Transport for DryRun {
+    type Out = FutureResult<rpc::Value, Error>;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        let id = self.id.fetch_add(1, Ordering::AcqRel);
+        (id, helpers::build_request(id, method, params))
+    }
+
+    fn send(&self, _id: RequestId, request: rpc::Call) -> Self::Out {
+        future::err(self.record(&rpc::Request::Single(request)))
+    }
+}
+
+impl BatchTransport for DryRun {
+    type Batch = FutureResult<Vec<Result<rpc::Value, Error>>, Error>;
+
+    fn send_batch<I>(&self, requests: I) -> Self::Batch
+    where
+        I: IntoIterator<Item = (RequestId, rpc::Call)>,
+    {
+        let calls = requests.into_iter().map(|(_, call)| call).collect();
+        future::err(self.record(&rpc::Request::Batch(calls)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DryRun;
+    use crate::api::{Eth, Namespace};
+    use crate::types::{Address, Bytes, CallRequest};
+    use crate::Error;
+    use futures::Future;
+    use serde_json::json;
+
+    #[test]
+    fn should_record_eth_call_json() {
+        // given
+        let dry_run = DryRun::default();
+        let request = CallRequest {
+            from: None,
+            to: Address::from_low_u64_be(0x123),
+            gas: None,
+            gas_price: None,
+            value: Some(0x1.into()),
+            data: Some(Bytes(vec![0x12, 0x34])),
+            access_list: None,
+        };
+
+        // when
+        let result = Eth::new(&dry_run).call(request, None).wait();
+
+        // then
+        match result {
+            Err(Error::Transport(_)) => {}
+            other => panic!("Expected transport error, got: {:?}", other),
+        }
+        let requests = dry_run.take_requests();
+        assert_eq!(requests.len(), 1);
+        let request: serde_json::Value = serde_json::from_str(&requests[0]).unwrap();
+        assert_eq!(
+            request,
+            json!({
+                "jsonrpc": "2.0",
+                "method": "eth_call",
+                "params": [
+                    {
+                        "to": "0x0000000000000000000000000000000000000123",
+                        "value": "0x1",
+                        "data": "0x1234"
+                    },
+                    "latest"
+                ],
+                "id": 0
+            })
+        );
+        assert!(dry_run.requests().is_empty());
+    }
+}
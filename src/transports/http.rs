@@ -8,6 +8,8 @@ extern crate hyper_tls;
 #[cfg(feature = "tls")]
 extern crate native_tls;
 
+use std::marker::PhantomData;
+use std::mem;
 use std::ops::Deref;
 use std::sync::atomic::{self, AtomicUsize};
 use std::sync::Arc;
@@ -19,10 +21,12 @@ use crate::rpc;
 use crate::transports::shared::{EventLoopHandle, Response};
 use crate::transports::tokio_core::reactor;
 use crate::transports::Result;
+use crate::types::{BlockId, Filter, Log, Transaction};
 use crate::{BatchTransport, Error, RequestId, Transport};
 use base64;
 use futures::sync::{mpsc, oneshot};
 use futures::{self, future, Future, Stream};
+use serde::de::DeserializeOwned;
 use serde_json;
 
 impl From<hyper::Error> for Error {
@@ -59,7 +63,15 @@ impl From<native_tls::Error> for Error {
 // The max string length of a request without transfer-encoding: chunked.
 const MAX_SINGLE_CHUNK: usize = 256;
 const DEFAULT_MAX_PARALLEL: usize = 64;
-type Pending = oneshot::Sender<Result<hyper::Chunk>>;
+const DEFAULT_USER_AGENT: &str = "web3.rs";
+const DEFAULT_JSONRPC_VERSION: &str = "2.0";
+
+/// Channel resuming a request with either the whole response body or a stream of its chunks.
+#[derive(Debug)]
+enum Pending {
+    Buffered(oneshot::Sender<Result<hyper::Chunk>>),
+    Streamed(oneshot::Sender<Result<hyper::Body>>),
+}
 
 /// A future representing pending HTTP request, resolves to a response.
 pub type FetchTask<F> = Response<F, hyper::Chunk>;
@@ -70,6 +82,8 @@ pub struct Http {
     id: Arc<AtomicUsize>,
     url: hyper::Uri,
     basic_auth: Option<HeaderValue>,
+    user_agent: HeaderValue,
+    jsonrpc_version: Option<String>,
     write_sender: mpsc::UnboundedSender<(hyper::Request<hyper::Body>, Pending)>,
 }
 
@@ -88,6 +102,24 @@ impl Http {
         EventLoopHandle::spawn(move |handle| Self::with_event_loop(&url, handle, max_parallel))
     }
 
+    /// Create new HTTP transport with given URL and spawn an event loop in a separate thread.
+    /// Every request is sent with the given `User-Agent` header instead of the default one,
+    /// which some providers reject.
+    /// NOTE: Dropping event loop handle will stop the transport layer!
+    pub fn with_user_agent(url: &str, user_agent: &str) -> Result<(EventLoopHandle, Self)> {
+        let user_agent = HeaderValue::from_str(user_agent)?;
+        let (event_loop, mut http) = Self::new(url)?;
+        http.user_agent = user_agent;
+        Ok((event_loop, http))
+    }
+
+    /// Returns this transport sending given `jsonrpc` version string with every request
+    /// (`"2.0"` by default). Passing `None` omits the field, which some legacy nodes expect.
+    pub fn with_jsonrpc_version(mut self, version: Option<&str>) -> Self {
+        self.jsonrpc_version = version.map(Into::into);
+        self
+    }
+
     /// Create new HTTP transport with given URL and existing event loop handle.
     pub fn with_event_loop(url: &str, handle: &reactor::Handle, max_parallel: usize) -> Result<Self> {
         let (write_sender, write_receiver) = mpsc::unbounded();
@@ -106,20 +138,34 @@ impl Http {
                 .buffer_unordered(max_parallel)
                 .for_each(|(response, tx)| {
                     use futures::future::Either::{A, B};
-                    let future = match response {
-                        Ok(ref res) if !res.status().is_success() => A(future::err(Error::Transport(format!(
+                    let body = match response {
+                        Ok(ref res) if !res.status().is_success() => Err(Error::Transport(format!(
                             "Unexpected response status code: {}",
                             res.status()
-                        )))),
-                        Ok(res) => B(res.into_body().concat2().map_err(Into::into)),
-                        Err(err) => A(future::err(err.into())),
+                        ))),
+                        Ok(res) => Ok(res.into_body()),
+                        Err(err) => Err(err.into()),
                     };
-                    future.then(move |result| {
-                        if let Err(err) = tx.send(result) {
-                            log::warn!("Error resuming asynchronous request: {:?}", err);
+                    match tx {
+                        Pending::Streamed(tx) => {
+                            if tx.send(body).is_err() {
+                                log::warn!("Error resuming asynchronous request: receiver dropped");
+                            }
+                            A(future::ok(()))
                         }
-                        Ok(())
-                    })
+                        Pending::Buffered(tx) => {
+                            let future = match body {
+                                Ok(body) => A(body.concat2().map_err(Into::into)),
+                                Err(err) => B(future::err(err)),
+                            };
+                            B(future.then(move |result| {
+                                if let Err(err) = tx.send(result) {
+                                    log::warn!("Error resuming asynchronous request: {:?}", err);
+                                }
+                                Ok(())
+                            }))
+                        }
+                    }
                 }),
         );
 
@@ -138,6 +184,8 @@ impl Http {
             id: Default::default(),
             url: url.parse()?,
             basic_auth,
+            user_agent: HeaderValue::from_static(DEFAULT_USER_AGENT),
+            jsonrpc_version: Some(DEFAULT_JSONRPC_VERSION.into()),
             write_sender,
         })
     }
@@ -146,8 +194,74 @@ impl Http {
     where
         F: Fn(hyper::Chunk) -> O,
     {
-        let request = helpers::to_string(&request);
+        let request = self.serialize_request(&request);
         log::debug!("[{}] Sending: {} to {}", id, request, self.url);
+        let req = self.build_request(request);
+        let (tx, rx) = futures::oneshot();
+        let result = self
+            .write_sender
+            .unbounded_send((req, Pending::Buffered(tx)))
+            .map_err(|_| Error::Io(::std::io::ErrorKind::BrokenPipe.into()));
+
+        Response::new(id, result, rx, extract)
+    }
+
+    /// Fetches logs matching given filter from an endpoint streaming them as
+    /// newline-delimited JSON (one log object per line) instead of a JSON-RPC response.
+    ///
+    /// Logs are decoded as the response body arrives, without buffering all of it.
+    pub fn logs_ndjson(&self, filter: &Filter) -> NdjsonStream<Log> {
+        NdjsonStream::new(self.send_streamed("eth_getLogs", vec![helpers::serialize(filter)]))
+    }
+
+    /// Fetches full transactions of a block.
+    ///
+    /// Unlike `Eth::block_with_txs`, transactions are decoded one by one as the response body
+    /// arrives, so memory usage is bounded by the largest transaction rather than the whole block.
+    pub fn block_with_txs_stream(&self, block: BlockId) -> TransactionStream {
+        let (method, block) = match block {
+            BlockId::Hash(hash) => ("eth_getBlockByHash", helpers::serialize(&hash)),
+            BlockId::Number(num) => ("eth_getBlockByNumber", helpers::serialize(&num)),
+        };
+        TransactionStream::new(self.send_streamed(method, vec![block, helpers::serialize(&true)]))
+    }
+
+    fn send_streamed(&self, method: &str, params: Vec<rpc::Value>) -> ChunkStream {
+        let id = self.id.fetch_add(1, atomic::Ordering::AcqRel);
+        let request = helpers::build_request(id, method, params);
+        let request = self.serialize_request(&rpc::Request::Single(request));
+        log::debug!("[{}] Streaming: {} to {}", id, request, self.url);
+        let req = self.build_request(request);
+        let (tx, rx) = futures::oneshot();
+
+        match self.write_sender.unbounded_send((req, Pending::Streamed(tx))) {
+            Ok(()) => Box::new(
+                rx.map_err(|_| Error::Transport("Request was cancelled".into()))
+                    .and_then(|body| body)
+                    .map(|body| body.map(|chunk| chunk.to_vec()).map_err(Into::into))
+                    .flatten_stream(),
+            ),
+            Err(_) => Box::new(futures::stream::once(Err(Error::Io(
+                ::std::io::ErrorKind::BrokenPipe.into(),
+            )))),
+        }
+    }
+
+    fn serialize_request(&self, request: &rpc::Request) -> String {
+        let version = self.jsonrpc_version.as_deref();
+        if version == Some(DEFAULT_JSONRPC_VERSION) {
+            return helpers::to_string(request);
+        }
+
+        let mut request = helpers::serialize(request);
+        match request {
+            rpc::Value::Array(ref mut calls) => calls.iter_mut().for_each(|call| set_jsonrpc_version(call, version)),
+            ref mut call => set_jsonrpc_version(call, version),
+        }
+        helpers::to_string(&request)
+    }
+
+    fn build_request(&self, request: String) -> hyper::Request<hyper::Body> {
         let len = request.len();
         let mut req = hyper::Request::new(hyper::Body::from(request));
         *req.method_mut() = hyper::Method::POST;
@@ -157,7 +271,7 @@ impl Http {
             HeaderValue::from_static("application/json"),
         );
         req.headers_mut()
-            .insert(hyper::header::USER_AGENT, HeaderValue::from_static("web3.rs"));
+            .insert(hyper::header::USER_AGENT, self.user_agent.clone());
 
         // Don't send chunked request
         if len < MAX_SINGLE_CHUNK {
@@ -168,13 +282,227 @@ impl Http {
             req.headers_mut()
                 .insert(hyper::header::AUTHORIZATION, basic_auth.clone());
         }
-        let (tx, rx) = futures::oneshot();
-        let result = self
-            .write_sender
-            .unbounded_send((req, tx))
-            .map_err(|_| Error::Io(::std::io::ErrorKind::BrokenPipe.into()));
+        req
+    }
+}
 
-        Response::new(id, result, rx, extract)
+type ChunkStream = Box<dyn Stream<Item = Vec<u8>, Error = Error> + Send>;
+
+/// Stream of values decoded from a newline-delimited JSON body.
+pub struct NdjsonStream<T> {
+    body: ChunkStream,
+    buffer: Vec<u8>,
+    done: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T> NdjsonStream<T> {
+    /// Decodes values from given stream of body chunks.
+    pub fn new(body: ChunkStream) -> Self {
+        NdjsonStream {
+            body,
+            buffer: vec![],
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Stream for NdjsonStream<T> {
+    type Item = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> futures::Poll<Option<T>, Error> {
+        loop {
+            let line = match self.buffer.iter().position(|&b| b == b'\n') {
+                Some(pos) => self.buffer.drain(..=pos).collect::<Vec<_>>(),
+                None if self.done => mem::take(&mut self.buffer),
+                None => {
+                    match try_ready!(self.body.poll()) {
+                        Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                        None => self.done = true,
+                    }
+                    continue;
+                }
+            };
+
+            if line.iter().all(u8::is_ascii_whitespace) {
+                if self.done && self.buffer.is_empty() {
+                    return Ok(futures::Async::Ready(None));
+                }
+                continue;
+            }
+
+            return serde_json::from_slice(&line)
+                .map(|item| futures::Async::Ready(Some(item)))
+                .map_err(|e| Error::InvalidResponse(format!("{:?}", e)));
+        }
+    }
+}
+
+/// Depth of the elements of the `transactions` array: response object, `result` object, array.
+const TRANSACTIONS_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ScanPhase {
+    /// Scanning block fields preceding the transactions.
+    Header,
+    /// Scanning the transactions array, with the start of the current transaction (if any).
+    Transactions(Option<usize>),
+    /// The transactions array (or the whole response) has been consumed.
+    Finished,
+}
+
+/// Stream of full transactions decoded from a JSON-RPC block response.
+///
+/// The body is scanned incrementally and only the transaction being decoded is kept in memory.
+/// Ends without items if the block does not exist.
+pub struct TransactionStream {
+    body: ChunkStream,
+    buffer: Vec<u8>,
+    pos: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    string_start: usize,
+    transactions_key: bool,
+    phase: ScanPhase,
+    done: bool,
+}
+
+impl TransactionStream {
+    /// Decodes transactions from given stream of block response body chunks.
+    pub fn new(body: ChunkStream) -> Self {
+        TransactionStream {
+            body,
+            buffer: vec![],
+            pos: 0,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            string_start: 0,
+            transactions_key: false,
+            phase: ScanPhase::Header,
+            done: false,
+        }
+    }
+
+    /// Scans buffered bytes until a complete transaction is found.
+    fn scan(&mut self) -> Result<Option<Transaction>> {
+        while self.pos < self.buffer.len() && self.phase != ScanPhase::Finished {
+            let pos = self.pos;
+            let byte = self.buffer[pos];
+            self.pos += 1;
+
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                    // keys of the `result` object
+                    if self.phase == ScanPhase::Header && self.depth == TRANSACTIONS_DEPTH - 1 {
+                        self.transactions_key = &self.buffer[self.string_start + 1..pos] == b"transactions";
+                    }
+                }
+                continue;
+            }
+
+            if let b' ' | b'\t' | b'\n' | b'\r' = byte {
+                continue;
+            }
+            let transactions_key = mem::replace(&mut self.transactions_key, false);
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    self.string_start = pos;
+                }
+                b'{' | b'[' => {
+                    match self.phase {
+                        ScanPhase::Header if transactions_key && byte == b'[' => {
+                            self.phase = ScanPhase::Transactions(None);
+                        }
+                        ScanPhase::Transactions(None) if self.depth == TRANSACTIONS_DEPTH => {
+                            self.phase = ScanPhase::Transactions(Some(pos));
+                        }
+                        _ => {}
+                    }
+                    self.depth += 1;
+                }
+                b'}' | b']' => {
+                    self.depth = self
+                        .depth
+                        .checked_sub(1)
+                        .ok_or_else(|| Error::InvalidResponse("Unbalanced block response".into()))?;
+                    match self.phase {
+                        ScanPhase::Transactions(Some(start)) if self.depth == TRANSACTIONS_DEPTH => {
+                            let transaction = serde_json::from_slice(&self.buffer[start..=pos])
+                                .map_err(|e| Error::InvalidResponse(format!("{:?}", e)))?;
+                            self.phase = ScanPhase::Transactions(None);
+                            self.consume(pos + 1);
+                            return Ok(Some(transaction));
+                        }
+                        ScanPhase::Transactions(None) if self.depth < TRANSACTIONS_DEPTH => {
+                            self.phase = ScanPhase::Finished;
+                        }
+                        _ => {}
+                    }
+                }
+                b':' => self.transactions_key = transactions_key,
+                _ => {}
+            }
+        }
+
+        // the header is kept to report RPC errors, scanned bytes between transactions are dropped
+        match self.phase {
+            ScanPhase::Transactions(Some(start)) => {
+                self.consume(start);
+                self.phase = ScanPhase::Transactions(Some(0));
+            }
+            ScanPhase::Transactions(None) | ScanPhase::Finished => self.consume(self.pos),
+            ScanPhase::Header => {}
+        }
+        Ok(None)
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.buffer.drain(..len);
+        self.pos -= len;
+    }
+}
+
+impl Stream for TransactionStream {
+    type Item = Transaction;
+    type Error = Error;
+
+    fn poll(&mut self) -> futures::Poll<Option<Transaction>, Error> {
+        loop {
+            if let Some(transaction) = self.scan()? {
+                return Ok(futures::Async::Ready(Some(transaction)));
+            }
+
+            match self.phase {
+                ScanPhase::Finished => return Ok(futures::Async::Ready(None)),
+                ScanPhase::Header if self.done => {
+                    // no transactions in the whole response, it's either an error or a missing block
+                    self.phase = ScanPhase::Finished;
+                    return match single_response(mem::take(&mut self.buffer))? {
+                        rpc::Value::Null => Ok(futures::Async::Ready(None)),
+                        _ => Err(Error::InvalidResponse("Expected block with transactions".into())),
+                    };
+                }
+                ScanPhase::Transactions(_) if self.done => {
+                    return Err(Error::InvalidResponse("Unexpected end of block response".into()));
+                }
+                _ => {}
+            }
+
+            match try_ready!(self.body.poll()) {
+                Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                None => self.done = true,
+            }
+        }
     }
 }
 
@@ -194,23 +522,35 @@ impl Transport for Http {
 }
 
 impl BatchTransport for Http {
-    type Batch = FetchTask<fn(hyper::Chunk) -> Result<Vec<Result<rpc::Value>>>>;
+    type Batch = FetchTask<Box<dyn Fn(hyper::Chunk) -> Result<Vec<Result<rpc::Value>>> + Send>>;
 
     fn send_batch<T>(&self, requests: T) -> Self::Batch
     where
         T: IntoIterator<Item = (RequestId, rpc::Call)>,
     {
-        let mut it = requests.into_iter();
-        let (id, first) = it.next().map(|x| (x.0, Some(x.1))).unwrap_or_else(|| (0, None));
-        let requests = first.into_iter().chain(it.map(|x| x.1)).collect();
+        let (ids, requests): (Vec<_>, Vec<_>) = requests.into_iter().unzip();
+        let id = ids.first().cloned().unwrap_or(0);
+
+        self.send_request(
+            id,
+            rpc::Request::Batch(requests),
+            Box::new(move |response| batch_response(&ids, response)),
+        )
+    }
+}
 
-        self.send_request(id, rpc::Request::Batch(requests), batch_response)
+fn set_jsonrpc_version(call: &mut rpc::Value, version: Option<&str>) {
+    if let rpc::Value::Object(ref mut call) = *call {
+        match version {
+            Some(version) => call.insert("jsonrpc".into(), version.into()),
+            None => call.remove("jsonrpc"),
+        };
     }
 }
 
 /// Parse bytes RPC response into `Result`.
 fn single_response<T: Deref<Target = [u8]>>(response: T) -> Result<rpc::Value> {
-    let response = serde_json::from_slice(&*response).map_err(|e| Error::InvalidResponse(format!("{:?}", e)))?;
+    let response = helpers::to_response_from_slice(&*response)?;
 
     match response {
         rpc::Response::Single(output) => helpers::to_result_from_output(output),
@@ -219,11 +559,11 @@ fn single_response<T: Deref<Target = [u8]>>(response: T) -> Result<rpc::Value> {
 }
 
 /// Parse bytes RPC batch response into `Result`.
-fn batch_response<T: Deref<Target = [u8]>>(response: T) -> Result<Vec<Result<rpc::Value>>> {
-    let response = serde_json::from_slice(&*response).map_err(|e| Error::InvalidResponse(format!("{:?}", e)))?;
+fn batch_response<T: Deref<Target = [u8]>>(ids: &[RequestId], response: T) -> Result<Vec<Result<rpc::Value>>> {
+    let response = helpers::to_response_from_slice(&*response)?;
 
     match response {
-        rpc::Response::Batch(outputs) => Ok(outputs.into_iter().map(helpers::to_result_from_output).collect()),
+        rpc::Response::Batch(outputs) => helpers::to_results_from_outputs_by_id(ids, outputs),
         _ => Err(Error::InvalidResponse("Expected batch, got single.".into())),
     }
 }
@@ -231,6 +571,7 @@ fn batch_response<T: Deref<Target = [u8]>>(response: T) -> Result<Vec<Result<rpc
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::Block;
 
     #[test]
     fn http_supports_basic_auth_with_user_and_password() {
@@ -279,4 +620,169 @@ mod tests {
             Err(_) => assert!(false, ""),
         }
     }
+
+    #[test]
+    fn http_sends_configured_user_agent() {
+        let (_, default) = Http::new("http://127.0.0.1:8545").unwrap();
+        let (_, custom) = Http::with_user_agent("http://127.0.0.1:8545", "my-app/1.0").unwrap();
+
+        let default = default.build_request("{}".into());
+        let custom = custom.build_request("{}".into());
+
+        assert_eq!(default.headers()[hyper::header::USER_AGENT], "web3.rs");
+        assert_eq!(custom.headers()[hyper::header::USER_AGENT], "my-app/1.0");
+    }
+
+    #[test]
+    fn http_rejects_response_without_result_and_error() {
+        let response = single_response(&br#"{"jsonrpc":"2.0","id":1}"#[..]);
+
+        assert_eq!(
+            response,
+            Err(Error::InvalidResponse(
+                "Response has neither result nor error (id: 1)".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn http_decodes_ndjson_logs() {
+        let log = r#"{"address":"0x0000000000000000000000000000000000000001","topics":[],"data":"0x","blockHash":null,"blockNumber":"0x1","transactionHash":null,"transactionIndex":null,"logIndex":null,"transactionLogIndex":null,"logType":null,"removed":false}"#;
+        let body = format!(
+            "{}\n{}\n\n{}",
+            log,
+            log.replace("0x1\"", "0x2\""),
+            log.replace("0x1\"", "0x3\"")
+        );
+        // split chunks in the middle of lines
+        let chunks = body
+            .as_bytes()
+            .chunks(100)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect::<Vec<_>>();
+
+        let logs: Vec<Log> = NdjsonStream::new(Box::new(futures::stream::iter_result(chunks)))
+            .collect()
+            .wait()
+            .unwrap();
+
+        assert_eq!(
+            logs.iter().map(|log| log.block_number).collect::<Vec<_>>(),
+            vec![Some(1.into()), Some(2.into()), Some(3.into())]
+        );
+    }
+
+    fn chunked(body: &str, size: usize) -> ChunkStream {
+        let chunks = body
+            .as_bytes()
+            .chunks(size)
+            .map(|chunk| Ok(chunk.to_vec()))
+            .collect::<Vec<_>>();
+        Box::new(futures::stream::iter_result(chunks))
+    }
+
+    #[test]
+    fn http_streams_block_transactions() {
+        let transactions = (0..4000u64)
+            .map(|nonce| Transaction {
+                nonce: nonce.into(),
+                input: vec![0x7b, 0x22, 0x5d].into(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let block = Block {
+            transactions: transactions.clone(),
+            size: Some(1.into()),
+            ..Default::default()
+        };
+        let body = format!(r#"{{"jsonrpc":"2.0","id":1,"result":{}}}"#, helpers::to_string(&block));
+        let transaction_len = helpers::to_string(&transactions[0]).len();
+        let mut stream = TransactionStream::new(chunked(&body, 64));
+
+        let mut streamed = vec![];
+        while let futures::Async::Ready(Some(transaction)) = stream.poll().unwrap() {
+            // only the transaction being decoded is buffered
+            assert!(stream.buffer.len() < transaction_len + 64);
+            streamed.push(transaction);
+        }
+
+        assert!(body.len() > 1_000_000);
+        assert_eq!(streamed, transactions);
+        assert_eq!(stream.poll(), Ok(futures::Async::Ready(None)));
+    }
+
+    #[test]
+    fn http_streams_transactions_of_pretty_printed_block() {
+        let transactions = (0..3u64)
+            .map(|nonce| Transaction {
+                nonce: nonce.into(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let block = Block {
+            transactions: transactions.clone(),
+            ..Default::default()
+        };
+        let response = serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": block});
+        let body = serde_json::to_string_pretty(&response).unwrap().replace("  ", "\t");
+        let mut stream = TransactionStream::new(chunked(&body.replace('\n', "\r\n"), 16));
+
+        let mut streamed = vec![];
+        while let futures::Async::Ready(Some(transaction)) = stream.poll().unwrap() {
+            // transactions are streamed rather than decoded from the whole buffered body
+            assert!(stream.buffer.len() < body.len() / 2);
+            streamed.push(transaction);
+        }
+
+        assert!(body.contains("\"transactions\": ["));
+        assert_eq!(streamed, transactions);
+    }
+
+    #[test]
+    fn http_streams_missing_or_failed_block() {
+        let missing = TransactionStream::new(chunked(r#"{"jsonrpc":"2.0","id":1,"result":null}"#, 8));
+        let failed = TransactionStream::new(chunked(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"header not found"}}"#,
+            8,
+        ));
+
+        assert_eq!(missing.collect().wait(), Ok(vec![]));
+        match failed.collect().wait() {
+            Err(Error::Rpc(err)) => assert_eq!(err.message, "header not found"),
+            other => panic!("Expected RPC error, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn http_sends_configured_jsonrpc_version() {
+        let (_, http) = Http::new("http://127.0.0.1:8545").unwrap();
+        let single = || rpc::Request::Single(helpers::build_request(1, "eth_blockNumber", vec![]));
+        let batch = || {
+            rpc::Request::Batch(vec![
+                helpers::build_request(1, "eth_blockNumber", vec![]),
+                helpers::build_request(2, "net_version", vec![]),
+            ])
+        };
+
+        assert_eq!(
+            http.serialize_request(&single()),
+            r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#
+        );
+
+        let http = http.with_jsonrpc_version(Some("1.0"));
+        assert_eq!(
+            http.serialize_request(&single()),
+            r#"{"id":1,"jsonrpc":"1.0","method":"eth_blockNumber","params":[]}"#
+        );
+        assert_eq!(
+            http.serialize_request(&batch()),
+            r#"[{"id":1,"jsonrpc":"1.0","method":"eth_blockNumber","params":[]},{"id":2,"jsonrpc":"1.0","method":"net_version","params":[]}]"#
+        );
+
+        let http = http.with_jsonrpc_version(None);
+        assert_eq!(
+            http.serialize_request(&single()),
+            r#"{"id":1,"method":"eth_blockNumber","params":[]}"#
+        );
+    }
 }
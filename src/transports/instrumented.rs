@@ -0,0 +1,224 @@
+//! Instrumented Transport
+
+use crate::rpc;
+use crate::{BatchTransport, Error, RequestId, Transport};
+use futures::{Async, Future, Poll};
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Hook notified about requests going through an `Instrumented` transport,
+/// e.g. to export per-method latency and error rates.
+///
+/// All methods do nothing by default.
+pub trait Instrument: fmt::Debug {
+    /// Called when a request for `method` is sent.
+    fn on_request(&self, _method: &str) {}
+
+    /// Called when a request for `method` completes, `elapsed` after it was sent.
+    fn on_response(&self, _method: &str, _elapsed: Duration, _result: &Result<rpc::Value, Error>) {}
+}
+
+/// No-op instrumentation.
+impl Instrument for () {}
+
+/// Transport reporting every request to an `Instrument` hook.
+///
+/// Calls of a batch are reported individually, each with the latency of the whole batch.
+#[derive(Debug)]
+pub struct Instrumented<T, I> {
+    transport: T,
+    instrument: Arc<I>,
+}
+
+impl<T: Clone, I> Clone for Instrumented<T, I> {
+    fn clone(&self) -> Self {
+        Instrumented {
+            transport: self.transport.clone(),
+            instrument: self.instrument.clone(),
+        }
+    }
+}
+
+impl<T: Transport, I: Instrument> Instrumented<T, I> {
+    /// Wraps existing transport, reporting every request to `instrument`.
+    pub fn new(transport: T, instrument: I) -> Self {
+        Instrumented {
+            transport,
+            instrument: Arc::new(instrument),
+        }
+    }
+
+    /// Returns the instrumentation hook.
+    pub fn instrument(&self) -> &I {
+        &self.instrument
+    }
+
+    /// Returns the underlying transport.
+    pub fn inner(&self) -> &T {
+        &self.transport
+    }
+
+    fn start(&self, request: &rpc::Call) -> String {
+        let method = match *request {
+            rpc::Call::MethodCall(ref call) => call.method.clone(),
+            rpc::Call::Notification(ref notification) => notification.method.clone(),
+            rpc::Call::Invalid { .. } => String::new(),
+        };
+        self.instrument.on_request(&method);
+        method
+    }
+}
+
+impl<T: Transport, I: Instrument> Transport for Instrumented<T, I> {
+    type Out = InstrumentedFuture<T::Out, I>;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        self.transport.prepare(method, params)
+    }
+
+    fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+        let method = self.start(&request);
+        InstrumentedFuture {
+            inner: self.transport.send(id, request),
+            method,
+            started: Instant::now(),
+            instrument: self.instrument.clone(),
+        }
+    }
+}
+
+impl<T: BatchTransport, I: Instrument> BatchTransport for Instrumented<T, I> {
+    type Batch = InstrumentedBatch<T::Batch, I>;
+
+    fn send_batch<R>(&self, requests: R) -> Self::Batch
+    where
+        R: IntoIterator<Item = (RequestId, rpc::Call)>,
+    {
+        let requests = requests.into_iter().collect::<Vec<_>>();
+        let methods = requests.iter().map(|(_, request)| self.start(request)).collect();
+        InstrumentedBatch {
+            inner: self.transport.send_batch(requests),
+            methods,
+            started: Instant::now(),
+            instrument: self.instrument.clone(),
+        }
+    }
+}
+
+/// A future reporting the result of a single request once resolved.
+#[derive(Debug)]
+pub struct InstrumentedFuture<F, I> {
+    inner: F,
+    method: String,
+    started: Instant,
+    instrument: Arc<I>,
+}
+
+impl<F, I> Future for InstrumentedFuture<F, I>
+where
+    F: Future<Item = rpc::Value, Error = Error>,
+    I: Instrument,
+{
+    type Item = rpc::Value;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let result = match self.inner.poll() {
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Ok(Async::Ready(value)) => Ok(value),
+            Err(err) => Err(err),
+        };
+        let elapsed = self.started.elapsed();
+        self.instrument.on_response(&self.method, elapsed, &result);
+        result.map(Async::Ready)
+    }
+}
+
+/// A future reporting the results of all calls of a batch once resolved.
+#[derive(Debug)]
+pub struct InstrumentedBatch<F, I> {
+    inner: F,
+    methods: Vec<String>,
+    started: Instant,
+    instrument: Arc<I>,
+}
+
+impl<F, I> Future for InstrumentedBatch<F, I>
+where
+    F: Future<Item = Vec<Result<rpc::Value, Error>>, Error = Error>,
+    I: Instrument,
+{
+    type Item = F::Item;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let result = match self.inner.poll() {
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Ok(Async::Ready(results)) => Ok(results),
+            Err(err) => Err(err),
+        };
+        let elapsed = self.started.elapsed();
+        match result {
+            Ok(ref results) => {
+                for (method, result) in self.methods.iter().zip(results) {
+                    self.instrument.on_response(method, elapsed, result);
+                }
+            }
+            Err(ref err) => {
+                for method in &self.methods {
+                    self.instrument.on_response(method, elapsed, &Err(err.clone()));
+                }
+            }
+        }
+        result.map(Async::Ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Instrument, Instrumented};
+    use crate::api::{Namespace, Web3Api};
+    use crate::helpers::tests::TestTransport;
+    use crate::{rpc, Error};
+    use futures::Future;
+    use parking_lot::Mutex;
+    use std::time::Duration;
+
+    #[derive(Debug, Default)]
+    struct Counting {
+        requests: Mutex<Vec<String>>,
+        responses: Mutex<Vec<(String, bool)>>,
+    }
+
+    impl Instrument for Counting {
+        fn on_request(&self, method: &str) {
+            self.requests.lock().push(method.into());
+        }
+
+        fn on_response(&self, method: &str, _elapsed: Duration, result: &Result<rpc::Value, Error>) {
+            self.responses.lock().push((method.into(), result.is_ok()));
+        }
+    }
+
+    #[test]
+    fn should_record_success_and_failure() {
+        // given
+        let mut transport = TestTransport::default();
+        transport.set_response(rpc::Value::String("Test123".into()));
+        let instrumented = Instrumented::new(&transport, Counting::default());
+
+        // when
+        let succeeded = Web3Api::new(instrumented.clone()).client_version().wait();
+        let failed = Web3Api::new(instrumented.clone()).client_version().wait();
+
+        // then
+        assert_eq!(succeeded, Ok("Test123".into()));
+        assert_eq!(failed, Err(Error::Unreachable));
+        let counting = instrumented.instrument();
+        let method = "web3_clientVersion".to_owned();
+        assert_eq!(*counting.requests.lock(), vec![method.clone(), method.clone()]);
+        let responses = vec![(method.clone(), true), (method, false)];
+        assert_eq!(*counting.responses.lock(), responses);
+    }
+}
@@ -37,12 +37,12 @@ macro_rules! try_nb {
     };
 }
 
-type Pending = oneshot::Sender<Result<Vec<Result<rpc::Value>>>>;
+type Pending = oneshot::Sender<Result<Vec<rpc::Output>>>;
 
 type Subscription = mpsc::UnboundedSender<rpc::Value>;
 
 /// A future representing pending IPC request, resolves to a response.
-pub type IpcTask<F> = Response<F, Vec<Result<rpc::Value>>>;
+pub type IpcTask<F> = Response<F, Vec<rpc::Output>>;
 
 /// Unix Domain Sockets (IPC) transport
 #[derive(Debug, Clone)]
@@ -119,7 +119,7 @@ impl Ipc {
 
     fn send_request<F, O>(&self, id: RequestId, request: rpc::Request, extract: F) -> IpcTask<F>
     where
-        F: Fn(Vec<Result<rpc::Value>>) -> O,
+        F: Fn(Vec<rpc::Output>) -> O,
     {
         let request = helpers::to_string(&request);
         log::debug!("[{}] Calling: {}", id, request);
@@ -136,7 +136,7 @@ impl Ipc {
 }
 
 impl Transport for Ipc {
-    type Out = IpcTask<fn(Vec<Result<rpc::Value>>) -> Result<rpc::Value>>;
+    type Out = IpcTask<fn(Vec<rpc::Output>) -> Result<rpc::Value>>;
 
     fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
         let id = self.id.fetch_add(1, atomic::Ordering::AcqRel);
@@ -150,24 +150,27 @@ impl Transport for Ipc {
     }
 }
 
-fn single_response(response: Vec<Result<rpc::Value>>) -> Result<rpc::Value> {
+fn single_response(response: Vec<rpc::Output>) -> Result<rpc::Value> {
     match response.into_iter().next() {
-        Some(res) => res,
+        Some(output) => helpers::to_result_from_output(output),
         None => Err(Error::InvalidResponse("Expected single, got batch.".into())),
     }
 }
 
 impl BatchTransport for Ipc {
-    type Batch = IpcTask<fn(Vec<Result<rpc::Value>>) -> Result<Vec<Result<rpc::Value>>>>;
+    type Batch = IpcTask<Box<dyn Fn(Vec<rpc::Output>) -> Result<Vec<Result<rpc::Value>>> + Send>>;
 
     fn send_batch<T>(&self, requests: T) -> Self::Batch
     where
         T: IntoIterator<Item = (RequestId, rpc::Call)>,
     {
-        let mut it = requests.into_iter();
-        let (id, first) = it.next().map(|x| (x.0, Some(x.1))).unwrap_or_else(|| (0, None));
-        let requests = first.into_iter().chain(it.map(|x| x.1)).collect();
-        self.send_request(id, rpc::Request::Batch(requests), Ok)
+        let (ids, requests): (Vec<_>, Vec<_>) = requests.into_iter().unzip();
+        let id = ids.first().cloned().unwrap_or(0);
+        self.send_request(
+            id,
+            rpc::Request::Batch(requests),
+            Box::new(move |outputs| helpers::to_results_from_outputs_by_id(&ids, outputs)),
+        )
     }
 }
 
@@ -308,16 +311,12 @@ impl ReadStream {
     fn respond(&self, response: Message) {
         match response {
             Message::Rpc(outputs) => {
-                let id = match outputs.get(0) {
-                    Some(&rpc::Output::Success(ref success)) => success.id.clone(),
-                    Some(&rpc::Output::Failure(ref failure)) => failure.id.clone(),
-                    None => rpc::Id::Num(0),
-                };
+                let id = helpers::pending_request_id(&self.pending.lock(), &outputs);
 
                 if let rpc::Id::Num(num) = id {
                     if let Some(request) = self.pending.lock().remove(&(num as usize)) {
                         log::trace!("Responding to (id: {:?}) with {:?}", num, outputs);
-                        if let Err(err) = request.send(helpers::to_results_from_outputs(outputs)) {
+                        if let Err(err) = request.send(Ok(outputs)) {
                             log::warn!("Sending a response to deallocated channel: {:?}", err);
                         }
                     } else {
@@ -8,6 +8,24 @@ pub type Result<T> = ::std::result::Result<T, Error>;
 pub mod batch;
 pub use self::batch::Batch;
 
+pub mod checksummed;
+pub use self::checksummed::Checksummed;
+
+pub mod coalesce;
+pub use self::coalesce::Coalesce;
+
+pub mod dry_run;
+pub use self::dry_run::DryRun;
+
+pub mod instrumented;
+pub use self::instrumented::{Instrument, Instrumented};
+
+pub mod rate_limit;
+pub use self::rate_limit::RateLimited;
+
+pub mod timeout;
+pub use self::timeout::Timeout;
+
 #[cfg(feature = "http")]
 pub mod http;
 #[cfg(feature = "http")]
@@ -0,0 +1,262 @@
+//! Rate Limiting Transport
+
+use crate::api::SubscriptionId;
+use crate::helpers;
+use crate::rpc;
+use crate::{DuplexTransport, Error, RequestId, Transport};
+use futures::{Future, Poll};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_timer::Sleep;
+
+/// Transport pacing outgoing requests with a token bucket.
+///
+/// Up to `burst` requests are sent immediately, afterwards requests are delayed
+/// so that no more than `rate` requests per second are sent on average.
+/// Requests over the limit wait for capacity instead of failing.
+/// Subscription notifications are not affected by the limiter, and requests
+/// dropped while waiting give their capacity back.
+#[derive(Debug, Clone)]
+pub struct RateLimited<T> {
+    transport: T,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Takes a token from the bucket, returning how long to wait until it is available.
+    fn reserve(&mut self) -> Duration {
+        self.reserve_at(Instant::now())
+    }
+
+    /// Takes a token from the bucket as of `now`.
+    fn reserve_at(&mut self, now: Instant) -> Duration {
+        let elapsed = now.duration_since(self.updated);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.burst);
+        self.updated = now;
+        self.tokens -= 1.0;
+
+        if self.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+
+    /// Returns a reserved token which ended up unused.
+    fn release(&mut self) {
+        self.tokens = (self.tokens + 1.0).min(self.burst);
+    }
+}
+
+impl<T: Transport> RateLimited<T> {
+    /// Wraps existing transport, allowing `rate` requests per second on average
+    /// and bursts of up to `burst` requests.
+    ///
+    /// Panics if `rate` is not positive or `burst` is zero.
+    pub fn new(transport: T, rate: f64, burst: u32) -> Self {
+        assert!(rate > 0.0, "rate must be positive");
+        assert!(burst > 0, "burst must be greater than 0");
+
+        RateLimited {
+            transport,
+            bucket: Arc::new(Mutex::new(Bucket {
+                rate,
+                burst: burst.into(),
+                tokens: burst.into(),
+                updated: Instant::now(),
+            })),
+        }
+    }
+
+    /// Returns the underlying transport.
+    pub fn inner(&self) -> &T {
+        &self.transport
+    }
+}
+
+impl<T: Transport> Transport for RateLimited<T> {
+    type Out = RateLimitedFuture<T>;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        self.transport.prepare(method, params)
+    }
+
+    fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+        let delay = self.bucket.lock().reserve();
+        if delay == Duration::from_secs(0) {
+            return RateLimitedFuture {
+                state: State::Sending(self.transport.send(id, request)),
+            };
+        }
+
+        RateLimitedFuture {
+            state: State::Waiting {
+                sleep: helpers::timer().sleep(delay),
+                bucket: self.bucket.clone(),
+                transport: self.transport.clone(),
+                request: Some((id, request)),
+            },
+        }
+    }
+}
+
+impl<T: DuplexTransport> DuplexTransport for RateLimited<T> {
+    type NotificationStream = T::NotificationStream;
+
+    fn subscribe(&self, id: &SubscriptionId) -> Self::NotificationStream {
+        self.transport.subscribe(id)
+    }
+
+    fn unsubscribe(&self, id: &SubscriptionId) {
+        self.transport.unsubscribe(id)
+    }
+}
+
+enum State<T: Transport> {
+    Waiting {
+        sleep: Sleep,
+        bucket: Arc<Mutex<Bucket>>,
+        transport: T,
+        request: Option<(RequestId, rpc::Call)>,
+    },
+    Sending(T::Out),
+}
+
+/// A future sending the request once the rate limit allows it.
+pub struct RateLimitedFuture<T: Transport> {
+    state: State<T>,
+}
+
+impl<T: Transport> Future for RateLimitedFuture<T> {
+    type Item = rpc::Value;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            let next = match self.state {
+                State::Waiting {
+                    ref mut sleep,
+                    ref transport,
+                    ref mut request,
+                    ..
+                } => {
+                    try_ready!(sleep.poll().map_err(|err| Error::Transport(format!("{:?}", err))));
+                    let (id, request) = request.take().expect("request is only taken once; qed");
+                    State::Sending(transport.send(id, request))
+                }
+                State::Sending(ref mut inner) => return inner.poll(),
+            };
+            self.state = next;
+        }
+    }
+}
+
+impl<T: Transport> Drop for RateLimitedFuture<T> {
+    fn drop(&mut self) {
+        // the request was never sent, so it shouldn't count against the rate
+        if let State::Waiting { ref bucket, .. } = self.state {
+            bucket.lock().release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bucket, RateLimited};
+    use crate::rpc;
+    use crate::{Error, RequestId, Transport};
+    use futures::{future, Future};
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    #[derive(Debug, Clone, Default)]
+    struct RecordingTransport(Arc<Mutex<Vec<Instant>>>);
+
+    impl Transport for RecordingTransport {
+        type Out = future::FutureResult<rpc::Value, Error>;
+
+        fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+            (1, crate::helpers::build_request(1, method, params))
+        }
+
+        fn send(&self, _id: RequestId, _request: rpc::Call) -> Self::Out {
+            self.0.lock().push(Instant::now());
+            future::ok(rpc::Value::Null)
+        }
+    }
+
+    #[test]
+    fn should_space_requests_according_to_rate() {
+        // given
+        let start = Instant::now();
+        let mut bucket = Bucket {
+            rate: 10.0,
+            burst: 2.0,
+            tokens: 2.0,
+            updated: start,
+        };
+
+        // when
+        let delays = (0..4).map(|_| bucket.reserve_at(start)).collect::<Vec<_>>();
+
+        // then
+        // the burst is sent right away, the rest is paced at 10 requests per second
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_secs(0),
+                Duration::from_secs(0),
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_refill_tokens_over_time_up_to_burst() {
+        // given
+        let start = Instant::now();
+        let mut bucket = Bucket {
+            rate: 10.0,
+            burst: 2.0,
+            tokens: -2.0,
+            updated: start,
+        };
+
+        // when
+        let partial = bucket.reserve_at(start + Duration::from_millis(250));
+        let full = bucket.reserve_at(start + Duration::from_secs(10));
+
+        // then
+        assert_eq!(partial, Duration::from_millis(50));
+        assert_eq!(full, Duration::from_secs(0));
+        assert_eq!(bucket.tokens, 1.0);
+    }
+
+    #[test]
+    fn should_return_token_of_dropped_request() {
+        // given
+        let recording = RecordingTransport::default();
+        let transport = RateLimited::new(recording.clone(), 10.0, 1);
+        transport.execute("eth_blockNumber", vec![]).wait().unwrap();
+
+        // when
+        let waiting = transport.execute("eth_blockNumber", vec![]);
+        assert!(transport.bucket.lock().tokens < 0.0);
+        drop(waiting);
+
+        // then
+        assert!(transport.bucket.lock().tokens >= 0.0);
+        assert_eq!(recording.0.lock().len(), 1);
+    }
+}
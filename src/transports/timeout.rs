@@ -0,0 +1,164 @@
+//! Request Timeout Transport
+
+use crate::helpers;
+use crate::rpc;
+use crate::{BatchTransport, Error, RequestId, Transport};
+use futures::{Async, Future, Poll};
+use std::io;
+use std::time::Duration;
+use tokio_timer::Sleep;
+
+/// Transport failing requests that don't complete within given duration.
+///
+/// The timeout can be overridden for a subset of calls by constructing
+/// a namespace with a copy of the transport returned from `with_timeout`:
+/// ```rust,ignore
+/// let transport = Timeout::new(http, Duration::from_secs(5));
+/// let traces = Traces::new(transport.with_timeout(Duration::from_secs(120)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Timeout<T> {
+    transport: T,
+    timeout: Duration,
+}
+
+impl<T: Transport> Timeout<T> {
+    /// Wraps existing transport, applying `timeout` to every request.
+    pub fn new(transport: T, timeout: Duration) -> Self {
+        Timeout { transport, timeout }
+    }
+
+    /// Returns a copy of this transport using a different timeout.
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Timeout {
+            transport: self.transport.clone(),
+            timeout,
+        }
+    }
+
+    /// Returns currently configured timeout.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the underlying transport.
+    pub fn inner(&self) -> &T {
+        &self.transport
+    }
+}
+
+impl<T: Transport> Transport for Timeout<T> {
+    type Out = TimeoutFuture<T::Out>;
+
+    fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+        self.transport.prepare(method, params)
+    }
+
+    fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
+        TimeoutFuture {
+            inner: self.transport.send(id, request),
+            sleep: helpers::timer().sleep(self.timeout),
+        }
+    }
+}
+
+impl<T: BatchTransport> BatchTransport for Timeout<T> {
+    type Batch = TimeoutFuture<T::Batch>;
+
+    fn send_batch<I>(&self, requests: I) -> Self::Batch
+    where
+        I: IntoIterator<Item = (RequestId, rpc::Call)>,
+    {
+        TimeoutFuture {
+            inner: self.transport.send_batch(requests),
+            sleep: helpers::timer().sleep(self.timeout),
+        }
+    }
+}
+
+/// A future resolving to the inner result or failing with `TimedOut` I/O error.
+#[derive(Debug)]
+pub struct TimeoutFuture<F> {
+    inner: F,
+    sleep: Sleep,
+}
+
+impl<F: Future<Error = Error>> Future for TimeoutFuture<F> {
+    type Item = F::Item;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if let Async::Ready(result) = self.inner.poll()? {
+            return Ok(Async::Ready(result));
+        }
+
+        match self.sleep.poll() {
+            Ok(Async::Ready(())) => Err(Error::Io(io::ErrorKind::TimedOut.into())),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(err) => Err(Error::Transport(format!("{:?}", err))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timeout;
+    use crate::api::{Namespace, Web3Api};
+    use crate::rpc;
+    use crate::{Error, RequestId, Transport};
+    use futures::Future;
+    use std::io;
+    use std::time::Duration;
+
+    #[derive(Debug, Clone)]
+    struct SlowTransport(Duration);
+
+    impl Transport for SlowTransport {
+        type Out = Box<dyn Future<Item = rpc::Value, Error = Error> + Send>;
+
+        fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
+            (1, crate::helpers::build_request(1, method, params))
+        }
+
+        fn send(&self, _id: RequestId, _request: rpc::Call) -> Self::Out {
+            Box::new(
+                crate::helpers::timer()
+                    .sleep(self.0)
+                    .then(|_| Ok(rpc::Value::String("Test123".into()))),
+            )
+        }
+    }
+
+    #[test]
+    fn should_override_timeout_for_namespace() {
+        // given
+        let transport = Timeout::new(SlowTransport(Duration::from_millis(500)), Duration::from_millis(100));
+        let short = Web3Api::new(transport.clone());
+        let long = Web3Api::new(transport.with_timeout(Duration::from_secs(5)));
+
+        // when
+        let timed_out = short.client_version().wait();
+        let result = long.client_version().wait();
+
+        // then
+        match timed_out {
+            Err(Error::Io(ref e)) if e.kind() == io::ErrorKind::TimedOut => {}
+            other => panic!("Expected timeout, got: {:?}", other),
+        }
+        assert_eq!(result, Ok("Test123".into()));
+        assert_eq!(transport.timeout(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn should_support_timeouts_over_the_default_timer_limit() {
+        // given
+        // the timeout exceeds the 409.6 seconds supported by a default `Timer`
+        let transport = Timeout::new(SlowTransport(Duration::from_millis(10)), Duration::from_secs(10 * 60));
+
+        // when
+        let result = Web3Api::new(transport).client_version().wait();
+
+        // then
+        assert_eq!(result, Ok("Test123".into()));
+    }
+}
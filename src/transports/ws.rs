@@ -30,18 +30,18 @@ impl From<websocket::client::ParseError> for Error {
     }
 }
 
-type Pending = oneshot::Sender<Result<Vec<Result<rpc::Value>>>>;
+type Pending = oneshot::Sender<Result<Vec<rpc::Output>>>;
 
 type Subscription = mpsc::UnboundedSender<rpc::Value>;
 
 /// A future representing pending WebSocket request, resolves to a response.
-pub type WsTask<F> = Response<F, Vec<Result<rpc::Value>>>;
+pub type WsTask<F> = Response<F, Vec<rpc::Output>>;
 
 /// WebSocket transport
 #[derive(Debug, Clone)]
 pub struct WebSocket {
     id: Arc<atomic::AtomicUsize>,
-    url: Url,
+    url: Option<Url>,
     pending: Arc<Mutex<BTreeMap<RequestId, Pending>>>,
     subscriptions: Arc<Mutex<BTreeMap<SubscriptionId, Subscription>>>,
     write_sender: mpsc::UnboundedSender<OwnedMessage>,
@@ -60,110 +60,61 @@ impl WebSocket {
         log::trace!("Connecting to: {:?}", url);
 
         let url: Url = url.parse()?;
-        let pending: Arc<Mutex<BTreeMap<RequestId, Pending>>> = Default::default();
-        let subscriptions: Arc<Mutex<BTreeMap<SubscriptionId, Subscription>>> = Default::default();
+        let shared = Shared::default();
         let (write_sender, write_receiver) = mpsc::unbounded();
 
         let ws_future = {
-            let pending_ = pending.clone();
-            let subscriptions_ = subscriptions.clone();
-            let write_sender_ = write_sender.clone();
+            let shared = shared.clone();
+            let write_sender = write_sender.clone();
 
             ClientBuilder::from_url(&url)
                 .async_connect(None, handle)
                 .from_err::<Error>()
-                .map(|(duplex, _)| duplex.split())
-                .and_then(move |(sink, stream)| {
-                    let reader = stream.from_err::<Error>().for_each(move |message| {
-                        log::trace!("Message received: {:?}", message);
-
-                        match message {
-                            OwnedMessage::Close(e) => write_sender_
-                                .unbounded_send(OwnedMessage::Close(e))
-                                .map_err(|_| Error::Transport("Error sending close message".into())),
-                            OwnedMessage::Ping(d) => write_sender_
-                                .unbounded_send(OwnedMessage::Pong(d))
-                                .map_err(|_| Error::Transport("Error sending pong message".into())),
-                            OwnedMessage::Text(t) => {
-                                if let Ok(notification) = helpers::to_notification_from_slice(t.as_bytes()) {
-                                    if let rpc::Params::Map(params) = notification.params {
-                                        let id = params.get("subscription");
-                                        let result = params.get("result");
-
-                                        if let (Some(&rpc::Value::String(ref id)), Some(result)) = (id, result) {
-                                            let id: SubscriptionId = id.clone().into();
-                                            if let Some(stream) = subscriptions_.lock().get(&id) {
-                                                return stream.unbounded_send(result.clone()).map_err(|_| {
-                                                    Error::Transport("Error sending notification".into())
-                                                });
-                                            } else {
-                                                log::warn!("Got notification for unknown subscription (id: {:?})", id);
-                                            }
-                                        } else {
-                                            log::error!("Got unsupported notification (id: {:?})", id);
-                                        }
-                                    }
-
-                                    return Ok(());
-                                }
+                .and_then(move |(duplex, _)| run_connection(duplex, shared, write_sender, write_receiver))
+        };
 
-                                let response = helpers::to_response_from_slice(t.as_bytes());
-                                let outputs = match response {
-                                    Ok(rpc::Response::Single(output)) => vec![output],
-                                    Ok(rpc::Response::Batch(outputs)) => outputs,
-                                    _ => vec![],
-                                };
-
-                                let id = match outputs.get(0) {
-                                    Some(&rpc::Output::Success(ref success)) => success.id.clone(),
-                                    Some(&rpc::Output::Failure(ref failure)) => failure.id.clone(),
-                                    None => rpc::Id::Num(0),
-                                };
-
-                                if let rpc::Id::Num(num) = id {
-                                    if let Some(request) = pending_.lock().remove(&(num as usize)) {
-                                        log::trace!("Responding to (id: {:?}) with {:?}", num, outputs);
-                                        if let Err(err) = request.send(helpers::to_results_from_outputs(outputs)) {
-                                            log::warn!("Sending a response to deallocated channel: {:?}", err);
-                                        }
-                                    } else {
-                                        log::warn!("Got response for unknown request (id: {:?})", num);
-                                    }
-                                } else {
-                                    log::warn!("Got unsupported response (id: {:?})", id);
-                                }
+        handle.spawn(ws_future.map_err(|err| {
+            log::error!("WebSocketError: {:?}", err);
+        }));
 
-                                Ok(())
-                            }
-                            _ => Ok(()),
-                        }
-                    });
+        Ok(Self::from_shared(Some(url), shared, write_sender))
+    }
 
-                    let writer = sink
-                        .sink_from_err()
-                        .send_all(write_receiver.map_err(|_| websocket::WebSocketError::NoDataAvailable))
-                        .map(|_| ());
+    /// Create new WebSocket transport over an already established connection.
+    ///
+    /// The connection is any duplex stream of WebSocket messages, which allows running
+    /// the transport over custom connections or in-memory pipes.
+    pub fn with_stream<S>(stream: S, handle: &reactor::Handle) -> Self
+    where
+        S: Stream<Item = OwnedMessage, Error = websocket::WebSocketError>
+            + Sink<SinkItem = OwnedMessage, SinkError = websocket::WebSocketError>
+            + 'static,
+    {
+        let shared = Shared::default();
+        let (write_sender, write_receiver) = mpsc::unbounded();
 
-                    reader.join(writer)
-                })
-        };
+        handle.spawn(
+            run_connection(stream, shared.clone(), write_sender.clone(), write_receiver).map_err(|err| {
+                log::error!("WebSocketError: {:?}", err);
+            }),
+        );
 
-        handle.spawn(ws_future.map(|_| ()).map_err(|err| {
-            log::error!("WebSocketError: {:?}", err);
-        }));
+        Self::from_shared(None, shared, write_sender)
+    }
 
-        Ok(Self {
+    fn from_shared(url: Option<Url>, shared: Shared, write_sender: mpsc::UnboundedSender<OwnedMessage>) -> Self {
+        Self {
             id: Arc::new(atomic::AtomicUsize::new(1)),
             url,
-            pending,
-            subscriptions,
+            pending: shared.pending,
+            subscriptions: shared.subscriptions,
             write_sender,
-        })
+        }
     }
 
     fn send_request<F, O>(&self, id: RequestId, request: rpc::Request, extract: F) -> WsTask<F>
     where
-        F: Fn(Vec<Result<rpc::Value>>) -> O,
+        F: Fn(Vec<rpc::Output>) -> O,
     {
         let request = helpers::to_string(&request);
         log::debug!("[{}] Calling: {}", id, request);
@@ -179,8 +130,98 @@ impl WebSocket {
     }
 }
 
+/// State shared between the transport and the connection task.
+#[derive(Default, Clone)]
+struct Shared {
+    pending: Arc<Mutex<BTreeMap<RequestId, Pending>>>,
+    subscriptions: Arc<Mutex<BTreeMap<SubscriptionId, Subscription>>>,
+}
+
+/// Routes incoming messages to pending requests and subscriptions and writes outgoing ones.
+fn run_connection<S>(
+    duplex: S,
+    shared: Shared,
+    write_sender: mpsc::UnboundedSender<OwnedMessage>,
+    write_receiver: mpsc::UnboundedReceiver<OwnedMessage>,
+) -> impl Future<Item = (), Error = Error>
+where
+    S: Stream<Item = OwnedMessage, Error = websocket::WebSocketError>
+        + Sink<SinkItem = OwnedMessage, SinkError = websocket::WebSocketError>,
+{
+    let (sink, stream) = duplex.split();
+    let Shared { pending, subscriptions } = shared;
+
+    let reader = stream.from_err::<Error>().for_each(move |message| {
+        log::trace!("Message received: {:?}", message);
+
+        match message {
+            OwnedMessage::Close(e) => write_sender
+                .unbounded_send(OwnedMessage::Close(e))
+                .map_err(|_| Error::Transport("Error sending close message".into())),
+            OwnedMessage::Ping(d) => write_sender
+                .unbounded_send(OwnedMessage::Pong(d))
+                .map_err(|_| Error::Transport("Error sending pong message".into())),
+            OwnedMessage::Text(t) => {
+                if let Ok(notification) = helpers::to_notification_from_slice(t.as_bytes()) {
+                    if let rpc::Params::Map(params) = notification.params {
+                        let id = params.get("subscription");
+                        let result = params.get("result");
+
+                        if let (Some(&rpc::Value::String(ref id)), Some(result)) = (id, result) {
+                            let id: SubscriptionId = id.clone().into();
+                            if let Some(stream) = subscriptions.lock().get(&id) {
+                                return stream
+                                    .unbounded_send(result.clone())
+                                    .map_err(|_| Error::Transport("Error sending notification".into()));
+                            } else {
+                                log::warn!("Got notification for unknown subscription (id: {:?})", id);
+                            }
+                        } else {
+                            log::error!("Got unsupported notification (id: {:?})", id);
+                        }
+                    }
+
+                    return Ok(());
+                }
+
+                let response = helpers::to_response_from_slice(t.as_bytes());
+                let outputs = match response {
+                    Ok(rpc::Response::Single(output)) => vec![output],
+                    Ok(rpc::Response::Batch(outputs)) => outputs,
+                    _ => vec![],
+                };
+
+                let id = helpers::pending_request_id(&pending.lock(), &outputs);
+
+                if let rpc::Id::Num(num) = id {
+                    if let Some(request) = pending.lock().remove(&(num as usize)) {
+                        log::trace!("Responding to (id: {:?}) with {:?}", num, outputs);
+                        if let Err(err) = request.send(Ok(outputs)) {
+                            log::warn!("Sending a response to deallocated channel: {:?}", err);
+                        }
+                    } else {
+                        log::warn!("Got response for unknown request (id: {:?})", num);
+                    }
+                } else {
+                    log::warn!("Got unsupported response (id: {:?})", id);
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    });
+
+    let writer = sink
+        .sink_from_err()
+        .send_all(write_receiver.map_err(|_| websocket::WebSocketError::NoDataAvailable))
+        .map(|_| ());
+
+    reader.join(writer).map(|_| ())
+}
+
 impl Transport for WebSocket {
-    type Out = WsTask<fn(Vec<Result<rpc::Value>>) -> Result<rpc::Value>>;
+    type Out = WsTask<fn(Vec<rpc::Output>) -> Result<rpc::Value>>;
 
     fn prepare(&self, method: &str, params: Vec<rpc::Value>) -> (RequestId, rpc::Call) {
         let id = self.id.fetch_add(1, atomic::Ordering::AcqRel);
@@ -192,7 +233,7 @@ impl Transport for WebSocket {
     fn send(&self, id: RequestId, request: rpc::Call) -> Self::Out {
         self.send_request(id, rpc::Request::Single(request), |response| {
             match response.into_iter().next() {
-                Some(res) => res,
+                Some(output) => helpers::to_result_from_output(output),
                 None => Err(Error::InvalidResponse("Expected single, got batch.".into())),
             }
         })
@@ -200,16 +241,19 @@ impl Transport for WebSocket {
 }
 
 impl BatchTransport for WebSocket {
-    type Batch = WsTask<fn(Vec<Result<rpc::Value>>) -> Result<Vec<Result<rpc::Value>>>>;
+    type Batch = WsTask<Box<dyn Fn(Vec<rpc::Output>) -> Result<Vec<Result<rpc::Value>>> + Send>>;
 
     fn send_batch<T>(&self, requests: T) -> Self::Batch
     where
         T: IntoIterator<Item = (RequestId, rpc::Call)>,
     {
-        let mut it = requests.into_iter();
-        let (id, first) = it.next().map(|x| (x.0, Some(x.1))).unwrap_or_else(|| (0, None));
-        let requests = first.into_iter().chain(it.map(|x| x.1)).collect();
-        self.send_request(id, rpc::Request::Batch(requests), Ok)
+        let (ids, requests): (Vec<_>, Vec<_>) = requests.into_iter().unzip();
+        let id = ids.first().cloned().unwrap_or(0);
+        self.send_request(
+            id,
+            rpc::Request::Batch(requests),
+            Box::new(move |outputs| helpers::to_results_from_outputs_by_id(&ids, outputs)),
+        )
     }
 }
 
@@ -238,9 +282,77 @@ mod tests {
     use self::websocket::r#async::Server;
     use self::websocket::server::InvalidConnection;
     use super::WebSocket;
+    use crate::api::SubscriptionId;
     use crate::rpc;
-    use crate::Transport;
-    use futures::{Future, Sink, Stream};
+    use crate::{DuplexTransport, Transport};
+    use futures::sync::mpsc;
+    use futures::{Future, Poll, Sink, StartSend, Stream};
+
+    /// In-memory connection replaying scripted frames and recording written ones.
+    struct Pipe {
+        incoming: mpsc::UnboundedReceiver<OwnedMessage>,
+        outgoing: mpsc::UnboundedSender<OwnedMessage>,
+    }
+
+    impl Stream for Pipe {
+        type Item = OwnedMessage;
+        type Error = websocket::WebSocketError;
+
+        fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+            self.incoming
+                .poll()
+                .map_err(|_| websocket::WebSocketError::NoDataAvailable)
+        }
+    }
+
+    impl Sink for Pipe {
+        type SinkItem = OwnedMessage;
+        type SinkError = websocket::WebSocketError;
+
+        fn start_send(&mut self, item: Self::SinkItem) -> StartSend<Self::SinkItem, Self::SinkError> {
+            self.outgoing
+                .start_send(item)
+                .map_err(|_| websocket::WebSocketError::NoDataAvailable)
+        }
+
+        fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
+            self.outgoing
+                .poll_complete()
+                .map_err(|_| websocket::WebSocketError::NoDataAvailable)
+        }
+    }
+
+    #[test]
+    fn should_route_scripted_frames_from_stream() {
+        // given
+        let mut eloop = tokio_core::reactor::Core::new().unwrap();
+        let (frames, incoming) = mpsc::unbounded();
+        let (outgoing, written) = mpsc::unbounded();
+        let ws = WebSocket::with_stream(Pipe { incoming, outgoing }, &eloop.handle());
+        let notifications = ws.subscribe(&SubscriptionId::from("0x1".to_owned()));
+
+        for frame in &[
+            r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"subscription":"0x1","result":5}}"#,
+            r#"{"jsonrpc":"2.0","id":1,"result":"x"}"#,
+        ] {
+            frames.unbounded_send(OwnedMessage::Text(frame.to_string())).unwrap();
+        }
+
+        // when
+        let res = ws.execute("eth_accounts", vec![rpc::Value::String("1".into())]);
+
+        // then
+        assert_eq!(eloop.run(res), Ok(rpc::Value::String("x".into())));
+        let (notification, _) = eloop.run(notifications.into_future()).map_err(|(e, _)| e).unwrap();
+        assert_eq!(notification, Some(rpc::Value::from(5)));
+        let (request, _) = eloop.run(written.into_future()).map_err(|_| ()).unwrap();
+        assert_eq!(
+            request,
+            Some(OwnedMessage::Text(
+                r#"{"jsonrpc":"2.0","method":"eth_accounts","params":["1"],"id":1}"#.into()
+            ))
+        );
+    }
 
     #[test]
     fn should_send_a_request() {
@@ -0,0 +1,14 @@
+use crate::types::{H256, U256};
+
+/// Balance, nonce and code of an account at a given block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountState {
+    /// Balance in wei
+    pub balance: U256,
+    /// Number of transactions sent from the account
+    pub nonce: U256,
+    /// Keccak-256 hash of the account code (hash of empty data for externally owned accounts)
+    pub code_hash: H256,
+    /// Whether the account has code, i.e. is a contract
+    pub has_code: bool,
+}
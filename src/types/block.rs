@@ -1,5 +1,5 @@
 use crate::types::{Bytes, H160, H2048, H256, H64, U256, U64};
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// The block header type returned from RPC calls.
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -26,8 +26,8 @@ pub struct BlockHeader {
     pub receipts_root: H256,
     /// Block number. None if pending.
     pub number: Option<U64>,
-    /// Gas Used
-    #[serde(rename = "gasUsed")]
+    /// Gas Used (zero if not reported by the node)
+    #[serde(default, rename = "gasUsed", deserialize_with = "null_to_default")]
     pub gas_used: U256,
     /// Gas Limit
     #[serde(rename = "gasLimit")]
@@ -35,9 +35,9 @@ pub struct BlockHeader {
     /// Extra data
     #[serde(rename = "extraData")]
     pub extra_data: Bytes,
-    /// Logs bloom
+    /// Logs bloom. None if not reported by the node.
     #[serde(rename = "logsBloom")]
-    pub logs_bloom: H2048,
+    pub logs_bloom: Option<H2048>,
     /// Timestamp
     pub timestamp: U256,
     /// Difficulty
@@ -47,6 +47,12 @@ pub struct BlockHeader {
     pub mix_hash: Option<H256>,
     /// Nonce
     pub nonce: Option<H64>,
+    /// Base fee per unit of gas (if past London)
+    #[serde(rename = "baseFeePerGas")]
+    pub base_fee_per_gas: Option<U256>,
+    /// Withdrawals root hash (if past Shanghai)
+    #[serde(default, rename = "withdrawalsRoot", skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<H256>,
 }
 
 /// The block type returned from RPC calls.
@@ -75,8 +81,8 @@ pub struct Block<TX> {
     pub receipts_root: H256,
     /// Block number. None if pending.
     pub number: Option<U64>,
-    /// Gas Used
-    #[serde(rename = "gasUsed")]
+    /// Gas Used (zero if not reported by the node)
+    #[serde(default, rename = "gasUsed", deserialize_with = "null_to_default")]
     pub gas_used: U256,
     /// Gas Limit
     #[serde(rename = "gasLimit")]
@@ -84,9 +90,9 @@ pub struct Block<TX> {
     /// Extra data
     #[serde(rename = "extraData")]
     pub extra_data: Bytes,
-    /// Logs bloom
+    /// Logs bloom. None if not reported by the node.
     #[serde(rename = "logsBloom")]
-    pub logs_bloom: H2048,
+    pub logs_bloom: Option<H2048>,
     /// Timestamp
     pub timestamp: U256,
     /// Difficulty
@@ -108,6 +114,119 @@ pub struct Block<TX> {
     pub mix_hash: Option<H256>,
     /// Nonce
     pub nonce: Option<H64>,
+    /// Base fee per unit of gas (if past London)
+    #[serde(rename = "baseFeePerGas")]
+    pub base_fee_per_gas: Option<U256>,
+    /// Withdrawals root hash (if past Shanghai)
+    #[serde(default, rename = "withdrawalsRoot", skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<H256>,
+    /// Validator withdrawals (if past Shanghai)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Total blob gas used by the transactions in the block (if past Cancun)
+    #[serde(default, rename = "blobGasUsed", skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<U64>,
+    /// Blob gas in excess of the target, used to price blobs (if past Cancun)
+    #[serde(default, rename = "excessBlobGas", skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<U64>,
+}
+
+impl<TX> Block<TX> {
+    /// Returns the price of a unit of blob gas in the block (if past Cancun),
+    /// given the blob schedule of the fork the block belongs to.
+    pub fn blob_base_fee(&self, schedule: BlobSchedule) -> Option<U256> {
+        self.excess_blob_gas
+            .map(|excess_blob_gas| blob_base_fee(excess_blob_gas, schedule))
+    }
+
+    /// Returns the excess blob gas of the next block (if past Cancun),
+    /// given the blob schedule of the fork the next block belongs to.
+    ///
+    /// Pass it to `blob_base_fee` to estimate the blob base fee of the next block.
+    pub fn next_excess_blob_gas(&self, schedule: BlobSchedule) -> Option<U64> {
+        let excess = self.excess_blob_gas?;
+        let used = self.blob_gas_used.unwrap_or_default();
+        let target = U64::from(schedule.target_blob_gas_per_block);
+        Some(excess.saturating_add(used).saturating_sub(target))
+    }
+}
+
+/// Blob gas parameters, which change with hard forks raising the number of blobs per block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlobSchedule {
+    /// Blob gas targeted per block
+    pub target_blob_gas_per_block: u64,
+    /// Controls the maximal rate of change of the blob base fee
+    pub base_fee_update_fraction: u64,
+}
+
+impl BlobSchedule {
+    /// Cancun, targeting three blobs of 2^17 gas each (EIP-4844).
+    pub const CANCUN: BlobSchedule = BlobSchedule {
+        target_blob_gas_per_block: 393_216,
+        base_fee_update_fraction: 3_338_477,
+    };
+    /// Prague, targeting six blobs of 2^17 gas each (EIP-7691).
+    pub const PRAGUE: BlobSchedule = BlobSchedule {
+        target_blob_gas_per_block: 786_432,
+        base_fee_update_fraction: 5_007_716,
+    };
+}
+
+/// Minimal price of a unit of blob gas, in wei.
+const MIN_BLOB_BASE_FEE: u64 = 1;
+
+/// Computes the price of a unit of blob gas given the excess blob gas of a block,
+/// as specified by EIP-4844, using the update fraction of `schedule`.
+///
+/// Saturates for excess blob gas far beyond anything reachable on chain, where the
+/// price doesn't fit into `U256`.
+pub fn blob_base_fee(excess_blob_gas: U64, schedule: BlobSchedule) -> U256 {
+    fake_exponential(
+        MIN_BLOB_BASE_FEE.into(),
+        excess_blob_gas.as_u64().into(),
+        schedule.base_fee_update_fraction.into(),
+    )
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
+fn fake_exponential(factor: U256, numerator: U256, denominator: U256) -> U256 {
+    let mut i = U256::one();
+    let mut output = U256::zero();
+    let mut accumulator = factor.saturating_mul(denominator);
+    while !accumulator.is_zero() {
+        output = output.saturating_add(accumulator);
+        // the series would only converge after about `numerator / denominator` more terms
+        if output == U256::max_value() {
+            break;
+        }
+        accumulator = accumulator.saturating_mul(numerator) / denominator.saturating_mul(i);
+        i += U256::one();
+    }
+    output / denominator
+}
+
+/// A validator withdrawal from the beacon chain (EIP-4895).
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Withdrawal {
+    /// Monotonically increasing index of the withdrawal
+    pub index: U64,
+    /// Index of the validator the withdrawal is for
+    #[serde(rename = "validatorIndex")]
+    pub validator_index: U64,
+    /// Recipient address
+    pub address: H160,
+    /// Withdrawn amount in Gwei
+    pub amount: U64,
+}
+
+/// Deserializes `null` as the default value, some nodes report `null` for fields of historical blocks.
+fn null_to_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
 }
 
 /// Block Number
@@ -119,6 +238,8 @@ pub enum BlockNumber {
     Earliest,
     /// Pending block (not yet part of the blockchain)
     Pending,
+    /// Latest block finalized by the consensus layer (post-merge chains only)
+    Finalized,
     /// Block by number from canon chain
     Number(U64),
 }
@@ -139,6 +260,7 @@ impl Serialize for BlockNumber {
             BlockNumber::Latest => serializer.serialize_str("latest"),
             BlockNumber::Earliest => serializer.serialize_str("earliest"),
             BlockNumber::Pending => serializer.serialize_str("pending"),
+            BlockNumber::Finalized => serializer.serialize_str("finalized"),
         }
     }
 }
@@ -181,3 +303,129 @@ impl From<H256> for BlockId {
         BlockId::Hash(hash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{blob_base_fee, BlobSchedule, Block, BlockHeader, Withdrawal};
+    use crate::types::{H160, H256, U256, U64};
+    use serde_json;
+
+    const SPARSE_BLOCK: &str = r#"{
+        "hash": "0x8a1dbd9f7f9b5fc4d14cf3b6d7f2c1e6d7c8b5a7e4b3c2d1e0f9a8b7c6d5e4f3",
+        "parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+        "miner": "0x0000000000000000000000000000000000000000",
+        "stateRoot": "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+        "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+        "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+        "number": "0x1",
+        "gasUsed": null,
+        "gasLimit": "0x1388",
+        "extraData": "0x",
+        "logsBloom": null,
+        "timestamp": "0x55ba4224",
+        "difficulty": "0x400000000",
+        "totalDifficulty": "0x400000000",
+        "uncles": [],
+        "transactions": [],
+        "size": null,
+        "mixHash": null,
+        "nonce": null
+    }"#;
+
+    #[test]
+    fn should_deserialize_block_with_null_fields() {
+        let block: Block<H256> = serde_json::from_str(SPARSE_BLOCK).unwrap();
+        let header: BlockHeader = serde_json::from_str(SPARSE_BLOCK).unwrap();
+
+        assert_eq!(block.gas_used, U256::zero());
+        assert_eq!(block.logs_bloom, None);
+        assert_eq!(header.gas_used, U256::zero());
+        assert_eq!(header.logs_bloom, None);
+    }
+
+    #[test]
+    fn should_deserialize_block_with_withdrawals() {
+        let mut json: serde_json::Value = serde_json::from_str(SPARSE_BLOCK).unwrap();
+        json["withdrawalsRoot"] = "0x7a4ecf19774d15cf9c15adf0dd8e8a250c128b26c9e2ab2a08d6c9c8ffbd104f".into();
+        json["withdrawals"] = serde_json::json!([{
+            "index": "0xc4d6f",
+            "validatorIndex": "0x8b2d0",
+            "address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f",
+            "amount": "0xe2f1c9",
+        }]);
+
+        let block: Block<H256> = serde_json::from_value(json.clone()).unwrap();
+        let header: BlockHeader = serde_json::from_value(json).unwrap();
+
+        let withdrawals_root = Some(
+            "7a4ecf19774d15cf9c15adf0dd8e8a250c128b26c9e2ab2a08d6c9c8ffbd104f"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(block.withdrawals_root, withdrawals_root);
+        assert_eq!(header.withdrawals_root, withdrawals_root);
+        assert_eq!(
+            block.withdrawals,
+            Some(vec![Withdrawal {
+                index: 0xc4d6f.into(),
+                validator_index: 0x8b2d0.into(),
+                address: "b9d7934878b5fb9610b3fe8a5e441e8fad7e293f".parse::<H160>().unwrap(),
+                amount: 0xe2f1c9.into(),
+            }])
+        );
+        let sparse: Block<H256> = serde_json::from_str(SPARSE_BLOCK).unwrap();
+        assert_eq!(sparse.withdrawals, None);
+        let serialized = serde_json::to_value(&sparse).unwrap();
+        assert_eq!(serialized.get("withdrawalsRoot"), None);
+        assert_eq!(serialized.get("withdrawals"), None);
+    }
+
+    #[test]
+    fn should_deserialize_block_with_blob_gas() {
+        let mut json: serde_json::Value = serde_json::from_str(SPARSE_BLOCK).unwrap();
+        json["blobGasUsed"] = "0x60000".into();
+        json["excessBlobGas"] = "0x1fd6000".into();
+
+        let block: Block<H256> = serde_json::from_value(json).unwrap();
+
+        assert_eq!(block.blob_gas_used, Some(0x60000.into()));
+        assert_eq!(block.excess_blob_gas, Some(0x1fd6000.into()));
+        assert_eq!(
+            block.blob_base_fee(BlobSchedule::CANCUN),
+            Some(blob_base_fee(0x1fd6000.into(), BlobSchedule::CANCUN))
+        );
+        assert_eq!(block.next_excess_blob_gas(BlobSchedule::CANCUN), Some(0x1fd6000.into()));
+        let full = Block {
+            blob_gas_used: Some(0xc0000.into()),
+            ..block.clone()
+        };
+        assert_eq!(full.next_excess_blob_gas(BlobSchedule::CANCUN), Some(0x2036000.into()));
+        assert_eq!(full.next_excess_blob_gas(BlobSchedule::PRAGUE), Some(0x1fd6000.into()));
+        assert_eq!(block.next_excess_blob_gas(BlobSchedule::PRAGUE), Some(0x1f76000.into()));
+        let sparse: Block<H256> = serde_json::from_str(SPARSE_BLOCK).unwrap();
+        assert_eq!(sparse.blob_base_fee(BlobSchedule::CANCUN), None);
+        assert_eq!(sparse.next_excess_blob_gas(BlobSchedule::CANCUN), None);
+    }
+
+    #[test]
+    fn should_compute_blob_base_fee() {
+        let cancun = BlobSchedule::CANCUN;
+        assert_eq!(blob_base_fee(0.into(), cancun), 1.into());
+        assert_eq!(blob_base_fee(3_338_477.into(), cancun), 2.into());
+        assert_eq!(blob_base_fee(33_384_770.into(), cancun), 22_026.into());
+        assert_eq!(blob_base_fee(78_643_200.into(), cancun), 17_002_220_575u64.into());
+        let prague = BlobSchedule::PRAGUE;
+        assert_eq!(blob_base_fee(0.into(), prague), 1.into());
+        assert_eq!(blob_base_fee(5_007_716.into(), prague), 2.into());
+        assert_eq!(blob_base_fee(50_077_160.into(), prague), 22_026.into());
+    }
+
+    #[test]
+    fn should_saturate_blob_base_fee_of_huge_excess_blob_gas() {
+        assert_eq!(
+            blob_base_fee(U64::max_value(), BlobSchedule::CANCUN),
+            U256::max_value() / U256::from(3_338_477)
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use rustc_hex::{FromHex, ToHex};
+use rustc_hex::FromHex;
 use serde::de::{Error, Unexpected, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
@@ -18,9 +18,17 @@ impl Serialize for Bytes {
     where
         S: Serializer,
     {
-        let mut serialized = "0x".to_owned();
-        serialized.push_str(self.0.to_hex::<String>().as_ref());
-        serializer.serialize_str(serialized.as_ref())
+        const CHARS: &[u8; 16] = b"0123456789abcdef";
+
+        // Encodes into a single buffer of the exact size, large payloads are
+        // neither copied into intermediate strings nor reallocated while growing.
+        let mut serialized = String::with_capacity(2 + self.0.len() * 2);
+        serialized.push_str("0x");
+        for byte in &self.0 {
+            serialized.push(CHARS[(byte >> 4) as usize] as char);
+            serialized.push(CHARS[(byte & 0xf) as usize] as char);
+        }
+        serializer.serialize_str(&serialized)
     }
 }
 
@@ -61,3 +69,17 @@ impl<'a> Visitor<'a> for BytesVisitor {
         self.visit_str(value.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Bytes;
+
+    #[test]
+    fn should_serialize_bytes() {
+        assert_eq!(serde_json::to_string(&Bytes(vec![])).unwrap(), r#""0x""#);
+        assert_eq!(
+            serde_json::to_string(&Bytes(vec![0x00, 0x0f, 0xab, 0xff])).unwrap(),
+            r#""0x000fabff""#
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use crate::types::{Address, Bytes, U256};
+use serde::{Deserialize, Serialize};
+
+/// A call of the call tree returned by `debug_traceTransaction` with geth's `callTracer`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CallFrame {
+    /// Call type, e.g. `CALL`, `STATICCALL`, `DELEGATECALL` or `CREATE`
+    #[serde(rename = "type")]
+    pub call_type: String,
+    /// Caller
+    pub from: Address,
+    /// Callee (`None` if contract creation failed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// Transfered value (`None` for calls which can't transfer value)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    /// Gas provided to the call
+    pub gas: U256,
+    /// Gas used by the call, including its subcalls
+    #[serde(rename = "gasUsed")]
+    pub gas_used: U256,
+    /// Call data, or init code for contract creations
+    pub input: Bytes,
+    /// Returned data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+    /// Error message if the call failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Calls made by this call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_deserialize_nested_call_frames() {
+        let frame: CallFrame = serde_json::from_str(
+            r#"{
+                "type": "CALL",
+                "from": "0x0000000000000000000000000000000000000001",
+                "to": "0x0000000000000000000000000000000000000002",
+                "value": "0x1",
+                "gas": "0x7a120",
+                "gasUsed": "0x5208",
+                "input": "0x12345678",
+                "output": "0x",
+                "calls": [
+                    {
+                        "type": "STATICCALL",
+                        "from": "0x0000000000000000000000000000000000000002",
+                        "to": "0x0000000000000000000000000000000000000003",
+                        "gas": "0x1388",
+                        "gasUsed": "0x1f4",
+                        "input": "0x",
+                        "error": "execution reverted"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(frame.call_type, "CALL");
+        assert_eq!(frame.to, Some(Address::from_low_u64_be(2)));
+        assert_eq!(frame.value, Some(1.into()));
+        assert_eq!(frame.gas_used, 0x5208.into());
+        assert_eq!(frame.input, Bytes(vec![0x12, 0x34, 0x56, 0x78]));
+        assert_eq!(frame.output, Some(Bytes::default()));
+        assert_eq!(frame.calls.len(), 1);
+
+        let inner = &frame.calls[0];
+        assert_eq!(inner.call_type, "STATICCALL");
+        assert_eq!(inner.from, Address::from_low_u64_be(2));
+        assert_eq!(inner.value, None);
+        assert_eq!(inner.output, None);
+        assert_eq!(inner.error, Some("execution reverted".into()));
+        assert!(inner.calls.is_empty());
+    }
+}
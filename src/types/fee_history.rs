@@ -0,0 +1,45 @@
+use crate::types::{U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// The fee history of a range of blocks, as returned by `eth_feeHistory`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeHistory {
+    /// Lowest number block of the returned range.
+    #[serde(rename = "oldestBlock")]
+    pub oldest_block: U64,
+    /// Base fees per gas of the blocks, including the next block after the newest of the range.
+    #[serde(rename = "baseFeePerGas")]
+    pub base_fee_per_gas: Vec<U256>,
+    /// Ratios of gas used to the gas limit of the blocks.
+    #[serde(rename = "gasUsedRatio")]
+    pub gas_used_ratio: Vec<f64>,
+    /// Effective priority fees per gas at the requested percentiles for each block
+    /// (None when no percentiles were requested).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reward: Option<Vec<Vec<U256>>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FeeHistory;
+    use serde_json;
+
+    #[test]
+    fn should_deserialize_fee_history_without_reward() {
+        let fee_history = r#"{
+            "oldestBlock": "0xfab8ac",
+            "baseFeePerGas": ["0x3da8e7618", "0x3e1ba3b1b"],
+            "gasUsedRatio": [0.5290747666666666]
+        }"#;
+
+        let fee_history: FeeHistory = serde_json::from_str(fee_history).unwrap();
+
+        assert_eq!(fee_history.oldest_block, 0xfab8ac.into());
+        assert_eq!(
+            fee_history.base_fee_per_gas,
+            vec![0x3da8e7618u64.into(), 0x3e1ba3b1bu64.into()]
+        );
+        assert_eq!(fee_history.gas_used_ratio, vec![0.5290747666666666]);
+        assert_eq!(fee_history.reward, None);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::Error;
 use crate::types::{BlockNumber, Bytes, Index, H160, H256, U256, U64};
 use ethabi;
 use serde::{Deserialize, Serialize, Serializer};
@@ -83,6 +84,9 @@ pub struct Filter {
     /// To Block
     #[serde(rename = "toBlock", skip_serializing_if = "Option::is_none")]
     to_block: Option<BlockNumber>,
+    /// Block Hash
+    #[serde(rename = "blockHash", skip_serializing_if = "Option::is_none")]
+    block_hash: Option<H256>,
     /// Address
     #[serde(skip_serializing_if = "Option::is_none")]
     address: Option<ValueOrArray<H160>>,
@@ -94,6 +98,32 @@ pub struct Filter {
     limit: Option<usize>,
 }
 
+impl Filter {
+    /// Splits the filter into filters with at most `max_addresses` addresses each.
+    ///
+    /// Nodes limit the size of a filter, so monitoring a large set of addresses
+    /// may require splitting it into multiple `eth_getLogs` queries.
+    /// A `max_addresses` of 0 means no limit, the filter is returned unsplit.
+    ///
+    /// Each filter keeps the `limit` of the original one, so all queries together
+    /// may return up to `limit` logs per filter.
+    pub fn split_addresses(&self, max_addresses: usize) -> Vec<Filter> {
+        match self.address {
+            Some(ValueOrArray(ref addresses)) if max_addresses > 0 && addresses.len() > max_addresses => addresses
+                .chunks(max_addresses)
+                .map(|chunk| Filter {
+                    address: Some(ValueOrArray(chunk.to_vec())),
+                    ..self.clone()
+                })
+                .collect(),
+            _ => vec![self.clone()],
+        }
+    }
+}
+
+/// Maximal number of topics of a log: the event signature and up to 3 indexed parameters.
+const MAX_TOPICS: usize = 4;
+
 /// Filter Builder
 #[derive(Default, Clone)]
 pub struct FilterBuilder {
@@ -102,14 +132,30 @@ pub struct FilterBuilder {
 
 impl FilterBuilder {
     /// Sets from block
+    ///
+    /// Clears the block hash, since a filter can't have both.
     pub fn from_block(mut self, block: BlockNumber) -> Self {
         self.filter.from_block = Some(block);
+        self.filter.block_hash = None;
         self
     }
 
     /// Sets to block
+    ///
+    /// Clears the block hash, since a filter can't have both.
     pub fn to_block(mut self, block: BlockNumber) -> Self {
         self.filter.to_block = Some(block);
+        self.filter.block_hash = None;
+        self
+    }
+
+    /// Matches only logs of the block with given hash (EIP-234).
+    ///
+    /// Clears the block range, since a filter can't have both.
+    pub fn block_hash(mut self, hash: H256) -> Self {
+        self.filter.block_hash = Some(hash);
+        self.filter.from_block = None;
+        self.filter.to_block = None;
         self
     }
 
@@ -119,7 +165,45 @@ impl FilterBuilder {
         self
     }
 
+    /// Addresses given as raw bytes, e.g. parsed from user input.
+    ///
+    /// Fails with a descriptive error instead of sending the request if any
+    /// of the addresses isn't exactly 20 bytes long.
+    pub fn try_address(self, address: Vec<Bytes>) -> Result<Self, Error> {
+        let address = address
+            .into_iter()
+            .map(|address| fixed_length(address, "address", H160::len_bytes()).map(|a| H160::from_slice(&a)))
+            .collect::<Result<_, _>>()?;
+        Ok(self.address(address))
+    }
+
+    /// Topics given as raw bytes, e.g. parsed from user input.
+    ///
+    /// Fails with a descriptive error instead of sending the request if any
+    /// of the topics isn't exactly 32 bytes long.
+    pub fn try_topics(
+        self,
+        topic1: Option<Vec<Bytes>>,
+        topic2: Option<Vec<Bytes>>,
+        topic3: Option<Vec<Bytes>>,
+        topic4: Option<Vec<Bytes>>,
+    ) -> Result<Self, Error> {
+        let topic = |topic: Option<Vec<Bytes>>| -> Result<Option<Vec<H256>>, Error> {
+            topic
+                .map(|topic| {
+                    topic
+                        .into_iter()
+                        .map(|t| fixed_length(t, "topic", H256::len_bytes()).map(|t| H256::from_slice(&t)))
+                        .collect()
+                })
+                .transpose()
+        };
+        Ok(self.topics(topic(topic1)?, topic(topic2)?, topic(topic3)?, topic(topic4)?))
+    }
+
     /// Topics
+    ///
+    /// Replaces all topic positions, including topic0 previously set with `event_signature`.
     pub fn topics(
         mut self,
         topic1: Option<Vec<H256>>,
@@ -139,6 +223,52 @@ impl FilterBuilder {
         self
     }
 
+    /// Matches only logs of the event with given signature hash (topic0).
+    ///
+    /// Indexed topics already set are kept.
+    pub fn event_signature(mut self, signature: H256) -> Self {
+        let mut topics = self.filter.topics.take().unwrap_or_default();
+        let signature = Some(ValueOrArray(vec![signature]));
+        if topics.is_empty() {
+            topics.push(signature);
+        } else {
+            topics[0] = signature;
+        }
+        self.filter.topics = Some(topics);
+        self
+    }
+
+    /// Indexed topics, i.e. topic positions following the event signature.
+    ///
+    /// Keeps topic0 set with `event_signature`. Since a log has at most 4 topics,
+    /// fails if more than 3 indexed topics are given.
+    pub fn try_indexed_topics(mut self, indexed: Vec<Option<Vec<H256>>>) -> Result<Self, Error> {
+        if indexed.len() >= MAX_TOPICS {
+            return Err(Error::Decoder(format!(
+                "Too many topics in filter: expected at most {}, got {}",
+                MAX_TOPICS,
+                indexed.len() + 1
+            )));
+        }
+
+        let signature = self
+            .filter
+            .topics
+            .take()
+            .and_then(|topics| topics.into_iter().next())
+            .and_then(|topic| topic);
+        let mut topics = Some(signature)
+            .into_iter()
+            .chain(indexed.into_iter().map(|option| option.map(ValueOrArray)))
+            .rev()
+            .skip_while(Option::is_none)
+            .collect::<Vec<_>>();
+        topics.reverse();
+
+        self.filter.topics = Some(topics);
+        Ok(self)
+    }
+
     /// Sets the topics according to the given `ethabi` topic filter
     pub fn topic_filter(self, topic_filter: ethabi::TopicFilter) -> Self {
         self.topics(
@@ -161,6 +291,20 @@ impl FilterBuilder {
     }
 }
 
+/// Checks that an address or topic given as raw bytes has exactly `len` bytes.
+fn fixed_length(bytes: Bytes, kind: &str, len: usize) -> Result<Vec<u8>, Error> {
+    if bytes.0.len() == len {
+        Ok(bytes.0)
+    } else {
+        Err(Error::Decoder(format!(
+            "Invalid {} length in filter: expected {} bytes, got {}",
+            kind,
+            len,
+            bytes.0.len()
+        )))
+    }
+}
+
 /// Converts a `Topic` to an equivalent `Option<Vec<T>>`, suitable for `FilterBuilder::topics`
 fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
     match topic {
@@ -172,8 +316,9 @@ fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
 
 #[cfg(test)]
 mod tests {
+    use crate::error::Error;
     use crate::types::{
-        log::{Bytes, FilterBuilder, Log},
+        log::{Bytes, FilterBuilder, Log, ValueOrArray},
         Address, H160, H256,
     };
     use ethabi;
@@ -287,4 +432,77 @@ mod tests {
             .build();
         assert_eq!(filter0, filter1);
     }
+
+    #[test]
+    fn should_split_filter_addresses() {
+        let addresses: Vec<_> = (1..=5).map(H160::from_low_u64_be).collect();
+        let filter = FilterBuilder::default().address(addresses.clone()).limit(10).build();
+
+        let filters = filter.split_addresses(3);
+
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[0].address, Some(ValueOrArray(addresses[..3].to_vec())));
+        assert_eq!(filters[1].address, Some(ValueOrArray(addresses[3..].to_vec())));
+        assert_eq!(filters[1].limit, Some(10));
+        assert_eq!(filter.split_addresses(5), vec![filter.clone()]);
+        assert_eq!(filter.split_addresses(0), vec![filter]);
+    }
+
+    #[test]
+    fn should_validate_raw_topics_and_addresses() {
+        let topic = H256::from_low_u64_be(1);
+        let address = H160::from_low_u64_be(2);
+
+        let filter = FilterBuilder::default()
+            .try_address(vec![Bytes(address.as_bytes().to_vec())])
+            .unwrap()
+            .try_topics(Some(vec![Bytes(topic.as_bytes().to_vec())]), None, None, None)
+            .unwrap()
+            .build();
+        assert_eq!(
+            filter,
+            FilterBuilder::default()
+                .address(vec![address])
+                .topics(Some(vec![topic]), None, None, None)
+                .build()
+        );
+
+        let err = FilterBuilder::default()
+            .try_topics(None, Some(vec![Bytes(vec![1; 20])]), None, None)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            Error::Decoder("Invalid topic length in filter: expected 32 bytes, got 20".into())
+        );
+        assert!(FilterBuilder::default().try_address(vec![Bytes(vec![1; 32])]).is_err());
+    }
+
+    #[test]
+    fn should_combine_event_signature_with_indexed_topics() {
+        let signature = H256::from_low_u64_be(1);
+        let indexed = H256::from_low_u64_be(2);
+
+        let filter = FilterBuilder::default()
+            .try_indexed_topics(vec![None, Some(vec![indexed])])
+            .unwrap()
+            .event_signature(signature)
+            .build();
+        assert_eq!(
+            filter,
+            FilterBuilder::default()
+                .topics(Some(vec![signature]), None, Some(vec![indexed]), None)
+                .build()
+        );
+
+        let err = FilterBuilder::default()
+            .event_signature(signature)
+            .try_indexed_topics(vec![None, None, None, Some(vec![indexed])])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            Error::Decoder("Too many topics in filter: expected at most 4, got 5".into())
+        );
+    }
 }
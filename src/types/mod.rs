@@ -1,11 +1,16 @@
 //! Web3 Types
 
+mod account_state;
 mod block;
 mod bytes;
+mod call_frame;
+mod fee_history;
 mod log;
 mod parity_peers;
+mod proof;
 mod recovery;
 mod signed;
+mod storage;
 mod sync_state;
 mod trace_filtering;
 mod traces;
@@ -15,14 +20,19 @@ mod transaction_request;
 mod uint;
 mod work;
 
-pub use self::block::{Block, BlockHeader, BlockId, BlockNumber};
+pub use self::account_state::AccountState;
+pub use self::block::{blob_base_fee, BlobSchedule, Block, BlockHeader, BlockId, BlockNumber, Withdrawal};
 pub use self::bytes::Bytes;
+pub use self::call_frame::CallFrame;
+pub use self::fee_history::FeeHistory;
 pub use self::log::{Filter, FilterBuilder, Log};
 pub use self::parity_peers::{
     EthProtocolInfo, ParityPeerInfo, ParityPeerType, PeerNetworkInfo, PeerProtocolsInfo, PipProtocolInfo,
 };
+pub use self::proof::{AccountProof, Proof, StorageProof};
 pub use self::recovery::{Recovery, RecoveryMessage};
-pub use self::signed::{SignedData, SignedTransaction, TransactionParameters};
+pub use self::signed::{DecodedTransaction, SignedData, SignedTransaction, TransactionParameters, ValidationError};
+pub use self::storage::{StorageEntry, StorageRange};
 pub use self::sync_state::{SyncInfo, SyncState};
 pub use self::trace_filtering::{
     Action, ActionType, Call, CallResult, CallType, Create, CreateResult, Res, Reward, RewardType, Suicide, Trace,
@@ -34,8 +44,14 @@ pub use self::traces::{
 };
 pub use self::transaction::{RawTransaction, Receipt as TransactionReceipt, Transaction};
 pub use self::transaction_id::TransactionId;
-pub use self::transaction_request::{CallRequest, TransactionCondition, TransactionRequest};
-pub use self::uint::{H128, H160, H2048, H256, H512, H520, H64, U128, U256, U64};
+pub use self::transaction_request::{
+    AccessList, AccessListComparison, AccessListItem, AccessListWithGasUsed, CallRequest, CallSender,
+    ExplicitCondition, TransactionCondition, TransactionRequest,
+};
+pub(crate) use self::transaction_request::{ACCESS_LIST_ADDRESS_COST, ACCESS_LIST_STORAGE_KEY_COST};
+#[cfg(feature = "rand")]
+pub use self::uint::Random;
+pub use self::uint::{format_units, H128, H160, H2048, H256, H512, H520, H64, U128, U256, U64};
 pub use self::work::Work;
 
 /// Address
@@ -0,0 +1,71 @@
+use crate::types::{Address, Bytes, H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// Merkle proof of an account and some of its storage, as returned by `eth_getProof` (EIP-1186).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Proof {
+    /// Address of the account
+    pub address: Address,
+    /// State of the account and its proof
+    #[serde(flatten)]
+    pub account: AccountProof,
+    /// Proofs of the requested storage entries
+    #[serde(rename = "storageProof")]
+    pub storage_proof: Vec<StorageProof>,
+}
+
+/// Merkle proof of the state of a single account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountProof {
+    /// Balance of the account
+    pub balance: U256,
+    /// Keccak-256 hash of the account code
+    #[serde(rename = "codeHash")]
+    pub code_hash: H256,
+    /// Nonce of the account
+    pub nonce: U256,
+    /// Root of the account storage trie
+    #[serde(rename = "storageHash")]
+    pub storage_hash: H256,
+    /// RLP encoded nodes of the state trie from the state root to the account
+    #[serde(rename = "accountProof")]
+    pub account_proof: Vec<Bytes>,
+}
+
+/// Merkle proof of a single storage entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageProof {
+    /// Storage key
+    pub key: U256,
+    /// Storage value
+    pub value: U256,
+    /// RLP encoded nodes of the storage trie from the storage root to the entry
+    pub proof: Vec<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Proof;
+    use crate::signing::keccak256;
+
+    // State trie holding only account 0x..0123 (nonce 1, 1 ether, no code, slot 0 = 42).
+    const PROOF: &str = include_str!("./res/proof.json");
+
+    #[test]
+    fn should_deserialize_proof_linked_to_storage_hash() {
+        // given
+        let proof: Proof = serde_json::from_str(PROOF).unwrap();
+
+        // when
+        let storage_leaf = &proof.storage_proof[0].proof[0].0;
+        let account_leaf = &proof.account.account_proof[0].0;
+
+        // then
+        assert_eq!(keccak256(storage_leaf), proof.account.storage_hash.0);
+        assert!(account_leaf.ends_with(&proof.account.code_hash.0));
+        assert_eq!(proof.account.nonce, 1.into());
+        assert_eq!(proof.storage_proof[0].value, 0x2a.into());
+        let expected: serde_json::Value = serde_json::from_str(PROOF).unwrap();
+        assert_eq!(serde_json::to_value(&proof).unwrap(), expected);
+    }
+}
@@ -70,6 +70,16 @@ impl Recovery {
         RecoveryId::from_i32(standard_v)
     }
 
+    /// Retrieves the chain ID from the replay protected `v` value.
+    ///
+    /// Returns `None` for legacy signatures where `v` is `27` or `28`.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self.v {
+            v if v >= 35 => Some((v - 35) / 2),
+            _ => None,
+        }
+    }
+
     /// Retrieves the recovery signature.
     pub fn as_signature(&self) -> Result<RecoverableSignature, Secp256k1Error> {
         let recovery_id = self.recovery_id()?;
@@ -204,4 +214,18 @@ mod tests {
             expected_signature
         );
     }
+
+    #[test]
+    fn recovery_chain_id() {
+        let recovery = |v| Recovery::new(H256::zero(), v, H256::zero(), H256::zero());
+
+        assert_eq!(recovery(27).chain_id(), None);
+        assert_eq!(recovery(28).chain_id(), None);
+        assert_eq!(recovery(35).chain_id(), Some(0));
+        assert_eq!(recovery(36).chain_id(), Some(0));
+        assert_eq!(recovery(37).chain_id(), Some(1));
+        assert_eq!(recovery(38).chain_id(), Some(1));
+        assert_eq!(recovery(35).recovery_id(), RecoveryId::from_i32(0));
+        assert_eq!(recovery(36).recovery_id(), RecoveryId::from_i32(1));
+    }
 }
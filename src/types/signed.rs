@@ -1,4 +1,4 @@
-use crate::types::{Address, Bytes, CallRequest, H256, U256};
+use crate::types::{AccessList, Address, Bytes, CallRequest, H256, U256};
 use serde::{Deserialize, Serialize};
 
 /// Struct representing signed data returned from `Accounts::sign` method.
@@ -27,6 +27,12 @@ pub struct SignedData {
 /// gas price will be used for `gas_price`, and the current network ID will be
 /// used for the `chain_id`.
 ///
+/// Setting `max_fee_per_gas` or `max_priority_fee_per_gas` produces an EIP-1559
+/// (type 2) transaction, which can't have a `gas_price`. A missing priority fee
+/// is filled with the node's suggestion (`eth_maxPriorityFeePerGas`), and a missing
+/// max fee leaves room for the base fee of the latest block to double on top of it.
+/// Otherwise setting `access_list` produces an EIP-2930 (type 1) transaction.
+///
 /// It is worth noting that the chain ID is not equivalent to the network ID.
 /// They happen to be the same much of the time but it is recommended to set
 /// this for signing transactions.
@@ -47,12 +53,24 @@ pub struct TransactionParameters {
     pub gas: U256,
     /// Gas price (None for estimated gas price)
     pub gas_price: Option<U256>,
+    /// Max fee per gas, EIP-1559 (None for legacy or twice the base fee plus the priority fee)
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas, EIP-1559 (None for legacy or the suggested priority fee)
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Addresses and storage keys the transaction is going to access, EIP-2930 (None for no access list)
+    pub access_list: Option<AccessList>,
     /// Transfered value
     pub value: U256,
     /// Data
     pub data: Bytes,
     /// The chain ID (None for network ID)
     pub chain_id: Option<u64>,
+    /// Sign a legacy transaction without EIP-155 replay protection, even if a chain ID
+    /// or EIP-1559 fees are known
+    pub force_legacy: bool,
+    /// Allow `Accounts::send` to transfer value to the zero address, which is
+    /// otherwise rejected since the funds would be burned
+    pub allow_zero_recipient: bool,
 }
 
 /// The default fas for transactions.
@@ -70,9 +88,14 @@ impl Default for TransactionParameters {
             to: None,
             gas: TRANSACTION_DEFAULT_GAS,
             gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: None,
             value: U256::zero(),
             data: Bytes::default(),
             chain_id: None,
+            force_legacy: false,
+            allow_zero_recipient: false,
         }
     }
 }
@@ -90,9 +113,14 @@ impl From<CallRequest> for TransactionParameters {
             to,
             gas: call.gas.unwrap_or(TRANSACTION_DEFAULT_GAS),
             gas_price: call.gas_price,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            access_list: call.access_list,
             value: call.value.unwrap_or_default(),
             data: call.data.unwrap_or_default(),
             chain_id: None,
+            force_legacy: false,
+            allow_zero_recipient: false,
         }
     }
 }
@@ -106,6 +134,7 @@ impl Into<CallRequest> for TransactionParameters {
             gas_price: self.gas_price,
             value: Some(self.value),
             data: Some(self.data),
+            access_list: self.access_list,
         }
     }
 }
@@ -115,7 +144,7 @@ impl Into<CallRequest> for TransactionParameters {
 pub struct SignedTransaction {
     /// The given message hash
     pub message_hash: H256,
-    /// V value with chain replay protection.
+    /// V value with chain replay protection, the recovery parity (`0` or `1`) for typed transactions.
     pub v: u64,
     /// R value.
     pub r: H256,
@@ -127,6 +156,68 @@ pub struct SignedTransaction {
     pub transaction_hash: H256,
 }
 
+/// A raw signed transaction decoded without a node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedTransaction {
+    /// Transaction type (None for legacy transactions)
+    pub transaction_type: Option<u8>,
+    /// The transaction hash
+    pub hash: H256,
+    /// Sender address recovered from the signature
+    pub from: Address,
+    /// Recipient address (None for contract creation)
+    pub to: Option<Address>,
+    /// Transaction nonce
+    pub nonce: U256,
+    /// Supplied gas
+    pub gas: U256,
+    /// Gas price (None for EIP-1559 transactions)
+    pub gas_price: Option<U256>,
+    /// Maximum total fee per gas (EIP-1559 transactions only)
+    pub max_fee_per_gas: Option<U256>,
+    /// Maximum priority fee per gas (EIP-1559 transactions only)
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// Transfered value
+    pub value: U256,
+    /// Data
+    pub data: Bytes,
+    /// Access list (None for legacy transactions)
+    pub access_list: Option<AccessList>,
+    /// The chain ID (None for legacy transactions without replay protection)
+    pub chain_id: Option<u64>,
+    /// V value, the recovery parity (`0` or `1`) for typed transactions.
+    pub v: u64,
+    /// R value.
+    pub r: H256,
+    /// S value.
+    pub s: H256,
+}
+
+/// A problem found when validating a raw signed transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    /// The input is not a valid (RLP encoded) transaction.
+    InvalidEncoding(String),
+    /// The sender can't be recovered from the signature.
+    InvalidSignature(String),
+    /// The transaction is not bound to any chain (no EIP-155 replay protection).
+    MissingChainId,
+    /// The transaction was signed for another chain.
+    ChainIdMismatch {
+        /// Chain ID of the connected network
+        expected: u64,
+        /// Chain ID of the transaction
+        actual: u64,
+    },
+    /// The supplied gas doesn't cover the intrinsic gas of the transaction.
+    InsufficientGas {
+        /// Intrinsic gas of the transaction
+        required: U256,
+        /// Supplied gas
+        provided: U256,
+    },
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,60 @@
+use crate::types::H256;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A page of contract storage returned by `debug_storageRangeAt`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageRange {
+    /// Storage entries keyed by the hash of the slot key
+    pub storage: BTreeMap<H256, StorageEntry>,
+    /// Key to start the next page from, `None` if this is the last page
+    #[serde(rename = "nextKey")]
+    pub next_key: Option<H256>,
+}
+
+/// A single storage slot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageEntry {
+    /// Slot key (`None` if the node doesn't know the preimage of the hashed key)
+    pub key: Option<H256>,
+    /// Slot value
+    pub value: H256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_deserialize_storage_range() {
+        let range: StorageRange = serde_json::from_str(
+            r#"{
+                "storage": {
+                    "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563": {
+                        "key": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                        "value": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                    },
+                    "0xb10e2d527612073b26eecdfd717e6a320cf44b4afac2b0732d9fcbe2b7fa0cf6": {
+                        "key": null,
+                        "value": "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    }
+                },
+                "nextKey": "0x0000000000000000000000000000000000000000000000000000000000000002"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(range.storage.len(), 2);
+        let first = &range.storage[&"290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"
+            .parse()
+            .unwrap()];
+        assert_eq!(first.key, Some(H256::zero()));
+        assert_eq!(first.value, H256::from_low_u64_be(1));
+        let second = &range.storage[&"b10e2d527612073b26eecdfd717e6a320cf44b4afac2b0732d9fcbe2b7fa0cf6"
+            .parse()
+            .unwrap()];
+        assert_eq!(second.key, None);
+        assert_eq!(second.value, H256::from_low_u64_be(2));
+        assert_eq!(range.next_key, Some(H256::from_low_u64_be(2)));
+    }
+}
@@ -364,4 +364,25 @@ mod tests {
         let _trace: Trace = serde_json::from_str(EXAMPLE_TRACE_SUICIDE).unwrap();
         let _trace: Trace = serde_json::from_str(EXAMPLE_TRACE_REWARD).unwrap();
     }
+
+    #[test]
+    fn test_serialize_trace_filter() {
+        let filter = TraceFilterBuilder::default()
+            .from_block(BlockNumber::Number(1.into()))
+            .to_block(BlockNumber::Latest)
+            .from_address(vec![H160::from_low_u64_be(1)])
+            .to_address(vec![H160::from_low_u64_be(2)])
+            .after(10)
+            .count(5)
+            .build();
+
+        assert_eq!(
+            serde_json::to_string(&filter).unwrap(),
+            r#"{"fromBlock":"0x1","toBlock":"latest","fromAddress":["0x0000000000000000000000000000000000000001"],"toAddress":["0x0000000000000000000000000000000000000002"],"after":10,"count":5}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&TraceFilterBuilder::default().build()).unwrap(),
+            "{}"
+        );
+    }
 }
@@ -30,6 +30,47 @@ pub struct Transaction {
     pub gas: U256,
     /// Input data
     pub input: Bytes,
+    /// Transaction type, None for legacy transactions from older nodes
+    #[serde(rename = "type")]
+    pub transaction_type: Option<U64>,
+    /// ECDSA recovery id
+    pub v: Option<U64>,
+    /// ECDSA signature r, 32 bytes
+    pub r: Option<U256>,
+    /// ECDSA signature s, 32 bytes
+    pub s: Option<U256>,
+    /// Signature parity of typed transactions (0 or 1)
+    #[serde(rename = "yParity")]
+    pub y_parity: Option<U64>,
+}
+
+impl Transaction {
+    /// Returns the signature parity (`0` or `1`).
+    ///
+    /// `yParity` is preferred for typed transactions, otherwise the parity is derived
+    /// from `v` which is either `27`/`28` or `35 + chain_id * 2` (EIP-155) for legacy
+    /// transactions and the parity itself for typed transactions.
+    pub fn signature_parity(&self) -> Option<u64> {
+        let typed = match self.transaction_type {
+            Some(transaction_type) => !transaction_type.is_zero(),
+            None => false,
+        };
+        if let (true, Some(y_parity)) = (typed, self.y_parity) {
+            return Some(y_parity.as_u64());
+        }
+
+        match self.v?.as_u64() {
+            v @ 0..=1 => Some(v),
+            v @ 27..=28 => Some(v - 27),
+            v if v >= 35 => Some((v - 35) % 2),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the transaction creates a contract (has no recipient).
+    pub fn is_contract_creation(&self) -> bool {
+        self.to.is_none()
+    }
 }
 
 /// "Receipt" of an executed transaction: details of its execution.
@@ -62,9 +103,9 @@ pub struct Receipt {
     pub logs: Vec<Log>,
     /// Status: either 1 (success) or 0 (failure).
     pub status: Option<U64>,
-    /// Logs bloom
+    /// Logs bloom. None if not reported by the node.
     #[serde(rename = "logsBloom")]
-    pub logs_bloom: H2048,
+    pub logs_bloom: Option<H2048>,
 }
 
 /// Raw bytes of a signed, but not yet sent transaction
@@ -117,6 +158,7 @@ pub struct RawTransactionDetails {
 mod tests {
     use super::RawTransaction;
     use super::Receipt;
+    use super::Transaction;
     use serde_json;
 
     #[test]
@@ -164,6 +206,82 @@ mod tests {
         let _receipt: Receipt = serde_json::from_str(receipt_str).unwrap();
     }
 
+    #[test]
+    fn should_deserialize_receipt_without_logs_bloom() {
+        let receipt_str = r#"{
+        "blockHash": "0x83eaba432089a0bfe99e9fc9022d1cfcb78f95f407821be81737c84ae0b439c5",
+        "blockNumber": "0x38",
+        "contractAddress": null,
+        "cumulativeGasUsed": "0x927c0",
+        "gasUsed": null,
+        "logs": [],
+        "logsBloom": null,
+        "root": null,
+        "transactionHash": "0x422fb0d5953c0c48cbb42fb58e1c30f5e150441c68374d70ca7d4f191fd56f26",
+        "transactionIndex": "0x0"
+    }"#;
+
+        let receipt: Receipt = serde_json::from_str(receipt_str).unwrap();
+
+        assert_eq!(receipt.gas_used, None);
+        assert_eq!(receipt.logs_bloom, None);
+    }
+
+    #[test]
+    fn should_deserialize_typed_transaction_with_y_parity() {
+        let tx_str = r#"{
+        "blockHash": "0x8a1dbd9f7f9b5fc4d14cf3b6d7f2c1e6d7c8b5a7e4b3c2d1e0f9a8b7c6d5e4f3",
+        "blockNumber": "0xc5d488",
+        "from": "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23",
+        "gas": "0x5208",
+        "gasPrice": "0x4a817c800",
+        "maxFeePerGas": "0x174876e800",
+        "maxPriorityFeePerGas": "0x77359400",
+        "hash": "0x3e5dc2337cdfd8216f10a5dd10eb1165e8437dba6f2e41a6db60108f227b5985",
+        "input": "0xdeadbeef",
+        "nonce": "0x7",
+        "to": "0xf0109fc8df283027b6285cc889f5aa624eac1f55",
+        "transactionIndex": "0x0",
+        "value": "0x3b9aca00",
+        "type": "0x2",
+        "accessList": [],
+        "chainId": "0x1",
+        "v": "0x1",
+        "yParity": "0x0",
+        "r": "0x215fac4c38e39c5d067907b750c0236fe9cde4eca255dbcd64ee65813e1f82b7",
+        "s": "0x1aa9598f7a56e70391f4fce500ab373fbc5a6dd4b1d20ba19ca790e37889996e"
+    }"#;
+
+        let tx: Transaction = serde_json::from_str(tx_str).unwrap();
+
+        assert_eq!(tx.transaction_type, Some(2.into()));
+        assert_eq!(tx.y_parity, Some(0.into()));
+        assert_eq!(tx.signature_parity(), Some(0));
+        assert!(!tx.is_contract_creation());
+    }
+
+    #[test]
+    fn should_deserialize_contract_creation_transaction() {
+        let tx_str = r#"{
+        "blockHash": null,
+        "blockNumber": null,
+        "from": "0x407d73d8a49eeb85d32cf465507dd71d507100c1",
+        "gas": "0x76c0",
+        "gasPrice": "0x9184e72a000",
+        "hash": "0xc6ef2fc5426d6ad6fd9e2a26abeab0aa2411b7ab17f30a99d3cb96aed1d1055b",
+        "input": "0x6060604052",
+        "nonce": "0x0",
+        "to": null,
+        "transactionIndex": null,
+        "value": "0x0"
+    }"#;
+
+        let tx: Transaction = serde_json::from_str(tx_str).unwrap();
+
+        assert_eq!(tx.to, None);
+        assert!(tx.is_contract_creation());
+    }
+
     #[test]
     fn test_deserialize_signed_tx_parity() {
         // taken from RPC docs.
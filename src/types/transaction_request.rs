@@ -1,5 +1,6 @@
-use crate::types::{Address, Bytes, U256};
-use serde::{Deserialize, Serialize};
+use crate::types::{Address, Bytes, H256, U256, U64};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
 
 /// Call contract request (eth_call / eth_estimateGas)
 #[derive(Clone, Debug, PartialEq, Serialize)]
@@ -22,35 +23,188 @@ pub struct CallRequest {
     /// Data (None for empty data)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<Bytes>,
+    /// Addresses and storage keys the call is going to access, EIP-2930 (None for no access list)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "accessList")]
+    pub access_list: Option<AccessList>,
+}
+
+/// Addresses and storage keys a transaction is going to access (EIP-2930).
+pub type AccessList = Vec<AccessListItem>;
+
+/// An address and the storage keys it is going to access (EIP-2930).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccessListItem {
+    /// Accessed address
+    pub address: Address,
+    /// Accessed storage keys
+    #[serde(rename = "storageKeys")]
+    pub storage_keys: Vec<H256>,
+}
+
+/// Access list generated by `eth_createAccessList` along with the gas used by the call with it applied.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccessListWithGasUsed {
+    /// Generated access list
+    #[serde(rename = "accessList")]
+    pub access_list: AccessList,
+    /// Gas used by the call with the access list
+    #[serde(rename = "gasUsed")]
+    pub gas_used: U256,
+}
+
+/// Gas charged for every address of an access list (EIP-2930).
+pub(crate) const ACCESS_LIST_ADDRESS_COST: u64 = 2_400;
+/// Gas charged for every storage key of an access list (EIP-2930).
+pub(crate) const ACCESS_LIST_STORAGE_KEY_COST: u64 = 1_900;
+
+/// Gas needed by a call with and without an access list, see `Eth::compare_access_list`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessListComparison {
+    /// Access list generated for the call
+    pub access_list: AccessList,
+    /// Gas estimated for the call without an access list
+    pub gas_without_access_list: U256,
+    /// Gas estimated for the call with the access list, including the intrinsic cost of the list
+    pub gas_with_access_list: U256,
+}
+
+impl AccessListComparison {
+    /// Returns the intrinsic cost of the access list, paid upfront for warming its entries.
+    pub fn access_list_cost(&self) -> U256 {
+        self.access_list
+            .iter()
+            .map(|item| {
+                let keys = item.storage_keys.len() as u64;
+                U256::from(ACCESS_LIST_ADDRESS_COST + keys * ACCESS_LIST_STORAGE_KEY_COST)
+            })
+            .fold(U256::zero(), |total, cost| total + cost)
+    }
+
+    /// Returns `true` if sending the call with the access list reduces the total gas.
+    pub fn saves_gas(&self) -> bool {
+        self.gas_with_access_list < self.gas_without_access_list
+    }
+}
+
+impl CallRequest {
+    /// Sets the sender of the call.
+    pub fn with_sender(mut self, sender: CallSender) -> Self {
+        self.from = sender.into();
+        self
+    }
+}
+
+/// Sender used for calls which don't depend on `msg.sender`.
+///
+/// Most nodes (e.g. Geth) execute calls without `from` as if sent from the zero
+/// address. Some providers require the field to be present, while others
+/// reject the zero address as a sender, in which case an explicit (funded)
+/// address has to be used.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CallSender {
+    /// Omit `from` from the request.
+    Omit,
+    /// Use the zero address.
+    Zero,
+    /// Use given address.
+    Address(Address),
+}
+
+impl From<CallSender> for Option<Address> {
+    fn from(sender: CallSender) -> Self {
+        match sender {
+            CallSender::Omit => None,
+            CallSender::Zero => Some(Address::zero()),
+            CallSender::Address(address) => Some(address),
+        }
+    }
 }
 
 /// Send Transaction Parameters
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TransactionRequest {
     /// Sender address
     pub from: Address,
     /// Recipient address (None for contract creation)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub to: Option<Address>,
     /// Supplied gas (None for sensible default)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub gas: Option<U256>,
     /// Gas price (None for sensible default)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "gasPrice")]
     pub gas_price: Option<U256>,
+    /// Max fee per gas, EIP-1559 (None for legacy or sensible default)
+    pub max_fee_per_gas: Option<U256>,
+    /// Max priority fee per gas, EIP-1559 (None for legacy or sensible default)
+    pub max_priority_fee_per_gas: Option<U256>,
     /// Transfered value (None for no transfer)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<U256>,
     /// Transaction data (None for empty bytes)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<Bytes>,
     /// Transaction nonce (None for next available nonce)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<U256>,
     /// Min block inclusion (None for include immediately)
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub condition: Option<TransactionCondition>,
+    /// Addresses and storage keys the transaction is going to access, EIP-2930 (None for no access list)
+    pub access_list: Option<AccessList>,
+    /// Transaction type, EIP-2718 (None to infer from the fee fields)
+    pub transaction_type: Option<U64>,
+}
+
+impl TransactionRequest {
+    /// Returns the type the request is sent with.
+    ///
+    /// Unless set explicitly, requests specifying EIP-1559 fees are sent as
+    /// dynamic-fee transactions (type 2), requests with only an access list as
+    /// access list transactions (type 1), others are left to the node.
+    pub fn effective_type(&self) -> Option<U64> {
+        self.transaction_type.or_else(|| {
+            if self.max_fee_per_gas.is_some() || self.max_priority_fee_per_gas.is_some() {
+                Some(DYNAMIC_FEE_TX_TYPE.into())
+            } else if self.access_list.is_some() {
+                Some(ACCESS_LIST_TX_TYPE.into())
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// EIP-2718 type of EIP-1559 dynamic-fee transactions.
+const DYNAMIC_FEE_TX_TYPE: u64 = 2;
+
+/// EIP-2718 type of EIP-2930 access list transactions.
+const ACCESS_LIST_TX_TYPE: u64 = 1;
+
+impl Serialize for TransactionRequest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("TransactionRequest", 12)?;
+        state.serialize_field("from", &self.from)?;
+
+        macro_rules! optional {
+            ($name: expr, $value: expr) => {
+                match $value {
+                    Some(ref value) => state.serialize_field($name, value)?,
+                    None => state.skip_field($name)?,
+                }
+            };
+        }
+
+        optional!("to", self.to);
+        optional!("gas", self.gas);
+        optional!("gasPrice", self.gas_price);
+        optional!("maxFeePerGas", self.max_fee_per_gas);
+        optional!("maxPriorityFeePerGas", self.max_priority_fee_per_gas);
+        optional!("value", self.value);
+        optional!("data", self.data);
+        optional!("nonce", self.nonce);
+        optional!("condition", self.condition);
+        optional!("accessList", self.access_list);
+        optional!("type", self.effective_type());
+        state.end()
+    }
 }
 
 /// Represents condition on minimum block number or block timestamp.
@@ -65,9 +219,25 @@ pub enum TransactionCondition {
     Timestamp(u64),
 }
 
+/// Transaction condition serialized as explicit `null` when unset.
+///
+/// `TransactionRequest` omits the `condition` field when there is no condition,
+/// but some clients require an explicit `null` to clear a previously set one.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct ExplicitCondition(pub Option<TransactionCondition>);
+
+impl From<Option<TransactionCondition>> for ExplicitCondition {
+    fn from(condition: Option<TransactionCondition>) -> Self {
+        ExplicitCondition(condition)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Address, CallRequest, TransactionCondition, TransactionRequest};
+    use super::{
+        AccessListItem, Address, CallRequest, CallSender, ExplicitCondition, TransactionCondition, TransactionRequest,
+    };
     use serde_json;
 
     #[test]
@@ -80,6 +250,7 @@ mod tests {
             gas_price: None,
             value: Some(5_000_000.into()),
             data: Some(vec![1, 2, 3].into()),
+            access_list: None,
         };
 
         // when
@@ -97,6 +268,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_serialize_call_request_sender() {
+        // given
+        let call_request = CallRequest {
+            from: None,
+            to: Address::from_low_u64_be(5),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: None,
+            access_list: None,
+        };
+
+        // when
+        let omit = serde_json::to_string(&call_request.clone().with_sender(CallSender::Omit)).unwrap();
+        let zero = serde_json::to_string(&call_request.clone().with_sender(CallSender::Zero)).unwrap();
+        let address =
+            serde_json::to_string(&call_request.with_sender(CallSender::Address(Address::from_low_u64_be(1)))).unwrap();
+
+        // then
+        assert_eq!(omit, r#"{"to":"0x0000000000000000000000000000000000000005"}"#);
+        assert_eq!(
+            zero,
+            r#"{"from":"0x0000000000000000000000000000000000000000","to":"0x0000000000000000000000000000000000000005"}"#
+        );
+        assert_eq!(
+            address,
+            r#"{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000005"}"#
+        );
+    }
+
     #[test]
     fn should_serialize_transaction_request() {
         // given
@@ -105,10 +307,14 @@ mod tests {
             to: None,
             gas: Some(21_000.into()),
             gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
             value: Some(5_000_000.into()),
             data: Some(vec![1, 2, 3].into()),
             nonce: None,
             condition: Some(TransactionCondition::Block(5)),
+            access_list: None,
+            transaction_type: None,
         };
 
         // when
@@ -128,4 +334,85 @@ mod tests {
 }"#
         );
     }
+
+    #[test]
+    fn should_serialize_explicit_condition() {
+        let clear = ExplicitCondition(None);
+        let set = ExplicitCondition::from(Some(TransactionCondition::Timestamp(10)));
+
+        assert_eq!(serde_json::to_string(&clear).unwrap(), "null");
+        assert_eq!(serde_json::to_string(&set).unwrap(), r#"{"time":10}"#);
+
+        let tx_request = TransactionRequest {
+            from: Address::from_low_u64_be(5),
+            to: None,
+            gas: None,
+            gas_price: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            value: None,
+            data: None,
+            nonce: None,
+            condition: None,
+            access_list: None,
+            transaction_type: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&tx_request).unwrap(),
+            r#"{"from":"0x0000000000000000000000000000000000000005"}"#
+        );
+    }
+
+    #[test]
+    fn should_serialize_dynamic_fee_transaction_type() {
+        let mut tx_request = TransactionRequest {
+            from: Address::from_low_u64_be(5),
+            to: None,
+            gas: None,
+            gas_price: None,
+            max_fee_per_gas: Some(100.into()),
+            max_priority_fee_per_gas: Some(2.into()),
+            value: None,
+            data: None,
+            nonce: None,
+            condition: None,
+            access_list: None,
+            transaction_type: None,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&tx_request).unwrap(),
+            r#"{"from":"0x0000000000000000000000000000000000000005","maxFeePerGas":"0x64","maxPriorityFeePerGas":"0x2","type":"0x2"}"#
+        );
+
+        tx_request.transaction_type = Some(1.into());
+        let serialized = serde_json::to_string(&tx_request).unwrap();
+        assert!(serialized.ends_with(r#","type":"0x1"}"#));
+    }
+
+    #[test]
+    fn should_serialize_access_list_transaction_type() {
+        let tx_request = TransactionRequest {
+            from: Address::from_low_u64_be(5),
+            to: None,
+            gas: None,
+            gas_price: Some(1.into()),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            value: None,
+            data: None,
+            nonce: None,
+            condition: None,
+            access_list: Some(vec![AccessListItem {
+                address: Address::from_low_u64_be(6),
+                storage_keys: vec![],
+            }]),
+            transaction_type: None,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&tx_request).unwrap(),
+            r#"{"from":"0x0000000000000000000000000000000000000005","gasPrice":"0x1","accessList":[{"address":"0x0000000000000000000000000000000000000006","storageKeys":[]}],"type":"0x1"}"#
+        );
+    }
 }
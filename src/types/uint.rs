@@ -1,5 +1,84 @@
 pub use ethereum_types::{BigEndianHash, Bloom as H2048, H128, H160, H256, H512, H520, H64, U128, U256, U64};
 
+/// Generation of random values, mostly useful in tests.
+#[cfg(feature = "rand")]
+pub trait Random: Sized {
+    /// Creates a random value using the thread-local random number generator.
+    fn random() -> Self {
+        Self::random_using(&mut rand::thread_rng())
+    }
+
+    /// Creates a random value using given random number generator.
+    fn random_using<R: rand::Rng + ?Sized>(rng: &mut R) -> Self;
+}
+
+#[cfg(feature = "rand")]
+macro_rules! impl_random_for_hash {
+    ($($name:ident),*) => {
+        $(
+            impl Random for $name {
+                fn random_using<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+                    let mut hash = $name::zero();
+                    rng.fill_bytes(hash.as_bytes_mut());
+                    hash
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "rand")]
+impl_random_for_hash!(H64, H128, H160, H256, H512, H520, H2048);
+
+#[cfg(feature = "rand")]
+impl Random for U256 {
+    fn random_using<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        U256::from_big_endian(&bytes)
+    }
+}
+
+/// Formats `value` expressed in the smallest unit as a decimal number with `decimals` decimal places,
+/// e.g. `format_units(1_234_500_000_000_000_000u64.into(), 18, None)` gives `"1.2345"`.
+///
+/// Trailing zeros of the fractional part are trimmed. When `precision` is given, the
+/// fractional part is rounded (half up) to at most `precision` digits.
+pub fn format_units(value: U256, decimals: u8, precision: Option<usize>) -> String {
+    let decimals = decimals as usize;
+    let (value, decimals) = match precision {
+        Some(precision) if precision < decimals => {
+            let shift = decimals - precision;
+            let value = match U256::from(10).checked_pow(shift.into()) {
+                Some(scale) => {
+                    let (quotient, remainder) = value.div_mod(scale);
+                    if remainder >= scale - remainder {
+                        quotient + 1
+                    } else {
+                        quotient
+                    }
+                }
+                // the value is smaller than half of the scale, so it's rounded to zero
+                None => U256::zero(),
+            };
+            (value, precision)
+        }
+        _ => (value, decimals),
+    };
+
+    let mut digits = value.to_string();
+    if digits.len() <= decimals {
+        digits = "0".repeat(decimals + 1 - digits.len()) + &digits;
+    }
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_owned()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -7,6 +86,49 @@ mod tests {
 
     type Res = Result<U256, serde_json::Error>;
 
+    #[cfg(feature = "rand")]
+    #[test]
+    fn should_generate_deterministic_random_values() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let a = H256::random_using(&mut rng);
+        let b = H160::random_using(&mut rng);
+        let c = U256::random_using(&mut rng);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        assert_eq!(H256::random_using(&mut rng), a);
+        assert_eq!(H160::random_using(&mut rng), b);
+        assert_eq!(U256::random_using(&mut rng), c);
+        assert_ne!(H256::random_using(&mut rng), a);
+        assert_ne!(H256::random(), H256::random());
+    }
+
+    #[test]
+    fn should_format_whole_units() {
+        assert_eq!(format_units(U256::exp10(18), 18, None), "1");
+        assert_eq!(format_units(U256::from(42) * U256::exp10(18), 18, Some(2)), "42");
+        assert_eq!(format_units(U256::zero(), 18, None), "0");
+        assert_eq!(format_units(U256::from(1234), 0, None), "1234");
+    }
+
+    #[test]
+    fn should_format_fractional_units() {
+        assert_eq!(format_units(U256::from(12_345) * U256::exp10(14), 18, None), "1.2345");
+        assert_eq!(format_units(U256::from(1), 18, None), "0.000000000000000001");
+        assert_eq!(format_units(U256::from(1_500_000), 6, None), "1.5");
+    }
+
+    #[test]
+    fn should_round_formatted_units() {
+        let unit = U256::exp10(13);
+        assert_eq!(format_units(U256::from(123_456) * unit, 18, Some(4)), "1.2346");
+        assert_eq!(format_units(U256::from(123_454) * unit, 18, Some(4)), "1.2345");
+        assert_eq!(format_units(U256::from(199_996) * unit, 18, Some(4)), "2");
+        assert_eq!(format_units(U256::from(1), 18, Some(4)), "0");
+        assert_eq!(format_units(U256::MAX, 255, Some(2)), "0");
+    }
+
     #[test]
     fn should_compare_correctly() {
         let mut arr = [0u8; 32];
@@ -0,0 +1,48 @@
+//! Allocations made when serializing large `Bytes` payloads into request parameters.
+//!
+//! Lives in its own test binary, as it installs a counting global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use web3::helpers;
+use web3::types::Bytes;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.with(|allocated| allocated.set(allocated.get() + layout.size()));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocated() -> usize {
+    ALLOCATED.with(Cell::get)
+}
+
+#[test]
+fn should_serialize_large_payload_into_request_parameter_without_extra_copies() {
+    let bytes = Bytes((0..256 * 1024).map(|i| i as u8).collect());
+    let encoded_len = 2 + bytes.0.len() * 2;
+
+    let before = allocated();
+    let value = helpers::serialize(&bytes);
+    let after = allocated();
+
+    // the hex encoding and the string of the resulting JSON value, plus some slack for
+    // small bookkeeping allocations, but well below a third copy of the payload
+    assert!(after - before <= 2 * encoded_len + 4096);
+    assert_eq!(value.as_str().map(str::len), Some(encoded_len));
+    assert_eq!(serde_json::from_value::<Bytes>(value).unwrap(), bytes);
+}